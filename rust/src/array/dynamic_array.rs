@@ -4,9 +4,9 @@ pub struct DynamicArray<T> {
     growth_factor: usize,
 }
 
-impl<T: Clone> DynamicArray<T> {
+impl<T> DynamicArray<T> {
     pub fn new(initial_capacity: usize) -> Result<DynamicArray<T>, String> {
-        if initial_capacity <= 0 {
+        if initial_capacity == 0 {
             return Err("Capacity must be a positive integer.".to_owned());
         }
         Ok(DynamicArray {
@@ -41,7 +41,7 @@ impl<T: Clone> DynamicArray<T> {
     pub fn append(&mut self, element: T) -> Result<(), String> {
         self.ensure_capacity();
         // self.items.push(element);
-        self.add_at(self.get_size() - 1, element)?;
+        self.add_at(self.get_size(), element)?;
         Ok(())
     }
 
@@ -83,6 +83,11 @@ impl<T: Clone> DynamicArray<T> {
         Ok(self.items.get(index))
     }
 
+    pub fn get_mut(&mut self, index: usize) -> Result<Option<&mut T>, String> {
+        self.check_index(index)?;
+        Ok(self.items.get_mut(index))
+    }
+
     pub fn set(&mut self, index: usize, element: T) -> Result<(), String> {
         self.check_index(index)?;
         self.items[index] = element;
@@ -10,13 +10,13 @@ pub struct FixedArray<T> {
 
 impl<T> FixedArray<T> {
     pub fn new(capacity: usize) -> Result<FixedArray<T>, String> {
-        if capacity <= 0 {
+        if capacity == 0 {
             return Err("Capacity must be a positive integer.".to_owned());
         }
-        return Ok(FixedArray {
+        Ok(FixedArray {
             items: Vec::with_capacity(capacity),
             capacity,
-        });
+        })
     }
 
     fn check_index(&self, index: usize) -> Result<(), String> {
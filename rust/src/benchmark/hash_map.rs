@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hint::black_box};
+
+use super::{BenchResult, bench};
+use crate::hash_table::flat_hash_map::FlatHashMap;
+
+// 相同的插入/查找/删除负载分别跑一遍 FlatHashMap 和标准库 HashMap
+pub fn compare_with_std(size: usize, iterations: usize) -> Vec<BenchResult> {
+    let keys: Vec<u64> = (0..size as u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect();
+    let mut results = Vec::new();
+
+    results.push(bench("FlatHashMap insert", iterations, || {
+        let mut map = FlatHashMap::new();
+        for &key in keys.iter() {
+            map.insert(key, key);
+        }
+        black_box(map);
+    }));
+    results.push(bench("HashMap insert", iterations, || {
+        let mut map = HashMap::new();
+        for &key in keys.iter() {
+            map.insert(key, key);
+        }
+        black_box(map);
+    }));
+
+    let flat: FlatHashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+    let std_map: HashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+    results.push(bench("FlatHashMap get", iterations, || {
+        for key in keys.iter() {
+            black_box(flat.get(key));
+        }
+    }));
+    results.push(bench("HashMap get", iterations, || {
+        for key in keys.iter() {
+            black_box(std_map.get(key));
+        }
+    }));
+
+    results.push(bench("FlatHashMap remove", iterations, || {
+        let mut map: FlatHashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        for key in keys.iter() {
+            black_box(map.remove(key));
+        }
+    }));
+    results.push(bench("HashMap remove", iterations, || {
+        let mut map: HashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        for key in keys.iter() {
+            black_box(map.remove(key));
+        }
+    }));
+
+    results.push(bench("FlatHashMap iter", iterations, || {
+        black_box(flat.values().fold(0u64, |acc, v| acc.wrapping_add(*v)));
+    }));
+    results.push(bench("HashMap iter", iterations, || {
+        black_box(std_map.values().fold(0u64, |acc, v| acc.wrapping_add(*v)));
+    }));
+    results
+}
@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+pub mod hash_map;
+pub mod strings;
+pub mod text;
+pub mod tree;
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub name: String,
+    pub iterations: usize,
+    pub total: Duration,
+}
+
+impl BenchResult {
+    pub fn per_iteration(&self) -> Duration {
+        if self.iterations == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.iterations as u32
+    }
+}
+
+// 先预热一次，再重复执行 iterations 次并统计总耗时
+pub fn bench<F>(name: &str, iterations: usize, mut f: F) -> BenchResult
+where
+    F: FnMut(),
+{
+    f();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    BenchResult {
+        name: name.to_owned(),
+        iterations,
+        total: start.elapsed(),
+    }
+}
+
+pub fn report(results: &[BenchResult]) {
+    let width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    for result in results {
+        println!(
+            "{:<width$}  {:>8} iters  {:>12?} total  {:>12?} / iter",
+            result.name,
+            result.iterations,
+            result.total,
+            result.per_iteration(),
+            width = width
+        );
+    }
+}
@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+
+use std::hint::black_box;
+
+use super::{BenchResult, bench};
+use crate::strings::{
+    horspool::Horspool, kmp::Kmp, matcher::Matcher, rabin_karp::RabinKarp, z_function::ZMatcher,
+};
+use crate::trie::aho_corasick::{AhoCorasick, MatchKind};
+
+fn bench_matcher<M: Matcher>(matcher: &M, haystack: &[u8], iterations: usize) -> BenchResult {
+    bench(matcher.get_name(), iterations, || {
+        black_box(matcher.count(haystack));
+    })
+}
+
+// 在伪随机的小字母表文本上，用同一个模式串统计所有匹配
+pub fn compare_matchers(
+    text_len: usize,
+    pattern: &[u8],
+    alphabet: u8,
+    iterations: usize,
+) -> Vec<BenchResult> {
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let haystack: Vec<u8> = (0..text_len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            b'a' + (state % alphabet.max(1) as u64) as u8
+        })
+        .collect();
+    let rabin_karp = RabinKarp::new([pattern]).expect("Default modulus and base are valid.");
+    let aho_corasick = AhoCorasick::new([pattern], MatchKind::Overlapping);
+    vec![
+        bench_matcher(&Kmp::new(pattern), &haystack, iterations),
+        bench_matcher(&ZMatcher::new(pattern), &haystack, iterations),
+        bench_matcher(&Horspool::new(pattern), &haystack, iterations),
+        bench_matcher(&rabin_karp, &haystack, iterations),
+        bench_matcher(&aho_corasick, &haystack, iterations),
+    ]
+}
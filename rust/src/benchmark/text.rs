@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+use std::hint::black_box;
+
+use super::{BenchResult, bench};
+use crate::text::{TextBuffer, gap_buffer::GapBuffer, piece_table::PieceTable, rope::Rope};
+
+// 模拟编辑器：在一个光标附近连续输入，偶尔退格，偶尔跳到文档的另一个位置
+fn edit_session<B: TextBuffer>(buffer: &mut B, edits: usize) {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut cursor = buffer.get_char_len() / 2;
+    for _ in 0..edits {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        match state % 100 {
+            0 => cursor = (state >> 8) as usize % (buffer.get_char_len() + 1),
+            1..=10 if cursor > 0 => {
+                buffer.remove(cursor - 1..cursor).unwrap();
+                cursor -= 1;
+            }
+            _ => {
+                buffer.insert(cursor, "x").unwrap();
+                cursor += 1;
+            }
+        }
+    }
+}
+
+pub fn compare_text_buffers(
+    initial_len: usize,
+    edits: usize,
+    iterations: usize,
+) -> Vec<BenchResult> {
+    let initial: String = (0..initial_len)
+        .map(|i| if i % 80 == 79 { '\n' } else { 'a' })
+        .collect();
+    vec![
+        bench("GapBuffer edits", iterations, || {
+            let mut buffer = GapBuffer::from(initial.as_str());
+            edit_session(&mut buffer, edits);
+            black_box(buffer);
+        }),
+        bench("PieceTable edits", iterations, || {
+            let mut buffer = PieceTable::from(initial.as_str());
+            edit_session(&mut buffer, edits);
+            black_box(buffer);
+        }),
+        bench("Rope edits", iterations, || {
+            let mut buffer = Rope::from(initial.as_str());
+            edit_session(&mut buffer, edits);
+            black_box(buffer);
+        }),
+    ]
+}
@@ -0,0 +1,89 @@
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+use crate::tree::{
+    avl_map::AvlMap, bst_map::BstMap, llrb_map::LlrbMap, ordered_map::OrderedMap,
+    red_black_map::RedBlackMap,
+};
+
+#[derive(Debug, Clone)]
+pub struct TreeStats {
+    pub name: &'static str,
+    pub workload: &'static str,
+    pub size: usize,
+    pub height: usize,
+    pub rotations: usize,
+    pub elapsed: Duration,
+}
+
+// 先按顺序插入 inserts，再按顺序删除 removals，记录最终的树高和累计旋转次数
+fn run_workload<M>(workload: &'static str, inserts: &[u64], removals: &[u64]) -> TreeStats
+where
+    M: OrderedMap<u64, u64> + Default,
+{
+    let mut map = M::default();
+    let start = Instant::now();
+    for &key in inserts {
+        map.insert(key, key);
+    }
+    for key in removals {
+        map.remove(key);
+    }
+    let elapsed = start.elapsed();
+    debug_assert!(map.check_invariants().is_ok());
+    TreeStats {
+        name: map.get_name(),
+        workload,
+        size: map.get_size(),
+        height: map.height(),
+        rotations: map.get_rotation_count(),
+        elapsed,
+    }
+}
+
+fn run_all(workload: &'static str, inserts: &[u64], removals: &[u64]) -> Vec<TreeStats> {
+    vec![
+        run_workload::<BstMap<u64, u64>>(workload, inserts, removals),
+        run_workload::<AvlMap<u64, u64>>(workload, inserts, removals),
+        run_workload::<RedBlackMap<u64, u64>>(workload, inserts, removals),
+        run_workload::<LlrbMap<u64, u64>>(workload, inserts, removals),
+    ]
+}
+
+// 顺序插入会让普通二叉搜索树退化成链表，耗时是 O(n²)，size 不宜太大
+pub fn compare_ordered_maps(size: usize) -> Vec<TreeStats> {
+    let sequential: Vec<u64> = (0..size as u64).collect();
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let random: Vec<u64> = (0..size)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect();
+    let half: Vec<u64> = random.iter().copied().step_by(2).collect();
+
+    let mut results = Vec::new();
+    results.extend(run_all("sequential insert", &sequential, &[]));
+    results.extend(run_all("random insert", &random, &[]));
+    results.extend(run_all("random insert, remove half", &random, &half));
+    results
+}
+
+pub fn report_tree_stats(results: &[TreeStats]) {
+    let width = results.iter().map(|r| r.workload.len()).max().unwrap_or(0);
+    for result in results {
+        println!(
+            "{:<width$}  {:<10}  {:>8} keys  height {:>6}  {:>10} rotations  {:>12?}",
+            result.workload,
+            result.name,
+            result.size,
+            result.height,
+            result.rotations,
+            result.elapsed,
+            width = width
+        );
+    }
+}
@@ -0,0 +1,395 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, fmt::Debug, hash::Hash};
+
+use super::{CacheStats, EvictionCallback};
+use crate::hash_table::flat_hash_map::FlatHashMap;
+use crate::linked_list::double_linked_list::{DoubleLinkedList, DoubleLinkedNodeRef};
+
+struct Entry<K, V> {
+    value: V,
+    frequency: usize,
+    node: DoubleLinkedNodeRef<K>,
+}
+
+struct Bucket<K> {
+    items: DoubleLinkedList<K>,
+    // 相邻的更低、更高的非空频率
+    lower: Option<usize>,
+    higher: Option<usize>,
+}
+
+// 每个访问频率对应一条链表，同一频率内按最近使用排序；各频率的链表再按频率从小到大串起来，
+// 最小频率就是这条链的头，淘汰时取它的头部，所有操作都是 O(1)
+pub struct LfuCache<K, V> {
+    entries: FlatHashMap<K, Entry<K, V>>,
+    buckets: FlatHashMap<usize, Bucket<K>>,
+    min_frequency: Option<usize>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K, V> LfuCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> Result<LfuCache<K, V>, String> {
+        if capacity == 0 {
+            return Err("Capacity must be a positive integer.".to_owned());
+        }
+        Ok(LfuCache {
+            entries: FlatHashMap::with_capacity(capacity),
+            buckets: FlatHashMap::new(),
+            min_frequency: None,
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        })
+    }
+
+    pub fn set_eviction_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.entries.get_size()
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.get_size() >= self.capacity
+    }
+
+    pub fn get_stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hit = self.touch(key);
+        self.stats.record(hit);
+        if !hit {
+            return None;
+        }
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hit = self.touch(key);
+        self.stats.record(hit);
+        if !hit {
+            return None;
+        }
+        self.entries.get_mut(key).map(|entry| &mut entry.value)
+    }
+
+    // 只查看，不增加访问频率也不计入命中统计
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    pub fn frequency<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key).map(|entry| entry.frequency)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.contains_key(key)
+    }
+
+    // 更新已有的 key 也算一次访问
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            let old = std::mem::replace(&mut entry.value, value);
+            self.touch(&key);
+            return Some(old);
+        }
+        if self.is_full()
+            && let Some((key, value)) = self.pop_lfu()
+            && let Some(callback) = self.on_evict.as_mut()
+        {
+            callback(key, value);
+        }
+        let node = self.bucket_after(None, 1).insert_at_tail(key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                frequency: 1,
+                node,
+            },
+        );
+        None
+    }
+
+    // 频率最低的项中最久未使用的那个
+    pub fn pop_lfu(&mut self) -> Option<(K, V)> {
+        let node = self
+            .buckets
+            .get(&self.min_frequency?)?
+            .items
+            .get_head_node()?;
+        let key = (*node).borrow().data.clone();
+        drop(node);
+        self.remove_entry(&key)
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, entry) = self.entries.remove_entry(key)?;
+        self.detach(&entry.node, entry.frequency);
+        Some((key, entry.value))
+    }
+
+    pub fn resize(&mut self, capacity: usize) -> Result<(), String> {
+        if capacity == 0 {
+            return Err("Capacity must be a positive integer.".to_owned());
+        }
+        self.capacity = capacity;
+        while self.entries.get_size() > self.capacity {
+            if let Some((key, value)) = self.pop_lfu()
+                && let Some(callback) = self.on_evict.as_mut()
+            {
+                callback(key, value);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.buckets.clear();
+        self.min_frequency = None;
+    }
+
+    // 无序遍历
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, entry)| (k, &entry.value))
+    }
+
+    // 频率为 frequency 的链表不存在时，把它接在频率 lower 之后（None 表示接在最前面）
+    fn bucket_after(&mut self, lower: Option<usize>, frequency: usize) -> &mut DoubleLinkedList<K> {
+        if !self.buckets.contains_key(&frequency) {
+            let higher = match lower {
+                None => self.min_frequency.replace(frequency),
+                Some(lower) => self
+                    .buckets
+                    .get_mut(&lower)
+                    .unwrap()
+                    .higher
+                    .replace(frequency),
+            };
+            if let Some(higher) = higher {
+                self.buckets.get_mut(&higher).unwrap().lower = Some(frequency);
+            }
+            let bucket = Bucket {
+                items: DoubleLinkedList::new(),
+                lower,
+                higher,
+            };
+            self.buckets.insert(frequency, bucket);
+        }
+        &mut self.buckets.get_mut(&frequency).unwrap().items
+    }
+
+    // 链表空了就从频率链上摘掉，摘掉的是头部时最小频率顺延到下一个
+    fn detach(&mut self, node: &DoubleLinkedNodeRef<K>, frequency: usize) {
+        let bucket = self.buckets.get_mut(&frequency).unwrap();
+        bucket.items.unlink(node);
+        if !bucket.items.is_empty() {
+            return;
+        }
+        let Bucket { lower, higher, .. } = self.buckets.remove(&frequency).unwrap();
+        match lower {
+            None => self.min_frequency = higher,
+            Some(lower) => self.buckets.get_mut(&lower).unwrap().higher = higher,
+        }
+        if let Some(higher) = higher {
+            self.buckets.get_mut(&higher).unwrap().lower = lower;
+        }
+    }
+
+    // 把 key 从频率 f 的链表移到 f + 1 的链表尾部
+    fn touch<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (node, frequency) = match self.entries.get_mut(key) {
+            None => return false,
+            Some(entry) => {
+                entry.frequency += 1;
+                (entry.node.clone(), entry.frequency - 1)
+            }
+        };
+        // 先接上 f + 1 的链表，f 的链表随后可能被摘掉
+        self.bucket_after(Some(frequency), frequency + 1);
+        self.detach(&node, frequency);
+        self.buckets
+            .get_mut(&(frequency + 1))
+            .unwrap()
+            .items
+            .link_at_tail(node);
+        true
+    }
+}
+
+impl<K, V> Debug for LfuCache<K, V>
+where
+    K: Hash + Eq + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LfuCache")
+            .field("capacity", &self.capacity)
+            .field("entries", &self.iter().collect::<Vec<_>>())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    // 频率链从最小频率开始严格递增、前后指针一致，并且覆盖所有非空链表
+    fn check_buckets(cache: &LfuCache<u64, u64>) {
+        let mut frequencies: Vec<usize> = cache.buckets.keys().copied().collect();
+        frequencies.sort_unstable();
+        let mut chain = Vec::new();
+        let mut lower = None;
+        let mut current = cache.min_frequency;
+        while let Some(frequency) = current {
+            let bucket = cache.buckets.get(&frequency).unwrap();
+            assert_eq!(bucket.lower, lower);
+            assert!(!bucket.items.is_empty());
+            chain.push(frequency);
+            lower = current;
+            current = bucket.higher;
+        }
+        assert_eq!(chain, frequencies);
+        let items: usize = cache.buckets.values().map(|b| b.items.get_size()).sum();
+        assert_eq!(items, cache.get_size());
+    }
+
+    #[test]
+    fn random_operations_match_model() {
+        let mut rng = TestRng::with_seed(29);
+        let mut cache = LfuCache::new(8).unwrap();
+        // key -> (value, frequency, 进入当前频率链表的时刻)
+        let mut model: Vec<(u64, u64, usize, usize)> = Vec::new();
+        for tick in 0..3000 {
+            let key = rng.below(16);
+            let position = model.iter().position(|&(k, ..)| k == key);
+            match rng.below(5) {
+                0 | 1 => {
+                    let value = rng.next_u64();
+                    let expected = match position {
+                        Some(i) => {
+                            let old = model[i].1;
+                            model[i] = (key, value, model[i].2 + 1, tick);
+                            Some(old)
+                        }
+                        None => {
+                            if model.len() == 8 {
+                                let victim = (0..model.len())
+                                    .min_by_key(|&i| (model[i].2, model[i].3))
+                                    .unwrap();
+                                model.remove(victim);
+                            }
+                            model.push((key, value, 1, tick));
+                            None
+                        }
+                    };
+                    assert_eq!(cache.put(key, value), expected);
+                }
+                2 => {
+                    let expected = position.map(|i| {
+                        model[i].2 += 1;
+                        model[i].3 = tick;
+                        model[i].1
+                    });
+                    assert_eq!(cache.get(&key).copied(), expected);
+                }
+                3 => {
+                    let expected = position.map(|i| model.remove(i).1);
+                    assert_eq!(cache.remove(&key), expected);
+                }
+                _ => {
+                    let expected = (0..model.len())
+                        .min_by_key(|&i| (model[i].2, model[i].3))
+                        .map(|i| model.remove(i))
+                        .map(|(k, v, ..)| (k, v));
+                    assert_eq!(cache.pop_lfu(), expected);
+                }
+            }
+            for &(k, _, frequency, _) in model.iter() {
+                assert_eq!(cache.frequency(&k), Some(frequency));
+            }
+            assert_eq!(cache.get_size(), model.len());
+            check_buckets(&cache);
+        }
+    }
+
+    #[test]
+    fn remove_last_entry_of_min_frequency() {
+        let mut cache = LfuCache::new(3).unwrap();
+        cache.put(1, 1);
+        cache.put(2, 2);
+        cache.get(&2);
+        cache.put(3, 3);
+        cache.get(&3);
+        cache.get(&3);
+        assert_eq!(cache.remove(&1), Some(1));
+        assert_eq!(cache.min_frequency, Some(2));
+        assert_eq!(cache.pop_lfu(), Some((2, 2)));
+        assert_eq!(cache.pop_lfu(), Some((3, 3)));
+        assert_eq!(cache.pop_lfu(), None);
+        assert_eq!(cache.min_frequency, None);
+    }
+}
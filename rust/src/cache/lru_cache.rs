@@ -0,0 +1,276 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, fmt::Debug, hash::Hash};
+
+use super::{CacheStats, EvictionCallback};
+use crate::hash_table::linked_hash_map::LinkedHashMap;
+
+// 链表头部是最久未使用的项，尾部是最近使用的项
+pub struct LruCache<K, V> {
+    entries: LinkedHashMap<K, V>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> Result<LruCache<K, V>, String> {
+        if capacity == 0 {
+            return Err("Capacity must be a positive integer.".to_owned());
+        }
+        Ok(LruCache {
+            entries: LinkedHashMap::with_capacity(capacity),
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        })
+    }
+
+    pub fn set_eviction_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.entries.get_size()
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.get_size() >= self.capacity
+    }
+
+    pub fn get_stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    // 命中时会把该项标记为最近使用
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hit = self.entries.to_back(key);
+        self.stats.record(hit);
+        if !hit {
+            return None;
+        }
+        self.entries.get(key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hit = self.entries.to_back(key);
+        self.stats.record(hit);
+        if !hit {
+            return None;
+        }
+        self.entries.get_mut(key)
+    }
+
+    // 只查看，不影响淘汰顺序和命中统计
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key)
+    }
+
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.entries.front()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.contains_key(key)
+    }
+
+    // 返回被替换掉的旧值，容量已满时淘汰最久未使用的项
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if self.entries.contains_key(&key) {
+            return self.entries.insert_to_back(key, value);
+        }
+        if self.is_full() {
+            self.evict();
+        }
+        self.entries.insert(key, value);
+        None
+    }
+
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.entries.pop_front()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.remove(key)
+    }
+
+    pub fn resize(&mut self, capacity: usize) -> Result<(), String> {
+        if capacity == 0 {
+            return Err("Capacity must be a positive integer.".to_owned());
+        }
+        self.capacity = capacity;
+        while self.entries.get_size() > self.capacity {
+            self.evict();
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn evict(&mut self) {
+        if let Some((key, value)) = self.entries.pop_front()
+            && let Some(callback) = self.on_evict.as_mut()
+        {
+            callback(key, value);
+        }
+    }
+
+    // 从最久未使用到最近使用
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+        self.entries.iter()
+    }
+}
+
+impl<K, V> Debug for LruCache<K, V>
+where
+    K: Hash + Eq + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LruCache")
+            .field("capacity", &self.capacity)
+            .field("entries", &self.entries)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::LruCache;
+    use crate::cache::CacheStats;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_model() {
+        let mut random = TestRng::with_seed(129);
+        let mut cache = LruCache::new(6).unwrap();
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&evicted);
+        cache.set_eviction_callback(move |key, value| sink.borrow_mut().push((key, value)));
+        // 从最久未使用到最近使用
+        let mut expected: Vec<(u64, u64)> = Vec::new();
+        let mut expected_evicted = Vec::new();
+        let mut stats = CacheStats::default();
+        let mut capacity = 6;
+        for _ in 0..3000 {
+            let key = random.below(12);
+            let position = expected.iter().position(|&(k, _)| k == key);
+            match random.below(10) {
+                0..=3 => {
+                    let value = random.next_u64();
+                    let old = match position {
+                        Some(i) => Some(expected.remove(i).1),
+                        None => {
+                            if expected.len() == capacity {
+                                expected_evicted.push(expected.remove(0));
+                            }
+                            None
+                        }
+                    };
+                    expected.push((key, value));
+                    assert_eq!(cache.put(key, value), old);
+                }
+                4..=6 => {
+                    stats.record(position.is_some());
+                    let wanted = position.map(|i| {
+                        let entry = expected.remove(i);
+                        expected.push(entry);
+                        entry.1
+                    });
+                    assert_eq!(cache.get(&key).copied(), wanted);
+                }
+                7 => {
+                    let wanted = position.map(|i| expected[i].1);
+                    assert_eq!(cache.peek(&key).copied(), wanted);
+                }
+                8 => {
+                    let wanted = position.map(|i| expected.remove(i).1);
+                    assert_eq!(cache.remove(&key), wanted);
+                }
+                _ => {
+                    capacity = 1 + random.index(8);
+                    while expected.len() > capacity {
+                        expected_evicted.push(expected.remove(0));
+                    }
+                    cache.resize(capacity).unwrap();
+                }
+            }
+            let entries: Vec<(u64, u64)> = cache.iter().map(|(&k, &v)| (k, v)).collect();
+            assert_eq!(entries, expected);
+            assert_eq!(
+                cache.peek_lru().map(|(&k, &v)| (k, v)),
+                expected.first().copied()
+            );
+            assert_eq!(*evicted.borrow(), expected_evicted);
+            assert_eq!(cache.get_stats(), stats);
+        }
+        assert!(!expected_evicted.is_empty());
+    }
+
+    #[test]
+    fn stats_and_manual_pops_skip_callback() {
+        let mut cache = LruCache::new(2).unwrap();
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&evicted);
+        cache.set_eviction_callback(move |key, _| sink.borrow_mut().push(key));
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("c"), None);
+        cache.put("c", 3);
+        assert_eq!(*evicted.borrow(), ["b"]);
+        // 手动弹出和删除不算淘汰
+        assert_eq!(cache.pop_lru(), Some(("a", 1)));
+        assert_eq!(cache.remove("c"), Some(3));
+        assert_eq!(*evicted.borrow(), ["b"]);
+        let stats = cache.get_stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate(), 0.5);
+        cache.reset_stats();
+        assert_eq!(cache.get_stats().get_total(), 0);
+        assert!(LruCache::<u64, u64>::new(0).is_err());
+    }
+}
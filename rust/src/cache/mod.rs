@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+pub mod lfu_cache;
+pub mod lru_cache;
+
+// 被淘汰的键值对会交给回调
+pub type EvictionCallback<K, V> = Box<dyn FnMut(K, V)>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl CacheStats {
+    pub fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.hits + self.misses
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        if self.get_total() == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.get_total() as f64
+    }
+}
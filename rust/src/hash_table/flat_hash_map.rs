@@ -0,0 +1,710 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
+
+// SwissTable 布局：每个槽位对应一个控制字节
+// EMPTY    = 1111_1111
+// DELETED  = 1000_0000 (墓碑)
+// FULL     = 0xxx_xxxx (低 7 位存放哈希值的高 7 位，即 H2)
+const EMPTY: u8 = 0xFF;
+const DELETED: u8 = 0x80;
+const GROUP_WIDTH: usize = 16;
+const LSB: u128 = 0x0101_0101_0101_0101_0101_0101_0101_0101;
+const MSB: u128 = 0x8080_8080_8080_8080_8080_8080_8080_8080;
+
+fn h1(hash: u64) -> usize {
+    hash as usize
+}
+
+fn h2(hash: u64) -> u8 {
+    (hash >> 57) as u8
+}
+
+// 每个字节的最高位表示该字节是否匹配
+#[derive(Clone, Copy)]
+struct BitMask(u128);
+
+impl BitMask {
+    fn any(&self) -> bool {
+        self.0 != 0
+    }
+
+    fn lowest(&self) -> Option<usize> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as usize / 8)
+        }
+    }
+
+    fn leading_bytes(&self) -> usize {
+        self.0.leading_zeros() as usize / 8
+    }
+
+    fn trailing_bytes(&self) -> usize {
+        self.0.trailing_zeros() as usize / 8
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let bit = self.lowest()?;
+        // 清除最低的一个置位
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+// 一次加载 16 个控制字节，用普通的整数运算代替 SIMD
+#[derive(Clone, Copy)]
+struct Group(u128);
+
+impl Group {
+    fn load(ctrl: &[u8], index: usize) -> Self {
+        let mut bytes = [0u8; GROUP_WIDTH];
+        bytes.copy_from_slice(&ctrl[index..index + GROUP_WIDTH]);
+        Group(u128::from_le_bytes(bytes))
+    }
+
+    // 经典的 "has zero byte" 技巧，可能出现假阳性，调用方需要再比较 key
+    fn match_byte(&self, byte: u8) -> BitMask {
+        let cmp = self.0 ^ (LSB * byte as u128);
+        BitMask(cmp.wrapping_sub(LSB) & !cmp & MSB)
+    }
+
+    // 只有 EMPTY 的最高两位同时为 1
+    fn match_empty(&self) -> BitMask {
+        BitMask(self.0 & (self.0 << 1) & MSB)
+    }
+
+    fn match_empty_or_deleted(&self) -> BitMask {
+        BitMask(self.0 & MSB)
+    }
+
+    fn match_full(&self) -> BitMask {
+        BitMask(!self.0 & MSB)
+    }
+}
+
+// 三角数探测：步长依次为 1, 2, 3... 个分组，桶数为 2 的幂时可以遍历所有分组
+struct ProbeSeq {
+    pos: usize,
+    stride: usize,
+}
+
+impl ProbeSeq {
+    fn move_next(&mut self, mask: usize) {
+        self.stride += GROUP_WIDTH;
+        self.pos = (self.pos + self.stride) & mask;
+    }
+}
+
+fn capacity_to_buckets(capacity: usize) -> usize {
+    let adjusted = capacity.saturating_mul(8) / 7;
+    adjusted.next_power_of_two().max(GROUP_WIDTH)
+}
+
+fn bucket_mask_to_capacity(bucket_mask: usize) -> usize {
+    (bucket_mask + 1) / 8 * 7
+}
+
+pub struct FlatHashMap<K, V, S = RandomState> {
+    // 长度为 buckets + GROUP_WIDTH，末尾的 GROUP_WIDTH 个字节是开头的镜像，
+    // 这样从任意位置加载一个分组都不需要处理回绕
+    ctrl: Vec<u8>,
+    slots: Vec<Option<(K, V)>>,
+    bucket_mask: usize,
+    size: usize,
+    tombstones: usize,
+    growth_left: usize,
+    hash_builder: S,
+}
+
+impl<K, V> FlatHashMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V> Default for FlatHashMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> FlatHashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        let buckets = capacity_to_buckets(capacity);
+        let mut slots = Vec::with_capacity(buckets);
+        slots.resize_with(buckets, || None);
+        FlatHashMap {
+            ctrl: vec![EMPTY; buckets + GROUP_WIDTH],
+            slots,
+            bucket_mask: buckets - 1,
+            size: 0,
+            tombstones: 0,
+            growth_left: bucket_mask_to_capacity(buckets - 1),
+            hash_builder,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        bucket_mask_to_capacity(self.bucket_mask)
+    }
+
+    pub fn get_buckets(&self) -> usize {
+        self.bucket_mask + 1
+    }
+
+    pub fn get_tombstones(&self) -> usize {
+        self.tombstones
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.size as f64 / self.get_buckets() as f64
+    }
+
+    fn hash<Q>(&self, key: &Q) -> u64
+    where
+        Q: Hash + ?Sized,
+    {
+        self.hash_builder.hash_one(key)
+    }
+
+    fn probe_seq(&self, hash: u64) -> ProbeSeq {
+        ProbeSeq {
+            pos: h1(hash) & self.bucket_mask,
+            stride: 0,
+        }
+    }
+
+    fn set_ctrl(&mut self, index: usize, value: u8) {
+        // 开头的 GROUP_WIDTH 个字节需要同步写到末尾的镜像区
+        let mirror = (index.wrapping_sub(GROUP_WIDTH) & self.bucket_mask) + GROUP_WIDTH;
+        self.ctrl[index] = value;
+        self.ctrl[mirror] = value;
+    }
+
+    fn find<Q>(&self, hash: u64, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let tag = h2(hash);
+        let mut probe = self.probe_seq(hash);
+        loop {
+            let group = Group::load(&self.ctrl, probe.pos);
+            for bit in group.match_byte(tag) {
+                let index = (probe.pos + bit) & self.bucket_mask;
+                if let Some((k, _)) = &self.slots[index]
+                    && k.borrow() == key
+                {
+                    return Some(index);
+                }
+            }
+            // 分组里只要出现过 EMPTY，探测链就在这里结束
+            if group.match_empty().any() {
+                return None;
+            }
+            probe.move_next(self.bucket_mask);
+        }
+    }
+
+    fn find_insert_slot(&self, hash: u64) -> usize {
+        let mut probe = self.probe_seq(hash);
+        loop {
+            let group = Group::load(&self.ctrl, probe.pos);
+            if let Some(bit) = group.match_empty_or_deleted().lowest() {
+                return (probe.pos + bit) & self.bucket_mask;
+            }
+            probe.move_next(self.bucket_mask);
+        }
+    }
+
+    fn reserve_one(&mut self) {
+        if self.growth_left > 0 {
+            return;
+        }
+        // 墓碑较多时原地重建即可回收空间，否则扩容一倍
+        let full_capacity = self.get_capacity();
+        if self.size < full_capacity / 2 {
+            self.rehash(self.get_buckets());
+        } else {
+            self.resize(full_capacity + 1);
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.rehash(capacity_to_buckets(capacity.max(self.size)));
+    }
+
+    fn rehash(&mut self, buckets: usize) {
+        let mut slots = Vec::with_capacity(buckets);
+        slots.resize_with(buckets, || None);
+        let old_slots = std::mem::replace(&mut self.slots, slots);
+        self.ctrl = vec![EMPTY; buckets + GROUP_WIDTH];
+        self.bucket_mask = buckets - 1;
+        self.tombstones = 0;
+        self.growth_left = bucket_mask_to_capacity(self.bucket_mask);
+        for (key, value) in old_slots.into_iter().flatten() {
+            let hash = self.hash(&key);
+            let index = self.find_insert_slot(hash);
+            self.set_ctrl(index, h2(hash));
+            self.slots[index] = Some((key, value));
+            self.growth_left -= 1;
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        if additional > self.growth_left {
+            self.resize(self.size + additional);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.resize(self.size);
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hash(&key);
+        if let Some(index) = self.find(hash, &key) {
+            let (_, old) = self.slots[index].as_mut().unwrap();
+            return Some(std::mem::replace(old, value));
+        }
+        self.reserve_one();
+        let index = self.find_insert_slot(hash);
+        if self.ctrl[index] == EMPTY {
+            self.growth_left -= 1;
+        } else {
+            // 复用墓碑不占用新的增长空间
+            self.tombstones -= 1;
+        }
+        self.set_ctrl(index, h2(hash));
+        self.slots[index] = Some((key, value));
+        self.size += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(self.hash(key), key)?;
+        self.slots[index].as_ref().map(|(_, v)| v)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(self.hash(key), key)?;
+        self.slots[index].as_ref().map(|(k, v)| (k, v))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(self.hash(key), key)?;
+        self.slots[index].as_mut().map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hash(key), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(self.hash(key), key)?;
+        self.erase(index)
+    }
+
+    fn erase(&mut self, index: usize) -> Option<(K, V)> {
+        // 如果该槽位前后连续的非 EMPTY 字节不足一个分组，说明没有探测链会穿过这里，
+        // 可以直接标记为 EMPTY，否则只能留下墓碑
+        let before = Group::load(
+            &self.ctrl,
+            index.wrapping_sub(GROUP_WIDTH) & self.bucket_mask,
+        );
+        let after = Group::load(&self.ctrl, index);
+        let empty_before = before.match_empty();
+        let empty_after = after.match_empty();
+        if empty_before.leading_bytes() + empty_after.trailing_bytes() >= GROUP_WIDTH {
+            self.set_ctrl(index, DELETED);
+            self.tombstones += 1;
+        } else {
+            self.set_ctrl(index, EMPTY);
+            self.growth_left += 1;
+        }
+        self.size -= 1;
+        self.slots[index].take()
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for index in 0..self.get_buckets() {
+            let keep = match self.slots[index].as_mut() {
+                None => continue,
+                Some((k, v)) => f(k, v),
+            };
+            if !keep {
+                self.erase(index);
+            }
+        }
+    }
+
+    pub fn drain(&mut self) -> std::vec::IntoIter<(K, V)> {
+        let buckets = self.get_buckets();
+        let mut slots = Vec::with_capacity(buckets);
+        slots.resize_with(buckets, || None);
+        let old_slots = std::mem::replace(&mut self.slots, slots);
+        self.clear();
+        old_slots
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = None;
+        }
+        self.ctrl.fill(EMPTY);
+        self.size = 0;
+        self.tombstones = 0;
+        self.growth_left = bucket_mask_to_capacity(self.bucket_mask);
+    }
+}
+
+impl<K, V, S> FlatHashMap<K, V, S> {
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            ctrl: &self.ctrl,
+            slots: &self.slots,
+            group_pos: 0,
+            current: BitMask(0),
+            remaining: self.size,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            inner: self.slots.iter_mut(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+// 按分组扫描控制字节，只访问 FULL 的槽位
+pub struct Iter<'a, K, V> {
+    ctrl: &'a [u8],
+    slots: &'a [Option<(K, V)>],
+    group_pos: usize,
+    current: BitMask,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if let Some(bit) = self.current.next() {
+                let index = self.group_pos - GROUP_WIDTH + bit;
+                if let Some((k, v)) = &self.slots[index] {
+                    self.remaining -= 1;
+                    return Some((k, v));
+                }
+                continue;
+            }
+            if self.group_pos >= self.slots.len() {
+                return None;
+            }
+            self.current = Group::load(self.ctrl, self.group_pos).match_full();
+            self.group_pos += GROUP_WIDTH;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    inner: std::slice::IterMut<'a, Option<(K, V)>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, v) = self.inner.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a FlatHashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, S> IntoIterator for FlatHashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = std::iter::Flatten<std::vec::IntoIter<Option<(K, V)>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slots.into_iter().flatten()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FlatHashMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = FlatHashMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for FlatHashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K, V, S> Debug for FlatHashMap<K, V, S>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, hash_map::DefaultHasher},
+        hash::{BuildHasherDefault, Hasher},
+    };
+
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    // 哈希值就是 key 本身：H2 全为 0，相邻的 key 落在相邻的槽位，容易形成长探测链和回绕
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = (self.0 << 8) | byte as u64;
+            }
+        }
+
+        fn write_u64(&mut self, value: u64) {
+            self.0 = value;
+        }
+    }
+
+    fn check_layout<K, V, S>(map: &FlatHashMap<K, V, S>)
+    where
+        K: Hash + Eq,
+        S: BuildHasher,
+    {
+        let buckets = map.get_buckets();
+        assert_eq!(map.ctrl.len(), buckets + GROUP_WIDTH);
+        for index in 0..GROUP_WIDTH {
+            assert_eq!(map.ctrl[buckets + index], map.ctrl[index & map.bucket_mask]);
+        }
+        let mut full = 0;
+        let mut deleted = 0;
+        for index in 0..buckets {
+            match (map.ctrl[index], &map.slots[index]) {
+                (EMPTY, None) => {}
+                (DELETED, None) => deleted += 1,
+                (tag, Some((key, _))) if tag & 0x80 == 0 => {
+                    assert_eq!(tag, h2(map.hash(key)));
+                    full += 1;
+                }
+                (tag, _) => panic!("Control byte {:#x} does not match slot {}.", tag, index),
+            }
+        }
+        assert_eq!(full, map.get_size());
+        assert_eq!(deleted, map.get_tombstones());
+        assert_eq!(
+            map.growth_left,
+            map.get_capacity() - map.get_size() - map.get_tombstones()
+        );
+    }
+
+    fn run_against_hash_map<S: BuildHasher>(mut map: FlatHashMap<u64, u64, S>, seed: u64) {
+        let mut random = TestRng::with_seed(seed);
+        let mut expected = HashMap::new();
+        let mut saw_tombstone = false;
+        let initial_buckets = map.get_buckets();
+        for _ in 0..20000 {
+            let key = random.below(300);
+            match random.below(8) {
+                0..=3 => {
+                    let value = random.next_u64();
+                    assert_eq!(map.insert(key, value), expected.insert(key, value));
+                }
+                4..=6 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                _ => assert_eq!(map.get(&key), expected.get(&key)),
+            }
+            assert_eq!(map.get_size(), expected.len());
+            saw_tombstone |= map.get_tombstones() > 0;
+            check_layout(&map);
+        }
+        assert!(saw_tombstone);
+        assert!(map.get_buckets() > initial_buckets);
+        for (key, value) in expected.iter() {
+            assert_eq!(map.get(key), Some(value));
+        }
+
+        map.retain(|key, _| key % 3 != 0);
+        expected.retain(|key, _| key % 3 != 0);
+        assert_eq!(map.get_size(), expected.len());
+        check_layout(&map);
+        let mut entries: Vec<(u64, u64)> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        let mut wanted: Vec<(u64, u64)> = expected.iter().map(|(&k, &v)| (k, v)).collect();
+        entries.sort_unstable();
+        wanted.sort_unstable();
+        assert_eq!(entries, wanted);
+
+        let mut drained: Vec<(u64, u64)> = map.drain().collect();
+        drained.sort_unstable();
+        assert_eq!(drained, wanted);
+        assert!(map.is_empty());
+        assert_eq!(map.get(&wanted[0].0), None);
+        check_layout(&map);
+    }
+
+    #[test]
+    fn random_operations_match_hash_map() {
+        let map = FlatHashMap::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+        run_against_hash_map(map, 26);
+    }
+
+    #[test]
+    fn clustered_keys_wrap_around() {
+        let map = FlatHashMap::with_hasher(BuildHasherDefault::<IdentityHasher>::default());
+        run_against_hash_map(map, 126);
+    }
+
+    #[test]
+    fn tombstones_are_reused() {
+        let mut map = FlatHashMap::with_hasher(BuildHasherDefault::<IdentityHasher>::default());
+        map.reserve(100);
+        let buckets = map.get_buckets();
+        // 连续 32 个槽位全满，删掉中间的一个只能留下墓碑
+        for key in 0..32 {
+            map.insert(key, key);
+        }
+        map.remove(&10);
+        assert_eq!(map.get_tombstones(), 1);
+        assert_eq!(map.get(&20), Some(&20));
+        map.insert(10, 100);
+        assert_eq!(map.get_tombstones(), 0);
+        assert_eq!(map.get(&10), Some(&100));
+        // 前后都挨着 EMPTY 的槽位，没有探测链会穿过，直接变回 EMPTY
+        map.insert(64, 64);
+        map.remove(&64);
+        assert_eq!(map.get_tombstones(), 0);
+        assert_eq!(map.get_buckets(), buckets);
+        check_layout(&map);
+    }
+}
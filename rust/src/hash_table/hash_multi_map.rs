@@ -0,0 +1,317 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
+
+use super::flat_hash_map::FlatHashMap;
+use crate::array::dynamic_array::DynamicArray;
+
+const CAPACITY_IS_POSITIVE: &str = "Initial capacity 1 is positive.";
+const APPEND_IN_BOUNDS: &str = "Appending at the current size is always in bounds.";
+
+// 同一个 key 的所有 value 按插入顺序存放在一个动态数组里
+pub struct HashMultiMap<K, V, S = RandomState> {
+    map: FlatHashMap<K, DynamicArray<V>, S>,
+    size: usize,
+}
+
+impl<K, V> HashMultiMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        HashMultiMap {
+            map: FlatHashMap::new(),
+            size: 0,
+        }
+    }
+}
+
+impl<K, V> Default for HashMultiMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> HashMultiMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: Clone,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        HashMultiMap {
+            map: FlatHashMap::with_hasher(hash_builder),
+            size: 0,
+        }
+    }
+
+    // 所有 value 的总数
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get_key_size(&self) -> usize {
+        self.map.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // DynamicArray 只会在容量为 0 或下标越界时报错：这里容量固定为 1，追加总是写在末尾，
+    // remove 的下标来自 position，都不可能失败
+    pub fn insert(&mut self, key: K, value: V) {
+        match self.map.get_mut(&key) {
+            Some(values) => values.append(value).expect(APPEND_IN_BOUNDS),
+            None => {
+                let mut values = DynamicArray::new(1).expect(CAPACITY_IS_POSITIVE);
+                values.append(value).expect(APPEND_IN_BOUNDS);
+                self.map.insert(key, values);
+            }
+        }
+        self.size += 1;
+    }
+
+    pub fn insert_all<I>(&mut self, key: K, values: I)
+    where
+        I: IntoIterator<Item = V>,
+    {
+        let mut list = self
+            .map
+            .remove(&key)
+            .unwrap_or_else(|| DynamicArray::new(1).expect(CAPACITY_IS_POSITIVE));
+        for value in values {
+            list.append(value).expect(APPEND_IN_BOUNDS);
+            self.size += 1;
+        }
+        if list.get_size() > 0 {
+            self.map.insert(key, list);
+        }
+    }
+
+    // 第一个 value
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).and_then(|values| values.get_first())
+    }
+
+    pub fn get_all<Q>(&self, key: &Q) -> &[V]
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            None => &[],
+            Some(values) => values.get_elements(),
+        }
+    }
+
+    pub fn count<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).map_or(0, |values| values.get_size())
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    pub fn contains<Q>(&self, key: &Q, value: &V) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        V: PartialEq,
+    {
+        self.get_all(key).contains(value)
+    }
+
+    // 删除第一个等于 value 的项
+    pub fn remove<Q>(&mut self, key: &Q, value: &V) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        V: PartialEq,
+    {
+        let values = match self.map.get_mut(key) {
+            None => return false,
+            Some(values) => values,
+        };
+        let index = match values.get_elements().iter().position(|v| v == value) {
+            None => return false,
+            Some(index) => index,
+        };
+        values
+            .remove(index)
+            .expect("Index returned by position is in bounds.");
+        if values.get_size() == 0 {
+            self.map.remove(key);
+        }
+        self.size -= 1;
+        true
+    }
+
+    pub fn remove_all<Q>(&mut self, key: &Q) -> Option<DynamicArray<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let values = self.map.remove(key)?;
+        self.size -= values.get_size();
+        Some(values)
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.size = 0;
+    }
+}
+
+impl<K, V, S> HashMultiMap<K, V, S>
+where
+    V: Clone,
+{
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.map.keys()
+    }
+
+    // 每个 (key, value) 对都会输出一次
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map
+            .iter()
+            .flat_map(|(key, values)| values.get_elements().iter().map(move |v| (key, v)))
+    }
+
+    pub fn iter_all(&self) -> impl Iterator<Item = (&K, &[V])> {
+        self.map
+            .iter()
+            .map(|(key, values)| (key, values.get_elements().as_slice()))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for HashMultiMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = HashMultiMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for HashMultiMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, S> Debug for HashMultiMap<K, V, S>
+where
+    K: Debug,
+    V: Debug + Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter_all()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::HashMultiMap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_model() {
+        let mut random = TestRng::with_seed(227);
+        let mut map = HashMultiMap::new();
+        let mut expected: HashMap<u64, Vec<u64>> = HashMap::new();
+        for _ in 0..3000 {
+            let key = random.below(15);
+            let value = random.below(5);
+            match random.below(6) {
+                0..=2 => {
+                    map.insert(key, value);
+                    expected.entry(key).or_default().push(value);
+                }
+                3 => {
+                    let values = expected.get_mut(&key);
+                    let position = values
+                        .as_ref()
+                        .and_then(|values| values.iter().position(|&v| v == value));
+                    assert_eq!(map.remove(&key, &value), position.is_some());
+                    if let (Some(values), Some(position)) = (values, position) {
+                        values.remove(position);
+                        if values.is_empty() {
+                            expected.remove(&key);
+                        }
+                    }
+                }
+                4 => {
+                    let removed = map
+                        .remove_all(&key)
+                        .map(|values| values.get_elements().clone());
+                    assert_eq!(removed, expected.remove(&key));
+                }
+                _ => {
+                    let values = [value, value + 1];
+                    map.insert_all(key, values);
+                    expected.entry(key).or_default().extend(values);
+                }
+            }
+            let wanted = expected
+                .get(&key)
+                .map_or(&[][..], |values| values.as_slice());
+            assert_eq!(map.get_all(&key), wanted);
+            assert_eq!(map.get(&key), wanted.first());
+            assert_eq!(map.count(&key), wanted.len());
+            assert_eq!(map.contains_key(&key), !wanted.is_empty());
+            assert_eq!(map.get_key_size(), expected.len());
+            assert_eq!(
+                map.get_size(),
+                expected.values().map(Vec::len).sum::<usize>()
+            );
+            assert_eq!(map.iter().count(), map.get_size());
+        }
+    }
+
+    #[test]
+    fn insert_all_with_no_values_adds_no_key() {
+        let mut map: HashMultiMap<&str, u64> = HashMultiMap::new();
+        map.insert_all("a", []);
+        assert!(map.is_empty());
+        assert!(!map.contains_key("a"));
+        map.insert_all("a", [1, 2]);
+        assert!(map.contains(&"a", &2));
+        assert!(map.remove(&"a", &1) && map.remove(&"a", &2));
+        assert!(!map.contains_key("a"));
+    }
+}
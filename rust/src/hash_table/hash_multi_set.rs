@@ -0,0 +1,302 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
+
+use super::flat_hash_map::{self, FlatHashMap};
+
+// 每个元素只存一份，值为出现次数
+pub struct HashMultiSet<T, S = RandomState> {
+    counts: FlatHashMap<T, usize, S>,
+    size: usize,
+}
+
+impl<T> HashMultiSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    pub fn new() -> Self {
+        HashMultiSet {
+            counts: FlatHashMap::new(),
+            size: 0,
+        }
+    }
+}
+
+impl<T> Default for HashMultiSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> HashMultiSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        HashMultiSet {
+            counts: FlatHashMap::with_hasher(hash_builder),
+            size: 0,
+        }
+    }
+
+    // 包含重复元素在内的总个数
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get_distinct_size(&self) -> usize {
+        self.counts.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn insert(&mut self, value: T) -> usize {
+        self.insert_many(value, 1)
+    }
+
+    pub fn insert_many(&mut self, value: T, count: usize) -> usize {
+        if count == 0 {
+            return self.count(&value);
+        }
+        self.size += count;
+        match self.counts.get_mut(&value) {
+            Some(current) => {
+                *current += count;
+                *current
+            }
+            None => {
+                self.counts.insert(value, count);
+                count
+            }
+        }
+    }
+
+    pub fn count<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.contains_key(value)
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_many(value, 1) > 0
+    }
+
+    // 返回实际删除的个数
+    pub fn remove_many<Q>(&mut self, value: &Q, count: usize) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let current = match self.counts.get_mut(value) {
+            None => return 0,
+            Some(current) => current,
+        };
+        let removed = count.min(*current);
+        *current -= removed;
+        if *current == 0 {
+            self.counts.remove(value);
+        }
+        self.size -= removed;
+        removed
+    }
+
+    pub fn remove_all<Q>(&mut self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let removed = self.counts.remove(value).unwrap_or(0);
+        self.size -= removed;
+        removed
+    }
+
+    pub fn set_count(&mut self, value: T, count: usize) -> usize {
+        let old = self.remove_all(&value);
+        self.insert_many(value, count);
+        old
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.size = 0;
+    }
+
+    // 出现次数最多的元素
+    pub fn most_common(&self) -> Option<(&T, usize)> {
+        self.iter().max_by_key(|(_, count)| *count)
+    }
+}
+
+impl<T, S> HashMultiSet<T, S> {
+    // (元素, 次数)
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.counts.iter(),
+        }
+    }
+
+    // 每个元素按次数重复输出
+    pub fn elements(&self) -> impl Iterator<Item = &T> {
+        self.iter()
+            .flat_map(|(value, count)| std::iter::repeat_n(value, count))
+    }
+}
+
+pub struct Iter<'a, T> {
+    inner: flat_hash_map::Iter<'a, T, usize>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(value, count)| (value, *count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashMultiSet<T, S> {
+    type Item = (&'a T, usize);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for HashMultiSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = HashMultiSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashMultiSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T, S> Debug for HashMultiSet<T, S>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::HashMultiSet;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn counts_match_hash_map() {
+        let mut random = TestRng::with_seed(127);
+        let mut set = HashMultiSet::new();
+        let mut expected: HashMap<u64, usize> = HashMap::new();
+        for _ in 0..3000 {
+            let value = random.below(20);
+            let count = random.index(4);
+            let current = expected.get(&value).copied().unwrap_or(0);
+            match random.below(5) {
+                0 => {
+                    assert_eq!(set.insert_many(value, count), current + count);
+                    if current + count > 0 {
+                        expected.insert(value, current + count);
+                    }
+                }
+                1 => {
+                    assert_eq!(set.remove_many(&value, count), count.min(current));
+                    if current > count {
+                        expected.insert(value, current - count);
+                    } else {
+                        expected.remove(&value);
+                    }
+                }
+                2 => {
+                    assert_eq!(set.remove_all(&value), current);
+                    expected.remove(&value);
+                }
+                3 => {
+                    assert_eq!(set.set_count(value, count), current);
+                    if count > 0 {
+                        expected.insert(value, count);
+                    } else {
+                        expected.remove(&value);
+                    }
+                }
+                _ => {
+                    assert_eq!(set.remove(&value), current > 0);
+                    if current > 1 {
+                        expected.insert(value, current - 1);
+                    } else {
+                        expected.remove(&value);
+                    }
+                }
+            }
+            // 次数减到 0 的元素不再出现
+            assert_eq!(
+                set.count(&value),
+                expected.get(&value).copied().unwrap_or(0)
+            );
+            assert_eq!(set.contains(&value), expected.contains_key(&value));
+            assert_eq!(set.get_distinct_size(), expected.len());
+            assert_eq!(set.get_size(), expected.values().sum::<usize>());
+            assert_eq!(set.elements().count(), set.get_size());
+            assert!(set.iter().all(|(_, count)| count > 0));
+        }
+    }
+
+    #[test]
+    fn most_common() {
+        let set: HashMultiSet<char> = "mississippi".chars().collect();
+        assert_eq!(set.count(&'s'), 4);
+        assert_eq!(set.count(&'m'), 1);
+        assert_eq!(set.get_distinct_size(), 4);
+        let (_, count) = set.most_common().unwrap();
+        assert_eq!(count, 4);
+        assert_eq!(HashMultiSet::<char>::new().most_common(), None);
+    }
+}
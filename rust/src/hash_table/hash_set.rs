@@ -0,0 +1,386 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+    iter::Chain,
+};
+
+use super::flat_hash_map::{self, FlatHashMap};
+
+// 集合就是值为 () 的哈希表
+pub struct HashSet<T, S = RandomState> {
+    map: FlatHashMap<T, (), S>,
+}
+
+impl<T> HashSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    pub fn new() -> Self {
+        HashSet {
+            map: FlatHashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashSet {
+            map: FlatHashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<T> Default for HashSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        HashSet {
+            map: FlatHashMap::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.map.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.map.get_capacity()
+    }
+
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.map.contains_key(&value) {
+            return false;
+        }
+        self.map.insert(value, ());
+        true
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get_key_value(value).map(|(k, _)| k)
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove_entry(value).map(|(k, _)| k)
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|k, _| f(k));
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn union<'a>(&'a self, other: &'a HashSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            iter: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    pub fn intersection<'a>(&'a self, other: &'a HashSet<T, S>) -> Intersection<'a, T, S> {
+        // 遍历较小的集合
+        let (small, large) = if self.get_size() <= other.get_size() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Intersection {
+            iter: small.iter(),
+            other: large,
+        }
+    }
+
+    pub fn difference<'a>(&'a self, other: &'a HashSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a HashSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            iter: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    pub fn is_disjoint(&self, other: &HashSet<T, S>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    pub fn is_subset(&self, other: &HashSet<T, S>) -> bool {
+        self.get_size() <= other.get_size() && self.iter().all(|v| other.contains(v))
+    }
+
+    pub fn is_superset(&self, other: &HashSet<T, S>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl<T, S> HashSet<T, S> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.map.iter(),
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    inner: flat_hash_map::Iter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+pub struct Union<'a, T, S> {
+    iter: Chain<Iter<'a, T>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+pub struct Intersection<'a, T, S> {
+    iter: Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|v| other.contains(*v))
+    }
+}
+
+pub struct Difference<'a, T, S> {
+    iter: Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|v| !other.contains(*v))
+    }
+}
+
+pub struct SymmetricDifference<'a, T, S> {
+    iter: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S> IntoIterator for HashSet<T, S> {
+    type Item = T;
+    type IntoIter =
+        std::iter::Map<<FlatHashMap<T, (), S> as IntoIterator>::IntoIter, fn((T, ())) -> T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter().map(|(k, _)| k)
+    }
+}
+
+impl<T> FromIterator<T> for HashSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = HashSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T, S> PartialEq for HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.get_size() == other.get_size() && self.is_subset(other)
+    }
+}
+
+impl<T, S> Debug for HashSet<T, S>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::HashSet;
+    use crate::test_util::rng::TestRng;
+
+    fn sorted<'a>(iter: impl Iterator<Item = &'a u64>) -> Vec<u64> {
+        let mut values: Vec<u64> = iter.copied().collect();
+        values.sort_unstable();
+        values
+    }
+
+    fn random_set(random: &mut TestRng) -> (HashSet<u64>, BTreeSet<u64>) {
+        let mut set = HashSet::new();
+        let mut expected = BTreeSet::new();
+        for _ in 0..random.below(40) {
+            let value = random.below(60);
+            assert_eq!(set.insert(value), expected.insert(value));
+        }
+        (set, expected)
+    }
+
+    #[test]
+    fn set_operations_match_btree_set() {
+        let mut random = TestRng::with_seed(27);
+        for _ in 0..200 {
+            let (mut a, mut expected_a) = random_set(&mut random);
+            let (b, expected_b) = random_set(&mut random);
+            assert_eq!(a.get_size(), expected_a.len());
+
+            let union: Vec<u64> = expected_a.union(&expected_b).copied().collect();
+            assert_eq!(sorted(a.union(&b)), union);
+            let intersection: Vec<u64> = expected_a.intersection(&expected_b).copied().collect();
+            assert_eq!(sorted(a.intersection(&b)), intersection);
+            assert_eq!(sorted(b.intersection(&a)), intersection);
+            let difference: Vec<u64> = expected_a.difference(&expected_b).copied().collect();
+            assert_eq!(sorted(a.difference(&b)), difference);
+            let symmetric: Vec<u64> = expected_a
+                .symmetric_difference(&expected_b)
+                .copied()
+                .collect();
+            assert_eq!(sorted(a.symmetric_difference(&b)), symmetric);
+
+            assert_eq!(a.is_disjoint(&b), expected_a.is_disjoint(&expected_b));
+            assert_eq!(a.is_subset(&b), expected_a.is_subset(&expected_b));
+            assert_eq!(a.is_superset(&b), expected_a.is_superset(&expected_b));
+
+            let value = random.below(60);
+            assert_eq!(a.remove(&value), expected_a.remove(&value));
+            assert_eq!(a.contains(&value), expected_a.contains(&value));
+            assert_eq!(
+                sorted(a.iter()),
+                expected_a.iter().copied().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn subset_of_itself_and_empty() {
+        let a: HashSet<u64> = (0..10).collect();
+        let empty = HashSet::new();
+        assert!(a.is_subset(&a) && a.is_superset(&a));
+        assert!(empty.is_subset(&a) && a.is_disjoint(&empty));
+        assert_eq!(a.union(&empty).count(), 10);
+        assert_eq!(a.symmetric_difference(&a).count(), 0);
+    }
+}
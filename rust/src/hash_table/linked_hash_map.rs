@@ -0,0 +1,437 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
+
+use super::flat_hash_map::FlatHashMap;
+use crate::linked_list::double_linked_list::{DoubleLinkedList, DoubleLinkedNodeRef};
+
+// 哈希表负责 O(1) 查找，双向链表只串联 key 来记录顺序，
+// 哈希表里同时保存链表节点的引用，这样删除和移动都是 O(1)
+struct Entry<K, V> {
+    value: V,
+    node: DoubleLinkedNodeRef<K>,
+}
+
+pub struct LinkedHashMap<K, V, S = RandomState> {
+    map: FlatHashMap<K, Entry<K, V>, S>,
+    order: DoubleLinkedList<K>,
+}
+
+impl<K, V> LinkedHashMap<K, V, RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinkedHashMap {
+            map: FlatHashMap::with_capacity(capacity),
+            order: DoubleLinkedList::new(),
+        }
+    }
+}
+
+impl<K, V> Default for LinkedHashMap<K, V, RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> LinkedHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        LinkedHashMap {
+            map: FlatHashMap::with_hasher(hash_builder),
+            order: DoubleLinkedList::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.map.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // 已存在的 key 只更新 value，保持原来的位置
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(entry) = self.map.get_mut(&key) {
+            return Some(std::mem::replace(&mut entry.value, value));
+        }
+        let node = self.order.insert_at_tail(key.clone());
+        self.map.insert(key, Entry { value, node });
+        None
+    }
+
+    // 已存在的 key 更新 value 并移动到末尾
+    pub fn insert_to_back(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.insert(key.clone(), value);
+        if old.is_some() {
+            self.to_back(&key);
+        }
+        old
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get_mut(key).map(|entry| &mut entry.value)
+    }
+
+    // 访问的同时把该项移动到末尾
+    pub fn get_to_back<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let entry = self.map.get(key)?;
+        self.order.move_to_tail(&entry.node);
+        Some(&entry.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            None => false,
+            Some(entry) => {
+                self.order.move_to_tail(&entry.node);
+                true
+            }
+        }
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            None => false,
+            Some(entry) => {
+                self.order.move_to_head(&entry.node);
+                true
+            }
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, entry) = self.map.remove_entry(key)?;
+        self.order.remove_node(entry.node);
+        Some((key, entry.value))
+    }
+
+    pub fn front(&self) -> Option<(&K, &V)> {
+        let node = self.order.get_head_node()?;
+        let node_ref = (*node).borrow();
+        self.map
+            .get_key_value(&node_ref.data)
+            .map(|(k, entry)| (k, &entry.value))
+    }
+
+    pub fn back(&self) -> Option<(&K, &V)> {
+        let node = self.order.get_tail_node()?;
+        let node_ref = (*node).borrow();
+        self.map
+            .get_key_value(&node_ref.data)
+            .map(|(k, entry)| (k, &entry.value))
+    }
+
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        let node = self.order.get_head_node()?;
+        let key = (*node).borrow().data.clone();
+        drop(node);
+        self.remove_entry(&key)
+    }
+
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        let node = self.order.get_tail_node()?;
+        let key = (*node).borrow().data.clone();
+        drop(node);
+        self.remove_entry(&key)
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let order = &mut self.order;
+        self.map.retain(|k, entry| {
+            let keep = f(k, &mut entry.value);
+            if !keep {
+                order.unlink(&entry.node);
+            }
+            keep
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.order.clear();
+    }
+
+    // 按链表顺序遍历，每一步用节点里的 key 回到哈希表里取出键值
+    pub fn iter(&self) -> Iter<'_, K, V, S> {
+        Iter {
+            map: &self.map,
+            front: self.order.get_head_node(),
+            back: self.order.get_tail_node(),
+            remaining: self.get_size(),
+        }
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+pub struct Iter<'a, K, V, S> {
+    map: &'a FlatHashMap<K, Entry<K, V>, S>,
+    front: Option<DoubleLinkedNodeRef<K>>,
+    back: Option<DoubleLinkedNodeRef<K>>,
+    remaining: usize,
+}
+
+impl<'a, K, V, S> Iterator for Iter<'a, K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front.take()?;
+        let node_ref = (*node).borrow();
+        self.front = node_ref.next.clone();
+        self.remaining -= 1;
+        self.map
+            .get_key_value(&node_ref.data)
+            .map(|(k, entry)| (k, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, S> DoubleEndedIterator for Iter<'a, K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back.take()?;
+        let node_ref = (*node).borrow();
+        self.back = node_ref.prev.as_ref().and_then(|weak| weak.upgrade());
+        self.remaining -= 1;
+        self.map
+            .get_key_value(&node_ref.data)
+            .map(|(k, entry)| (k, &entry.value))
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a LinkedHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for LinkedHashMap<K, V, RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = LinkedHashMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for LinkedHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K, V, S> Debug for LinkedHashMap<K, V, S>
+where
+    K: Hash + Eq + Clone + Debug,
+    V: Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinkedHashMap;
+    use crate::test_util::rng::TestRng;
+
+    fn check(map: &LinkedHashMap<u64, u64>, expected: &[(u64, u64)]) {
+        let forward: Vec<(u64, u64)> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(forward, expected);
+        let mut backward: Vec<(u64, u64)> = map.iter().rev().map(|(&k, &v)| (k, v)).collect();
+        backward.reverse();
+        assert_eq!(backward, expected);
+        assert_eq!(map.get_size(), expected.len());
+        assert_eq!(
+            map.front().map(|(&k, &v)| (k, v)),
+            expected.first().copied()
+        );
+        assert_eq!(map.back().map(|(&k, &v)| (k, v)), expected.last().copied());
+    }
+
+    #[test]
+    fn random_operations_keep_order() {
+        let mut random = TestRng::with_seed(28);
+        let mut map = LinkedHashMap::new();
+        let mut expected: Vec<(u64, u64)> = Vec::new();
+        for _ in 0..3000 {
+            let key = random.below(20);
+            let value = random.next_u64();
+            let position = expected.iter().position(|&(k, _)| k == key);
+            match random.below(9) {
+                0 | 1 => {
+                    // 已存在的 key 原地更新
+                    let old = match position {
+                        Some(i) => Some(std::mem::replace(&mut expected[i].1, value)),
+                        None => {
+                            expected.push((key, value));
+                            None
+                        }
+                    };
+                    assert_eq!(map.insert(key, value), old);
+                }
+                2 => {
+                    let old = position.map(|i| expected.remove(i).1);
+                    expected.push((key, value));
+                    assert_eq!(map.insert_to_back(key, value), old);
+                }
+                3 => {
+                    let wanted = position.map(|i| {
+                        let entry = expected.remove(i);
+                        expected.push(entry);
+                        entry.1
+                    });
+                    assert_eq!(map.get_to_back(&key).copied(), wanted);
+                }
+                4 => {
+                    if let Some(i) = position {
+                        let entry = expected.remove(i);
+                        expected.insert(0, entry);
+                    }
+                    assert_eq!(map.to_front(&key), position.is_some());
+                }
+                5 => {
+                    if let Some(i) = position {
+                        let entry = expected.remove(i);
+                        expected.push(entry);
+                    }
+                    assert_eq!(map.to_back(&key), position.is_some());
+                }
+                6 => {
+                    let wanted = position.map(|i| expected.remove(i).1);
+                    assert_eq!(map.remove(&key), wanted);
+                }
+                7 => {
+                    let wanted = (!expected.is_empty()).then(|| expected.remove(0));
+                    assert_eq!(map.pop_front(), wanted);
+                }
+                _ => assert_eq!(map.pop_back(), expected.pop()),
+            }
+            check(&map, &expected);
+        }
+    }
+
+    #[test]
+    fn retain_unlinks_removed_entries() {
+        let mut map: LinkedHashMap<u64, u64> = (0..10).map(|k| (k, k * 10)).collect();
+        map.to_front(&7);
+        map.retain(|&k, v| {
+            *v += 1;
+            k % 3 != 0
+        });
+        check(
+            &map,
+            &[(7, 71), (1, 11), (2, 21), (4, 41), (5, 51), (8, 81)],
+        );
+        map.insert(3, 0);
+        assert_eq!(map.pop_front(), Some((7, 71)));
+        check(&map, &[(1, 11), (2, 21), (4, 41), (5, 51), (8, 81), (3, 0)]);
+        map.clear();
+        check(&map, &[]);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod flat_hash_map;
+pub mod hash_multi_map;
+pub mod hash_multi_set;
+pub mod hash_set;
+pub mod linked_hash_map;
@@ -5,9 +5,10 @@ use std::{
     fmt::Debug,
     rc::{Rc, Weak},
 };
+pub type DoubleLinkedNodeRef<T> = Rc<RefCell<DoubleLinkedNode<T>>>;
 type DoubleLinked<T> = Option<Rc<RefCell<DoubleLinkedNode<T>>>>;
 type DoubleWeakLinked<T> = Option<Weak<RefCell<DoubleLinkedNode<T>>>>;
-struct DoubleLinkedNode<T> {
+pub struct DoubleLinkedNode<T> {
     pub data: T,
     pub next: DoubleLinked<T>,
     pub prev: DoubleWeakLinked<T>,
@@ -15,11 +16,11 @@ struct DoubleLinkedNode<T> {
 
 impl<T> DoubleLinkedNode<T> {
     pub fn new(data: T, next: DoubleLinked<T>, prev: DoubleWeakLinked<T>) -> Rc<RefCell<Self>> {
-        return Rc::new(RefCell::new(DoubleLinkedNode { data, prev, next }));
+        Rc::new(RefCell::new(DoubleLinkedNode { data, prev, next }))
     }
 }
 
-struct DoubleLinkedList<T> {
+pub struct DoubleLinkedList<T> {
     size: usize,
     head: DoubleLinked<T>,
     tail: DoubleLinked<T>,
@@ -27,11 +28,11 @@ struct DoubleLinkedList<T> {
 
 impl<T> DoubleLinkedList<T> {
     pub fn new() -> Self {
-        return DoubleLinkedList {
+        DoubleLinkedList {
             size: 0,
             head: None,
             tail: None,
-        };
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -48,32 +49,94 @@ impl<T> DoubleLinkedList<T> {
         self.size += 1;
     }
 
-    pub fn insert_at_head(&mut self, data: T) {
+    // 返回新节点的引用，调用方可以保存下来，之后 O(1) 地移动或删除该节点
+    pub fn insert_at_head(&mut self, data: T) -> DoubleLinkedNodeRef<T> {
+        let node = DoubleLinkedNode::new(data, None, None);
+        self.link_at_head(node.clone());
+        node
+    }
+
+    pub fn insert_at_tail(&mut self, data: T) -> DoubleLinkedNodeRef<T> {
         let node = DoubleLinkedNode::new(data, None, None);
+        self.link_at_tail(node.clone());
+        node
+    }
+
+    // 把一个已经摘下的节点重新接入链表
+    pub fn link_at_head(&mut self, node: DoubleLinkedNodeRef<T>) {
         if self.is_empty() {
             self.initialize_list(node);
             return;
         }
         let head = self.head.as_mut().unwrap();
-        node.borrow_mut().prev = Some(Rc::downgrade(head));
-        head.borrow_mut().next = Some(node.clone());
-        self.head = Some(node.clone());
+        head.borrow_mut().prev = Some(Rc::downgrade(&node));
+        node.borrow_mut().next = Some(head.clone());
+        self.head = Some(node);
         self.size += 1;
     }
 
-    pub fn insert_at_tail(&mut self, data: T) {
-        let node = DoubleLinkedNode::new(data, None, None);
+    pub fn link_at_tail(&mut self, node: DoubleLinkedNodeRef<T>) {
         if self.is_empty() {
             self.initialize_list(node);
             return;
         }
         let tail = self.tail.as_mut().unwrap();
         tail.borrow_mut().next = Some(node.clone());
-        node.borrow_mut().prev = Some(Rc::downgrade(&tail));
-        self.tail = Some(node.clone());
+        node.borrow_mut().prev = Some(Rc::downgrade(tail));
+        self.tail = Some(node);
         self.size += 1;
     }
 
+    // 把节点从链表中摘下来，节点本身仍然有效，调用方需保证节点属于当前链表
+    pub fn unlink(&mut self, node: &DoubleLinkedNodeRef<T>) {
+        let mut node_ref = node.borrow_mut();
+        let prev = node_ref.prev.take().and_then(|weak| weak.upgrade());
+        let next = node_ref.next.take();
+        match &prev {
+            None => self.head = next.clone(),
+            Some(prev_node) => prev_node.borrow_mut().next = next.clone(),
+        }
+        match next {
+            None => self.tail = prev,
+            Some(next_node) => next_node.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+        }
+        self.size -= 1;
+    }
+
+    pub fn move_to_head(&mut self, node: &DoubleLinkedNodeRef<T>) {
+        self.unlink(node);
+        self.link_at_head(node.clone());
+    }
+
+    pub fn move_to_tail(&mut self, node: &DoubleLinkedNodeRef<T>) {
+        self.unlink(node);
+        self.link_at_tail(node.clone());
+    }
+
+    // 摘下节点并取出数据，如果外部还持有该节点的其他强引用则返回 None
+    pub fn remove_node(&mut self, node: DoubleLinkedNodeRef<T>) -> Option<T> {
+        self.unlink(&node);
+        Rc::try_unwrap(node).ok().map(|cell| cell.into_inner().data)
+    }
+
+    pub fn get_head_node(&self) -> Option<DoubleLinkedNodeRef<T>> {
+        self.head.clone()
+    }
+
+    pub fn get_tail_node(&self) -> Option<DoubleLinkedNodeRef<T>> {
+        self.tail.clone()
+    }
+
+    pub fn clear(&mut self) {
+        // 逐个断开，避免长链表递归 drop 导致栈溢出
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = node.borrow_mut().next.take();
+        }
+        self.tail = None;
+        self.size = 0;
+    }
+
     pub fn delete_at_head(&mut self) -> Option<T>
     where
         T: Copy,
@@ -101,7 +164,7 @@ impl<T> DoubleLinkedList<T> {
             }
         }
         self.size -= 1;
-        return Some(data);
+        Some(data)
     }
 
     pub fn delete_at_tail(&mut self) -> Option<T>
@@ -109,20 +172,25 @@ impl<T> DoubleLinkedList<T> {
         T: Copy,
     {
         let tail = self.tail.take();
-        let tail_rc = match tail {
-            None => return None,
-            Some(node_rc) => node_rc,
-        };
+        let tail_rc = tail?;
         let data = tail_rc.borrow().data;
-        // 清空tail的next指向
-        if let Some(next_node_ref) = tail_rc.borrow_mut().prev.take() {
-            // next_node_ref的prev是弱引用，不用管
-            self.tail = next_node_ref.upgrade();
-            self.size -= 1;
-            return Some(data);
-        } else {
-            return None;
+        // 清空前一个节点的next指向
+        match tail_rc
+            .borrow_mut()
+            .prev
+            .take()
+            .and_then(|weak| weak.upgrade())
+        {
+            None => {
+                self.head = None;
+            }
+            Some(prev_node_ref) => {
+                prev_node_ref.borrow_mut().next = None;
+                self.tail = Some(prev_node_ref);
+            }
         }
+        self.size -= 1;
+        Some(data)
     }
 
     pub fn find(&mut self, data: T) -> Option<usize>
@@ -186,6 +254,12 @@ impl<T> DoubleLinkedList<T> {
     }
 }
 
+impl<T> Drop for DoubleLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
 // 迭代器的实现，Iterator是让当前结构可以遍历自身的元素，IntoIterator是让当前的结构变成一个集合，用于for循环遍历
 
 // impl<'a, T> Iterator for DoubleLinked<'a, T>
@@ -259,3 +333,114 @@ impl<T> DoubleLinkedList<T> {
 // }
 
 // 其他还有Debug Copy Clone Eq PartialEq，都可以进行实现
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{DoubleLinkedList, DoubleLinkedNodeRef};
+
+    // 顺着 next 正向走一遍、顺着 prev 反向走一遍，两边必须一致
+    fn collect(list: &DoubleLinkedList<u32>) -> Vec<u32> {
+        let mut forward = Vec::new();
+        let mut current = list.get_head_node();
+        while let Some(node) = current {
+            forward.push(node.borrow().data);
+            current = node.borrow().next.clone();
+        }
+        let mut backward = Vec::new();
+        let mut current = list.get_tail_node();
+        while let Some(node) = current {
+            backward.push(node.borrow().data);
+            current = node.borrow().prev.as_ref().and_then(|weak| weak.upgrade());
+        }
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), list.get_size());
+        forward
+    }
+
+    #[test]
+    fn insert_at_head_links_both_directions() {
+        let mut list = DoubleLinkedList::new();
+        let handles: Vec<DoubleLinkedNodeRef<u32>> =
+            (0..4).map(|value| list.insert_at_head(value)).collect();
+        assert_eq!(collect(&list), [3, 2, 1, 0]);
+        for (value, handle) in handles.iter().enumerate() {
+            assert_eq!(handle.borrow().data, value as u32);
+        }
+        assert!(Rc::ptr_eq(&list.get_head_node().unwrap(), &handles[3]));
+        assert!(Rc::ptr_eq(&list.get_tail_node().unwrap(), &handles[0]));
+        list.insert_at_tail(9);
+        assert_eq!(collect(&list), [3, 2, 1, 0, 9]);
+    }
+
+    #[test]
+    fn handles_move_and_unlink_in_place() {
+        let mut list = DoubleLinkedList::new();
+        let handles: Vec<DoubleLinkedNodeRef<u32>> =
+            (0..5).map(|value| list.insert_at_tail(value)).collect();
+        list.move_to_head(&handles[3]);
+        assert_eq!(collect(&list), [3, 0, 1, 2, 4]);
+        list.move_to_tail(&handles[0]);
+        assert_eq!(collect(&list), [3, 1, 2, 4, 0]);
+        list.move_to_tail(&handles[0]);
+        assert_eq!(collect(&list), [3, 1, 2, 4, 0]);
+        list.unlink(&handles[2]);
+        assert_eq!(collect(&list), [3, 1, 4, 0]);
+        // 摘下的节点可以重新接回去
+        list.link_at_head(handles[2].clone());
+        assert_eq!(collect(&list), [2, 3, 1, 4, 0]);
+        list.unlink(&handles[2]);
+        list.unlink(&handles[3]);
+        list.unlink(&handles[0]);
+        assert_eq!(collect(&list), [1, 4]);
+    }
+
+    #[test]
+    fn remove_node_with_other_handles_alive() {
+        let mut list = DoubleLinkedList::new();
+        let handles: Vec<DoubleLinkedNodeRef<u32>> =
+            (0..3).map(|value| list.insert_at_tail(value)).collect();
+        let [first, middle, last] = handles.try_into().ok().unwrap();
+        // 外面还握着一份引用，数据取不出来，但节点已经从链表上摘下
+        let extra = middle.clone();
+        assert_eq!(list.remove_node(middle), None);
+        assert_eq!(collect(&list), [0, 2]);
+        assert_eq!(extra.borrow().data, 1);
+        assert!(extra.borrow().next.is_none() && extra.borrow().prev.is_none());
+        assert_eq!(list.remove_node(last), Some(2));
+        assert_eq!(collect(&list), [0]);
+        assert_eq!(list.remove_node(first), Some(0));
+        assert!(list.is_empty() && list.get_tail_node().is_none());
+    }
+
+    #[test]
+    fn delete_at_both_ends() {
+        let mut list = DoubleLinkedList::new();
+        for value in 0..4 {
+            list.insert_at_tail(value);
+        }
+        assert_eq!(list.delete_at_head(), Some(0));
+        assert_eq!(list.delete_at_tail(), Some(3));
+        assert_eq!(collect(&list), [1, 2]);
+        assert_eq!(list.delete_at_tail(), Some(2));
+        assert_eq!(list.delete_at_head(), Some(1));
+        assert_eq!(list.delete_at_head(), None);
+        assert_eq!(list.delete_at_tail(), None);
+    }
+
+    #[test]
+    fn drop_releases_long_lists() {
+        let mut list = DoubleLinkedList::new();
+        let handle = list.insert_at_tail(0);
+        let weak = Rc::downgrade(&handle);
+        drop(handle);
+        for value in 1..200_000 {
+            list.insert_at_tail(value);
+        }
+        // 递归 drop 会在这里栈溢出
+        drop(list);
+        assert!(weak.upgrade().is_none());
+    }
+}
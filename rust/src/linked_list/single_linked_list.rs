@@ -12,7 +12,7 @@ struct SingleLinkedNode<T> {
 
 impl<T> SingleLinkedNode<T> {
     fn new(data: T, next: SingleLinked<T>) -> Box<Self> {
-        return Box::new(SingleLinkedNode { data, next });
+        Box::new(SingleLinkedNode { data, next })
     }
 }
 
@@ -59,15 +59,8 @@ impl<T> SingleLinkedList<T> {
         // }
         // current.next = Some(new_node);
         // self.size += 1;
-        loop {
-            match current.next {
-                Some(ref mut node) => {
-                    current = node;
-                }
-                None => {
-                    break;
-                }
-            }
+        while current.next.is_some() {
+            current = current.next.as_mut().unwrap();
         }
         current.next = Some(new_node);
         self.size += 1;
@@ -113,7 +106,7 @@ impl<T> SingleLinkedList<T> {
             }
             current = node.next.as_mut();
         }
-        return None;
+        None
     }
 
     pub fn find(&mut self, data: T) -> Option<usize>
@@ -174,7 +167,7 @@ impl<T> SingleLinkedList<T> {
 
         while let Some(node) = current {
             result = format!("{:?}", node.data);
-            if !node.next.is_none() {
+            if node.next.is_some() {
                 result = format!("{:?}", "->");
                 current = node.next.as_ref();
             }
@@ -2,7 +2,19 @@ use crate::array::fixed_array::FixedArray;
 use std::string::String;
 
 mod array;
+mod benchmark;
+mod cache;
+mod hash_table;
 mod linked_list;
+mod queue;
+mod range_query;
+mod stack;
+mod strings;
+#[cfg(test)]
+mod test_util;
+mod text;
+mod tree;
+mod trie;
 fn main() {
     let mut fixed_arr = FixedArray::<String>::new(20).unwrap();
     fixed_arr.append(String::from("hello world!")).unwrap();
@@ -0,0 +1,190 @@
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use crate::array::dynamic_array::DynamicArray;
+
+const MIN_SLOTS: usize = 4;
+// DynamicArray 只会在容量为 0 或下标越界时报错：容量都是正数，追加总是写在末尾，槽位下标都对槽位数取模
+const CAPACITY_IS_POSITIVE: &str = "Slot capacity is positive.";
+const SLOT_IN_BOUNDS: &str = "Slot index is below the slot count.";
+const QUEUED_SLOT_IS_FULL: &str = "Slots from head to head + size hold elements.";
+
+// 环形缓冲区实现的队列，底层是 DynamicArray，每个槽位是 Option，
+// head 是队首所在的槽位，满了以后按 growth_factor 扩容
+pub struct ArrayQueue<T> {
+    slots: DynamicArray<Option<T>>,
+    head: usize,
+    size: usize,
+    growth_factor: usize,
+}
+
+impl<T> ArrayQueue<T> {
+    pub fn new() -> Self {
+        ArrayQueue {
+            slots: DynamicArray::new(MIN_SLOTS).expect(CAPACITY_IS_POSITIVE),
+            head: 0,
+            size: 0,
+            growth_factor: 2,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Result<ArrayQueue<T>, String> {
+        let mut slots = DynamicArray::new(capacity)?;
+        for _ in 0..capacity {
+            slots.append(None)?;
+        }
+        Ok(ArrayQueue {
+            slots,
+            ..ArrayQueue::new()
+        })
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.slots.get_size()
+    }
+
+    fn slot(&self, offset: usize) -> usize {
+        (self.head + offset) % self.slots.get_size()
+    }
+
+    fn slot_mut(&mut self, index: usize) -> &mut Option<T> {
+        self.slots
+            .get_mut(index)
+            .expect(SLOT_IN_BOUNDS)
+            .expect(SLOT_IN_BOUNDS)
+    }
+
+    // 扩容时按队列顺序搬到新数组的开头
+    fn grow(&mut self) {
+        let capacity = (self.slots.get_size() * self.growth_factor).max(MIN_SLOTS);
+        let mut slots = DynamicArray::new(capacity).expect(CAPACITY_IS_POSITIVE);
+        for offset in 0..self.size {
+            let index = self.slot(offset);
+            slots
+                .append(self.slot_mut(index).take())
+                .expect(SLOT_IN_BOUNDS);
+        }
+        while slots.get_size() < capacity {
+            slots.append(None).expect(SLOT_IN_BOUNDS);
+        }
+        self.slots = slots;
+        self.head = 0;
+    }
+
+    pub fn enqueue(&mut self, element: T) {
+        if self.size == self.slots.get_size() {
+            self.grow();
+        }
+        let index = self.slot(self.size);
+        *self.slot_mut(index) = Some(element);
+        self.size += 1;
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+        let element = self.slot_mut(self.head).take();
+        self.head = (self.head + 1) % self.slots.get_size();
+        self.size -= 1;
+        element
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+        self.slots.get(self.head).expect(SLOT_IN_BOUNDS)?.as_ref()
+    }
+
+    pub fn clear(&mut self) {
+        for index in 0..self.slots.get_size() {
+            *self.slot_mut(index) = None;
+        }
+        self.head = 0;
+        self.size = 0;
+    }
+
+    // 从队首到队尾
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let slots = self.slots.get_elements();
+        (0..self.size).map(move |offset| {
+            slots[self.slot(offset)]
+                .as_ref()
+                .expect(QUEUED_SLOT_IS_FULL)
+        })
+    }
+}
+
+impl<T> Default for ArrayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = ArrayQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for ArrayQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.enqueue(element);
+        }
+    }
+}
+
+impl<T> Debug for ArrayQueue<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn random_operations_match_vec_deque() {
+        let mut random = TestRng::with_seed(392);
+        // 容量 3 不是 2 的幂，队首绕回开头之后再扩容，检查搬运顺序
+        let mut queue = ArrayQueue::with_capacity(3).unwrap();
+        let mut expected = VecDeque::new();
+        for step in 0..2000 {
+            if random.below(5) < 2 {
+                assert_eq!(queue.dequeue(), expected.pop_front());
+            } else {
+                queue.enqueue(step);
+                expected.push_back(step);
+            }
+            assert_eq!(queue.get_size(), expected.len());
+            assert_eq!(queue.peek(), expected.front());
+            assert!(queue.get_capacity() >= queue.get_size());
+            assert!(queue.iter().eq(expected.iter()));
+        }
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+        queue.extend([1, 2]);
+        assert_eq!(queue.dequeue(), Some(1));
+        assert!(ArrayQueue::<i32>::with_capacity(0).is_err());
+    }
+}
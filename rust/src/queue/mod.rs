@@ -0,0 +1 @@
+pub mod array_queue;
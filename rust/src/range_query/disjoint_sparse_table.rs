@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, ops::Range};
+
+use super::monoid::Monoid;
+use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+
+// 分离稀疏表：第 h 层把下标按 2^(h+1) 分块，每块从中点 mid 分开，
+// 左半记到 mid 为止的后缀聚合，右半记从 mid 开始的前缀聚合。
+// 闭区间 [l, r] 的首尾下标最高的不同位是 h 时，两者恰好落在第 h 层同一块的两侧，
+// 一次合并就是答案。只要求结合律，不要求幂等和交换律
+pub struct DisjointSparseTable<M: Monoid> {
+    values: Vec<M::Value>,
+    table: Vec<Vec<M::Value>>,
+}
+
+impl<M: Monoid> DisjointSparseTable<M> {
+    pub fn from_slice(values: &[M::Value]) -> Self {
+        let len = values.len();
+        let levels = if len < 2 {
+            0
+        } else {
+            (len - 1).ilog2() as usize + 1
+        };
+        let mut table = Vec::with_capacity(levels);
+        for h in 0..levels {
+            let half = 1 << h;
+            let mut level = values.to_vec();
+            let mut mid = half;
+            while mid < len {
+                for i in (mid - half..mid - 1).rev() {
+                    level[i] = M::combine(&values[i], &level[i + 1]);
+                }
+                for i in mid + 1..(mid + half).min(len) {
+                    level[i] = M::combine(&level[i - 1], &values[i]);
+                }
+                mid += 2 * half;
+            }
+            table.push(level);
+        }
+        DisjointSparseTable {
+            values: values.to_vec(),
+            table,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&M::Value> {
+        self.values.get(index)
+    }
+
+    // 空区间返回单位元
+    pub fn query(&self, range: Range<usize>) -> Result<M::Value, String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        let (left, right) = (range.start, range.end.wrapping_sub(1));
+        match range.len() {
+            0 => Ok(M::identity()),
+            1 => Ok(self.values[left].clone()),
+            _ => {
+                let level = &self.table[(left ^ right).ilog2() as usize];
+                Ok(M::combine(&level[left], &level[right]))
+            }
+        }
+    }
+}
+
+impl<M: Monoid> From<&FixedArray<M::Value>> for DisjointSparseTable<M> {
+    fn from(array: &FixedArray<M::Value>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<M: Monoid> From<&DynamicArray<M::Value>> for DisjointSparseTable<M> {
+    fn from(array: &DynamicArray<M::Value>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<M: Monoid> Debug for DisjointSparseTable<M>
+where
+    M::Value: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.values).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisjointSparseTable;
+    use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+    use crate::range_query::monoid::{Gcd, Max, Min, Monoid, Sum};
+    use crate::test_util::rng::TestRng;
+
+    // 0、1 以及 2^k 前后的长度，覆盖层数刚好增加的边界
+    const LENGTHS: [usize; 15] = [0, 1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 31, 32, 33];
+
+    // 不满足交换律和幂等性的拼接，分离稀疏表也必须支持
+    struct Concat;
+
+    impl Monoid for Concat {
+        type Value = Vec<usize>;
+
+        fn identity() -> Vec<usize> {
+            Vec::new()
+        }
+
+        fn combine(left: &Vec<usize>, right: &Vec<usize>) -> Vec<usize> {
+            [left.as_slice(), right.as_slice()].concat()
+        }
+    }
+
+    fn check_all_ranges<M>(table: &DisjointSparseTable<M>, values: &[M::Value])
+    where
+        M: Monoid,
+        M::Value: PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(table.get_size(), values.len());
+        for start in 0..=values.len() {
+            for end in start..=values.len() {
+                let expected = values[start..end]
+                    .iter()
+                    .fold(M::identity(), |acc, x| M::combine(&acc, x));
+                assert_eq!(table.query(start..end), Ok(expected));
+            }
+        }
+        let (start, end) = (1, 0);
+        assert!(table.query(start..end).is_err());
+        assert!(table.query(0..values.len() + 1).is_err());
+        assert_eq!(table.get(values.len()), None);
+    }
+
+    #[test]
+    fn random_queries_match_brute_force() {
+        let mut random = TestRng::with_seed(492);
+        for len in LENGTHS {
+            let values: Vec<i64> = (0..len).map(|_| random.below(100) as i64 - 50).collect();
+            check_all_ranges(
+                &DisjointSparseTable::<Min<i64>>::from_slice(&values),
+                &values,
+            );
+            check_all_ranges(
+                &DisjointSparseTable::<Max<i64>>::from_slice(&values),
+                &values,
+            );
+            check_all_ranges(
+                &DisjointSparseTable::<Sum<i64>>::from_slice(&values),
+                &values,
+            );
+            let multiples: Vec<u64> = (0..len).map(|_| 6 * random.below(20)).collect();
+            check_all_ranges(
+                &DisjointSparseTable::<Gcd<u64>>::from_slice(&multiples),
+                &multiples,
+            );
+            let singletons: Vec<Vec<usize>> = (0..len).map(|i| vec![i]).collect();
+            check_all_ranges(
+                &DisjointSparseTable::<Concat>::from_slice(&singletons),
+                &singletons,
+            );
+        }
+    }
+
+    #[test]
+    fn builds_from_fixed_and_dynamic_arrays() {
+        let mut random = TestRng::with_seed(493);
+        for len in LENGTHS {
+            let values: Vec<u32> = (0..len).map(|_| random.below(1000) as u32).collect();
+            let mut fixed = FixedArray::new(len.max(1)).unwrap();
+            let mut dynamic = DynamicArray::new(1).unwrap();
+            for &value in values.iter() {
+                fixed.append(value).unwrap();
+                dynamic.append(value).unwrap();
+            }
+            check_all_ranges(&DisjointSparseTable::<Min<u32>>::from(&fixed), &values);
+            check_all_ranges(&DisjointSparseTable::<Max<u32>>::from(&dynamic), &values);
+        }
+    }
+}
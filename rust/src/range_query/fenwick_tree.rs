@@ -0,0 +1,223 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Range, Sub},
+};
+
+use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+
+// 树状数组：下标从 1 开始，tree[i] 存 (i - lowbit(i), i] 这一段的和，
+// 单点加和前缀和都只沿 lowbit 跳 O(log n) 次
+pub struct FenwickTree<T> {
+    tree: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(len: usize) -> Self {
+        FenwickTree {
+            tree: vec![T::default(); len + 1],
+        }
+    }
+
+    // 每个节点建好后把自己加到父节点上，整体 O(n)
+    pub fn from_slice(values: &[T]) -> Self {
+        let mut tree = vec![T::default(); values.len() + 1];
+        tree[1..].copy_from_slice(values);
+        for i in 1..tree.len() {
+            let parent = i + lowbit(i);
+            if parent < tree.len() {
+                tree[parent] = tree[parent] + tree[i];
+            }
+        }
+        FenwickTree { tree }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_size() == 0
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        if index >= self.get_size() {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                index,
+                self.get_size()
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, index: usize, delta: T) -> Result<(), String> {
+        self.check_index(index)?;
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] = self.tree[i] + delta;
+            i += lowbit(i);
+        }
+        Ok(())
+    }
+
+    // 前 end 个元素的和
+    pub fn prefix_sum(&self, end: usize) -> Result<T, String> {
+        self.check_range(&(0..end))?;
+        let mut sum = T::default();
+        let mut i = end;
+        while i > 0 {
+            sum = sum + self.tree[i];
+            i -= lowbit(i);
+        }
+        Ok(sum)
+    }
+
+    pub fn range_sum(&self, range: Range<usize>) -> Result<T, String> {
+        self.check_range(&range)?;
+        Ok(self.prefix_sum(range.end)? - self.prefix_sum(range.start)?)
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.check_index(index).ok()?;
+        self.range_sum(index..index + 1).ok()
+    }
+
+    // 改大时加上差值；改小时从覆盖它的节点里减去差值，这些节点的和都包含旧值，无符号类型也不会下溢
+    pub fn set(&mut self, index: usize, value: T) -> Result<(), String>
+    where
+        T: PartialOrd,
+    {
+        self.check_index(index)?;
+        let old = self.range_sum(index..index + 1)?;
+        if value >= old {
+            return self.add(index, value - old);
+        }
+        let delta = old - value;
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] = self.tree[i] - delta;
+            i += lowbit(i);
+        }
+        Ok(())
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        (0..self.get_size())
+            .filter_map(|index| self.get(index))
+            .collect()
+    }
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    // 前缀和第一次达到 target 的下标，即最小的 i 使 prefix_sum(i + 1) >= target；
+    // 要求所有元素非负，前缀和才单调。从最高位开始逐位试探，总和不够时返回 get_size()
+    pub fn lower_bound(&self, target: T) -> usize {
+        let len = self.get_size();
+        if target <= T::default() || len == 0 {
+            return 0;
+        }
+        let mut position = 0;
+        let mut remaining = target;
+        let mut step = 1 << len.ilog2();
+        while step > 0 {
+            let next = position + step;
+            if next <= len && self.tree[next] < remaining {
+                position = next;
+                remaining = remaining - self.tree[next];
+            }
+            step >>= 1;
+        }
+        position
+    }
+}
+
+pub(super) fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl<T> From<&FixedArray<T>> for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(array: &FixedArray<T>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<T> From<&DynamicArray<T>> for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(array: &DynamicArray<T>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<T> Debug for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FenwickTree;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn set_can_decrease_unsigned_values() {
+        let mut tree = FenwickTree::<u64>::from_slice(&[5, 5]);
+        tree.set(0, 1).unwrap();
+        assert_eq!(tree.to_vec(), vec![1, 5]);
+        assert_eq!(tree.prefix_sum(2), Ok(6));
+    }
+
+    #[test]
+    fn random_updates_match_brute_force() {
+        let mut random = TestRng::with_seed(48);
+        let mut expected = vec![0_u64; 45];
+        let mut tree = FenwickTree::new(expected.len());
+        for _ in 0..1000 {
+            let index = random.index(45);
+            let value = random.below(10);
+            if random.next_u64().is_multiple_of(2) {
+                tree.set(index, value).unwrap();
+                expected[index] = value;
+            } else {
+                tree.add(index, value).unwrap();
+                expected[index] += value;
+            }
+            let end = random.index(46);
+            assert_eq!(tree.prefix_sum(end), Ok(expected[..end].iter().sum()));
+            let target = random.below(200) + 1;
+            let lower_bound = (0..expected.len())
+                .find(|&i| expected[..=i].iter().sum::<u64>() >= target)
+                .unwrap_or(expected.len());
+            assert_eq!(tree.lower_bound(target), lower_bound);
+        }
+        assert_eq!(tree.to_vec(), expected);
+    }
+}
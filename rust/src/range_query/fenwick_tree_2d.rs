@@ -0,0 +1,211 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Range, Sub},
+};
+
+use super::fenwick_tree::lowbit;
+
+// 二维树状数组：行和列各自按 lowbit 跳，tree[i][j] 存以 (i, j) 为右下角的一块子矩形的和，
+// 单点加和前缀矩形求和都是 O(log rows * log cols)
+pub struct FenwickTree2D<T> {
+    rows: usize,
+    cols: usize,
+    // (rows + 1) * (cols + 1) 按行展开，第 0 行和第 0 列不用
+    tree: Vec<T>,
+}
+
+impl<T> FenwickTree2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(rows: usize, cols: usize) -> Self {
+        FenwickTree2D {
+            rows,
+            cols,
+            tree: vec![T::default(); (rows + 1) * (cols + 1)],
+        }
+    }
+
+    // 与一维相同的 O(rows * cols) 建树：先沿行方向、再沿列方向把每个节点加到父节点上
+    pub fn from_rows(grid: &[Vec<T>]) -> Result<Self, String> {
+        let rows = grid.len();
+        let cols = grid.first().map_or(0, Vec::len);
+        if let Some(row) = grid.iter().position(|row| row.len() != cols) {
+            return Err(format!(
+                "Row {} has {} columns, expected {}.",
+                row,
+                grid[row].len(),
+                cols
+            ));
+        }
+        let mut tree = FenwickTree2D::new(rows, cols);
+        for (i, row) in grid.iter().enumerate() {
+            let start = tree.offset(i + 1, 1);
+            tree.tree[start..start + cols].copy_from_slice(row);
+        }
+        for i in 1..=rows {
+            for j in 1..=cols {
+                let parent = j + lowbit(j);
+                if parent <= cols {
+                    let (child, parent) = (tree.offset(i, j), tree.offset(i, parent));
+                    tree.tree[parent] = tree.tree[parent] + tree.tree[child];
+                }
+            }
+        }
+        for i in 1..=rows {
+            let parent = i + lowbit(i);
+            if parent <= rows {
+                for j in 1..=cols {
+                    let (child, parent) = (tree.offset(i, j), tree.offset(parent, j));
+                    tree.tree[parent] = tree.tree[parent] + tree.tree[child];
+                }
+            }
+        }
+        Ok(tree)
+    }
+
+    fn offset(&self, row: usize, col: usize) -> usize {
+        row * (self.cols + 1) + col
+    }
+
+    pub fn get_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn get_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0 || self.cols == 0
+    }
+
+    fn check_cell(&self, row: usize, col: usize) -> Result<(), String> {
+        if row >= self.rows || col >= self.cols {
+            return Err(format!(
+                "Cell out of bounds: ({}, {}). Current shape: {} x {}.",
+                row, col, self.rows, self.cols
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_ranges(&self, rows: &Range<usize>, cols: &Range<usize>) -> Result<(), String> {
+        if rows.start > rows.end
+            || rows.end > self.rows
+            || cols.start > cols.end
+            || cols.end > self.cols
+        {
+            return Err(format!(
+                "Invalid range: {}..{} x {}..{}. Current shape: {} x {}.",
+                rows.start, rows.end, cols.start, cols.end, self.rows, self.cols
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, row: usize, col: usize, delta: T) -> Result<(), String> {
+        self.check_cell(row, col)?;
+        let mut i = row + 1;
+        while i <= self.rows {
+            let mut j = col + 1;
+            while j <= self.cols {
+                let offset = self.offset(i, j);
+                self.tree[offset] = self.tree[offset] + delta;
+                j += lowbit(j);
+            }
+            i += lowbit(i);
+        }
+        Ok(())
+    }
+
+    // 左上角 row_end x col_end 矩形的和
+    pub fn prefix_sum(&self, row_end: usize, col_end: usize) -> Result<T, String> {
+        self.check_ranges(&(0..row_end), &(0..col_end))?;
+        let mut sum = T::default();
+        let mut i = row_end;
+        while i > 0 {
+            let mut j = col_end;
+            while j > 0 {
+                sum = sum + self.tree[self.offset(i, j)];
+                j -= lowbit(j);
+            }
+            i -= lowbit(i);
+        }
+        Ok(sum)
+    }
+
+    // 容斥：大矩形减去上方一块，再减去左侧去掉左上角的一块；先做减法的两项都非负，无符号类型也不会下溢
+    pub fn range_sum(&self, rows: Range<usize>, cols: Range<usize>) -> Result<T, String> {
+        self.check_ranges(&rows, &cols)?;
+        let whole = self.prefix_sum(rows.end, cols.end)?;
+        let above = self.prefix_sum(rows.start, cols.end)?;
+        let left = self.prefix_sum(rows.end, cols.start)?;
+        let corner = self.prefix_sum(rows.start, cols.start)?;
+        Ok((whole - above) - (left - corner))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<T> {
+        self.check_cell(row, col).ok()?;
+        self.range_sum(row..row + 1, col..col + 1).ok()
+    }
+
+    // 与 FenwickTree::set 相同，改小时直接减去差值，避免无符号类型下溢
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Result<(), String>
+    where
+        T: PartialOrd,
+    {
+        self.check_cell(row, col)?;
+        let old = self.range_sum(row..row + 1, col..col + 1)?;
+        if value >= old {
+            return self.add(row, col, value - old);
+        }
+        let delta = old - value;
+        let mut i = row + 1;
+        while i <= self.rows {
+            let mut j = col + 1;
+            while j <= self.cols {
+                let offset = self.offset(i, j);
+                self.tree[offset] = self.tree[offset] - delta;
+                j += lowbit(j);
+            }
+            i += lowbit(i);
+        }
+        Ok(())
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        (0..self.rows)
+            .map(|row| {
+                (0..self.cols)
+                    .filter_map(|col| self.get(row, col))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<T> Debug for FenwickTree2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.to_rows()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FenwickTree2D;
+
+    #[test]
+    fn set_can_decrease_unsigned_values() {
+        let mut tree = FenwickTree2D::<u32>::from_rows(&[vec![3, 4, 5], vec![6, 7, 8]]).unwrap();
+        tree.set(1, 1, 2).unwrap();
+        tree.set(0, 2, 9).unwrap();
+        assert_eq!(tree.to_rows(), vec![vec![3, 4, 9], vec![6, 2, 8]]);
+        assert_eq!(tree.range_sum(0..2, 1..3), Ok(23));
+    }
+}
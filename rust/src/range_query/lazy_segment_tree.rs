@@ -0,0 +1,374 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, ops::Range};
+
+use super::monoid::{MapMonoid, Monoid};
+use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+
+type Value<F> = <<F as MapMonoid>::Monoid as Monoid>::Value;
+
+// 带懒标记的线段树：区间修改只把映射记在完整覆盖的节点上，
+// 访问到子节点之前再下推，区间修改和区间查询都是 O(log n)
+pub struct LazySegmentTree<F: MapMonoid> {
+    len: usize,
+    size: usize,
+    log: u32,
+    data: Vec<Value<F>>,
+    // 只有内部节点有懒标记：已经作用到 data[k]、还没有下推给孩子的映射
+    lazy: Vec<F::Map>,
+}
+
+impl<F: MapMonoid> LazySegmentTree<F> {
+    // len 个 value。单位元不一定是合法的叶子：区间求和的叶子要写成 (x, 1)，
+    // 单位元 (0, 0) 长度为 0，之后的区间加和区间赋值都作用不上
+    pub fn new(len: usize, value: Value<F>) -> Self {
+        Self::from_slice(&vec![value; len])
+    }
+
+    pub fn from_slice(values: &[Value<F>]) -> Self {
+        let len = values.len();
+        let size = len.next_power_of_two();
+        let mut data = vec![F::Monoid::identity(); 2 * size];
+        data[size..size + len].clone_from_slice(values);
+        let mut tree = LazySegmentTree {
+            len,
+            size,
+            log: size.trailing_zeros(),
+            data,
+            lazy: vec![F::identity_map(); size],
+        };
+        for k in (1..size).rev() {
+            tree.update(k);
+        }
+        tree
+    }
+
+    fn update(&mut self, k: usize) {
+        self.data[k] = F::Monoid::combine(&self.data[2 * k], &self.data[2 * k + 1]);
+    }
+
+    fn apply_node(&mut self, k: usize, map: &F::Map) {
+        self.data[k] = F::apply(map, &self.data[k]);
+        if k < self.size {
+            self.lazy[k] = F::compose(map, &self.lazy[k]);
+        }
+    }
+
+    fn push(&mut self, k: usize) {
+        let map = std::mem::replace(&mut self.lazy[k], F::identity_map());
+        self.apply_node(2 * k, &map);
+        self.apply_node(2 * k + 1, &map);
+    }
+
+    // 区间两端所在的祖先链上先把标记推下去，区间内部的节点不受影响
+    fn push_boundaries(&mut self, left: usize, right: usize) {
+        for i in (1..=self.log).rev() {
+            if (left >> i) << i != left {
+                self.push(left >> i);
+            }
+            if (right >> i) << i != right {
+                self.push((right - 1) >> i);
+            }
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        if index >= self.len {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                index, self.len
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.len {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start, range.end, self.len
+            ));
+        }
+        Ok(())
+    }
+
+    // 读单点也要先把路径上的标记推下去，所以需要 &mut self
+    pub fn get(&mut self, index: usize) -> Option<Value<F>> {
+        self.check_index(index).ok()?;
+        let leaf = self.size + index;
+        for i in (1..=self.log).rev() {
+            self.push(leaf >> i);
+        }
+        Some(self.data[leaf].clone())
+    }
+
+    pub fn set(&mut self, index: usize, value: Value<F>) -> Result<(), String> {
+        self.check_index(index)?;
+        let leaf = self.size + index;
+        for i in (1..=self.log).rev() {
+            self.push(leaf >> i);
+        }
+        self.data[leaf] = value;
+        for i in 1..=self.log {
+            self.update(leaf >> i);
+        }
+        Ok(())
+    }
+
+    pub fn query(&mut self, range: Range<usize>) -> Result<Value<F>, String> {
+        self.check_range(&range)?;
+        if range.is_empty() {
+            return Ok(F::Monoid::identity());
+        }
+        let mut left = range.start + self.size;
+        let mut right = range.end + self.size;
+        self.push_boundaries(left, right);
+        let mut left_sum = F::Monoid::identity();
+        let mut right_sum = F::Monoid::identity();
+        while left < right {
+            if left & 1 == 1 {
+                left_sum = F::Monoid::combine(&left_sum, &self.data[left]);
+                left += 1;
+            }
+            if right & 1 == 1 {
+                right -= 1;
+                right_sum = F::Monoid::combine(&self.data[right], &right_sum);
+            }
+            left >>= 1;
+            right >>= 1;
+        }
+        Ok(F::Monoid::combine(&left_sum, &right_sum))
+    }
+
+    pub fn query_all(&self) -> Value<F> {
+        self.data[1].clone()
+    }
+
+    pub fn apply_at(&mut self, index: usize, map: F::Map) -> Result<(), String> {
+        self.check_index(index)?;
+        let leaf = self.size + index;
+        for i in (1..=self.log).rev() {
+            self.push(leaf >> i);
+        }
+        self.data[leaf] = F::apply(&map, &self.data[leaf]);
+        for i in 1..=self.log {
+            self.update(leaf >> i);
+        }
+        Ok(())
+    }
+
+    // 把映射作用到区间内每个元素上：先在覆盖区间的节点上打标记，再重算两端祖先
+    pub fn apply(&mut self, range: Range<usize>, map: F::Map) -> Result<(), String> {
+        self.check_range(&range)?;
+        if range.is_empty() {
+            return Ok(());
+        }
+        let start = range.start + self.size;
+        let end = range.end + self.size;
+        self.push_boundaries(start, end);
+        let (mut left, mut right) = (start, end);
+        while left < right {
+            if left & 1 == 1 {
+                self.apply_node(left, &map);
+                left += 1;
+            }
+            if right & 1 == 1 {
+                right -= 1;
+                self.apply_node(right, &map);
+            }
+            left >>= 1;
+            right >>= 1;
+        }
+        for i in 1..=self.log {
+            if (start >> i) << i != start {
+                self.update(start >> i);
+            }
+            if (end >> i) << i != end {
+                self.update((end - 1) >> i);
+            }
+        }
+        Ok(())
+    }
+
+    // 与 SegmentTree::max_right 相同，只是向下定位时要先下推标记
+    pub fn max_right<P>(&mut self, left: usize, predicate: P) -> Result<usize, String>
+    where
+        P: Fn(&Value<F>) -> bool,
+    {
+        if left > self.len {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                left, self.len
+            ));
+        }
+        if !predicate(&F::Monoid::identity()) {
+            return Err("Predicate must hold for the identity element.".to_owned());
+        }
+        if left == self.len {
+            return Ok(self.len);
+        }
+        let mut left = left + self.size;
+        for i in (1..=self.log).rev() {
+            self.push(left >> i);
+        }
+        let mut sum = F::Monoid::identity();
+        loop {
+            while left.is_multiple_of(2) {
+                left >>= 1;
+            }
+            let combined = F::Monoid::combine(&sum, &self.data[left]);
+            if !predicate(&combined) {
+                while left < self.size {
+                    self.push(left);
+                    left *= 2;
+                    let combined = F::Monoid::combine(&sum, &self.data[left]);
+                    if predicate(&combined) {
+                        sum = combined;
+                        left += 1;
+                    }
+                }
+                return Ok(left - self.size);
+            }
+            sum = combined;
+            left += 1;
+            if left.is_power_of_two() {
+                return Ok(self.len);
+            }
+        }
+    }
+
+    pub fn min_left<P>(&mut self, right: usize, predicate: P) -> Result<usize, String>
+    where
+        P: Fn(&Value<F>) -> bool,
+    {
+        if right > self.len {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                right, self.len
+            ));
+        }
+        if !predicate(&F::Monoid::identity()) {
+            return Err("Predicate must hold for the identity element.".to_owned());
+        }
+        if right == 0 {
+            return Ok(0);
+        }
+        let mut right = right + self.size;
+        for i in (1..=self.log).rev() {
+            self.push((right - 1) >> i);
+        }
+        let mut sum = F::Monoid::identity();
+        loop {
+            right -= 1;
+            while right > 1 && right % 2 == 1 {
+                right >>= 1;
+            }
+            let combined = F::Monoid::combine(&self.data[right], &sum);
+            if !predicate(&combined) {
+                while right < self.size {
+                    self.push(right);
+                    right = 2 * right + 1;
+                    let combined = F::Monoid::combine(&self.data[right], &sum);
+                    if predicate(&combined) {
+                        sum = combined;
+                        right -= 1;
+                    }
+                }
+                return Ok(right + 1 - self.size);
+            }
+            sum = combined;
+            if right.is_power_of_two() {
+                return Ok(0);
+            }
+        }
+    }
+
+    // 只读时不能下推标记，把祖先链上的映射由近及远逐层作用到叶子上
+    pub fn to_vec(&self) -> Vec<Value<F>> {
+        (0..self.len)
+            .map(|index| {
+                let leaf = self.size + index;
+                let mut value = self.data[leaf].clone();
+                for i in 1..=self.log {
+                    value = F::apply(&self.lazy[leaf >> i], &value);
+                }
+                value
+            })
+            .collect()
+    }
+}
+
+impl<F: MapMonoid> From<&FixedArray<Value<F>>> for LazySegmentTree<F> {
+    fn from(array: &FixedArray<Value<F>>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<F: MapMonoid> From<&DynamicArray<Value<F>>> for LazySegmentTree<F> {
+    fn from(array: &DynamicArray<Value<F>>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<F: MapMonoid> Debug for LazySegmentTree<F>
+where
+    Value<F>: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LazySegmentTree;
+    use crate::range_query::monoid::{RangeAddMin, RangeAddSum, RangeAssignSum};
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn new_fills_leaves_with_the_given_value() {
+        let mut sums = LazySegmentTree::<RangeAddSum<i64>>::new(5, (0, 1));
+        sums.apply(0..5, 3).unwrap();
+        assert_eq!(sums.query(0..5), Ok((15, 5)));
+
+        let mut assigned = LazySegmentTree::<RangeAssignSum<i64>>::new(5, (0, 1));
+        assigned.apply(1..4, Some(2)).unwrap();
+        assert_eq!(assigned.query(0..5), Ok((6, 5)));
+
+        let mut mins = LazySegmentTree::<RangeAddMin<i64>>::new(5, 0);
+        mins.apply(0..3, 4).unwrap();
+        assert_eq!(mins.query(0..5), Ok(0));
+        assert_eq!(mins.query(0..3), Ok(4));
+    }
+
+    #[test]
+    fn random_range_add_matches_brute_force() {
+        let mut random = TestRng::with_seed(47);
+        let mut expected = vec![0_i64; 37];
+        let leaves: Vec<(i64, i64)> = expected.iter().map(|&x| (x, 1)).collect();
+        let mut tree = LazySegmentTree::<RangeAddSum<i64>>::from_slice(&leaves);
+        for _ in 0..1000 {
+            let a = random.index(38);
+            let b = random.index(38);
+            let range = a.min(b)..a.max(b);
+            if random.next_u64().is_multiple_of(2) {
+                let delta = random.below(21) as i64 - 10;
+                tree.apply(range.clone(), delta).unwrap();
+                expected[range].iter_mut().for_each(|x| *x += delta);
+            } else {
+                let sum = expected[range.clone()].iter().sum();
+                assert_eq!(tree.query(range.clone()), Ok((sum, range.len() as i64)));
+            }
+        }
+        let values: Vec<(i64, i64)> = expected.iter().map(|&x| (x, 1)).collect();
+        assert_eq!(tree.to_vec(), values);
+    }
+}
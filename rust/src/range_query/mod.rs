@@ -0,0 +1,8 @@
+pub mod disjoint_sparse_table;
+pub mod fenwick_tree;
+pub mod fenwick_tree_2d;
+pub mod lazy_segment_tree;
+pub mod monoid;
+pub mod range_fenwick_tree;
+pub mod segment_tree;
+pub mod sparse_table;
@@ -0,0 +1,306 @@
+#![allow(dead_code)]
+
+use std::{
+    marker::PhantomData,
+    ops::{Add, Mul, Rem},
+};
+
+// 幺半群：满足结合律的二元运算加上单位元，线段树只依赖这两条性质
+pub trait Monoid {
+    type Value: Clone;
+
+    fn identity() -> Self::Value;
+
+    fn combine(left: &Self::Value, right: &Self::Value) -> Self::Value;
+}
+
+// 作用在幺半群上的映射族：映射之间能复合，且对整段区间的聚合值可以直接作用，
+// 懒标记线段树靠它把区间修改推迟到真正访问子节点的时候
+pub trait MapMonoid {
+    type Monoid: Monoid;
+    type Map: Clone;
+
+    fn identity_map() -> Self::Map;
+
+    fn apply(
+        map: &Self::Map,
+        value: &<Self::Monoid as Monoid>::Value,
+    ) -> <Self::Monoid as Monoid>::Value;
+
+    // 先做 inner 再做 outer
+    fn compose(outer: &Self::Map, inner: &Self::Map) -> Self::Map;
+}
+
+// 幂等：x 与自身合并仍是 x。区间被两段重叠的子区间覆盖时结果不变，稀疏表靠这一点做 O(1) 查询
+pub trait IdempotentMonoid: Monoid {}
+
+// 取值范围的上下界，作为最小值和最大值幺半群的单位元
+pub trait Bounded {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+impl Bounded for i32 {
+    const MIN: Self = i32::MIN;
+    const MAX: Self = i32::MAX;
+}
+
+impl Bounded for i64 {
+    const MIN: Self = i64::MIN;
+    const MAX: Self = i64::MAX;
+}
+
+impl Bounded for u32 {
+    const MIN: Self = u32::MIN;
+    const MAX: Self = u32::MAX;
+}
+
+impl Bounded for u64 {
+    const MIN: Self = u64::MIN;
+    const MAX: Self = u64::MAX;
+}
+
+impl Bounded for usize {
+    const MIN: Self = usize::MIN;
+    const MAX: Self = usize::MAX;
+}
+
+pub struct Sum<T>(PhantomData<T>);
+
+impl<T> Monoid for Sum<T>
+where
+    T: Copy + Default + Add<Output = T>,
+{
+    type Value = T;
+
+    fn identity() -> T {
+        T::default()
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left + *right
+    }
+}
+
+pub struct Min<T>(PhantomData<T>);
+
+impl<T> Monoid for Min<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type Value = T;
+
+    fn identity() -> T {
+        T::MAX
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left.min(right)
+    }
+}
+
+pub struct Max<T>(PhantomData<T>);
+
+impl<T> Monoid for Max<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type Value = T;
+
+    fn identity() -> T {
+        T::MIN
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left.max(right)
+    }
+}
+
+impl<T> IdempotentMonoid for Min<T> where T: Copy + Ord + Bounded {}
+
+impl<T> IdempotentMonoid for Max<T> where T: Copy + Ord + Bounded {}
+
+// 最大公约数，单位元是 0；只对非负数有意义
+pub struct Gcd<T>(PhantomData<T>);
+
+impl<T> Monoid for Gcd<T>
+where
+    T: Copy + Default + Eq + Rem<Output = T>,
+{
+    type Value = T;
+
+    fn identity() -> T {
+        T::default()
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        let (mut a, mut b) = (*left, *right);
+        while b != T::default() {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+}
+
+impl<T> IdempotentMonoid for Gcd<T> where T: Copy + Default + Eq + Rem<Output = T> {}
+
+// 值为 (区间和, 区间长度)，叶子写成 (x, 1)；区间加和区间赋值都要用到长度
+pub struct SumWithLength<T>(PhantomData<T>);
+
+impl<T> Monoid for SumWithLength<T>
+where
+    T: Copy + Default + Add<Output = T>,
+{
+    type Value = (T, T);
+
+    fn identity() -> (T, T) {
+        (T::default(), T::default())
+    }
+
+    fn combine(left: &(T, T), right: &(T, T)) -> (T, T) {
+        (left.0 + right.0, left.1 + right.1)
+    }
+}
+
+// 区间加、区间求最小值
+pub struct RangeAddMin<T>(PhantomData<T>);
+
+impl<T> MapMonoid for RangeAddMin<T>
+where
+    T: Copy + Ord + Bounded + Default + Add<Output = T>,
+{
+    type Monoid = Min<T>;
+    type Map = T;
+
+    fn identity_map() -> T {
+        T::default()
+    }
+
+    fn apply(map: &T, value: &T) -> T {
+        *value + *map
+    }
+
+    fn compose(outer: &T, inner: &T) -> T {
+        *outer + *inner
+    }
+}
+
+pub struct RangeAddMax<T>(PhantomData<T>);
+
+impl<T> MapMonoid for RangeAddMax<T>
+where
+    T: Copy + Ord + Bounded + Default + Add<Output = T>,
+{
+    type Monoid = Max<T>;
+    type Map = T;
+
+    fn identity_map() -> T {
+        T::default()
+    }
+
+    fn apply(map: &T, value: &T) -> T {
+        *value + *map
+    }
+
+    fn compose(outer: &T, inner: &T) -> T {
+        *outer + *inner
+    }
+}
+
+pub struct RangeAddSum<T>(PhantomData<T>);
+
+impl<T> MapMonoid for RangeAddSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Monoid = SumWithLength<T>;
+    type Map = T;
+
+    fn identity_map() -> T {
+        T::default()
+    }
+
+    fn apply(map: &T, value: &(T, T)) -> (T, T) {
+        (value.0 + *map * value.1, value.1)
+    }
+
+    fn compose(outer: &T, inner: &T) -> T {
+        *outer + *inner
+    }
+}
+
+// 区间赋值的映射是 Option：None 表示不改，后来的赋值覆盖先前的
+pub struct RangeAssignMin<T>(PhantomData<T>);
+
+impl<T> MapMonoid for RangeAssignMin<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type Monoid = Min<T>;
+    type Map = Option<T>;
+
+    fn identity_map() -> Option<T> {
+        None
+    }
+
+    fn apply(map: &Option<T>, value: &T) -> T {
+        match map {
+            Some(assigned) => *assigned,
+            None => *value,
+        }
+    }
+
+    fn compose(outer: &Option<T>, inner: &Option<T>) -> Option<T> {
+        outer.or(*inner)
+    }
+}
+
+pub struct RangeAssignMax<T>(PhantomData<T>);
+
+impl<T> MapMonoid for RangeAssignMax<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type Monoid = Max<T>;
+    type Map = Option<T>;
+
+    fn identity_map() -> Option<T> {
+        None
+    }
+
+    fn apply(map: &Option<T>, value: &T) -> T {
+        match map {
+            Some(assigned) => *assigned,
+            None => *value,
+        }
+    }
+
+    fn compose(outer: &Option<T>, inner: &Option<T>) -> Option<T> {
+        outer.or(*inner)
+    }
+}
+
+pub struct RangeAssignSum<T>(PhantomData<T>);
+
+impl<T> MapMonoid for RangeAssignSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Monoid = SumWithLength<T>;
+    type Map = Option<T>;
+
+    fn identity_map() -> Option<T> {
+        None
+    }
+
+    fn apply(map: &Option<T>, value: &(T, T)) -> (T, T) {
+        match map {
+            Some(assigned) => (*assigned * value.1, value.1),
+            None => *value,
+        }
+    }
+
+    fn compose(outer: &Option<T>, inner: &Option<T>) -> Option<T> {
+        outer.or(*inner)
+    }
+}
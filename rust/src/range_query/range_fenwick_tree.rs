@@ -0,0 +1,150 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Mul, Range, Sub},
+};
+
+use super::fenwick_tree::FenwickTree;
+
+// 区间加、区间求和的树状数组。对差分数组 d 有
+//   sum(a[0..p]) = p * sum(d[0..p]) - sum(i * d[i], i < p)，
+// 用两棵树状数组分别维护 d[i] 和 i * d[i]，两种操作都是 O(log n)。
+// 差分会出现负数，元素类型应当是有符号数
+pub struct RangeFenwickTree<T> {
+    deltas: FenwickTree<T>,
+    weighted: FenwickTree<T>,
+}
+
+impl<T> RangeFenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+{
+    // 下标要参与乘法，长度必须能用元素类型表示
+    fn check_length(len: usize) -> Result<(), String> {
+        if T::try_from(len).is_err() {
+            return Err(format!(
+                "Length {} cannot be represented by the element type.",
+                len
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn new(len: usize) -> Result<Self, String> {
+        Self::check_length(len)?;
+        Ok(RangeFenwickTree {
+            deltas: FenwickTree::new(len),
+            weighted: FenwickTree::new(len),
+        })
+    }
+
+    pub fn from_slice(values: &[T]) -> Result<Self, String> {
+        Self::check_length(values.len())?;
+        let mut previous = T::default();
+        let mut deltas = Vec::with_capacity(values.len());
+        let mut weighted = Vec::with_capacity(values.len());
+        for (index, &value) in values.iter().enumerate() {
+            let delta = value - previous;
+            deltas.push(delta);
+            weighted.push(delta * weight(index));
+            previous = value;
+        }
+        Ok(RangeFenwickTree {
+            deltas: FenwickTree::from_slice(&deltas),
+            weighted: FenwickTree::from_slice(&weighted),
+        })
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.deltas.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        Ok(())
+    }
+
+    // 差分数组只改两端：start 处加 delta，end 处减 delta
+    pub fn range_add(&mut self, range: Range<usize>, delta: T) -> Result<(), String> {
+        self.check_range(&range)?;
+        if range.is_empty() {
+            return Ok(());
+        }
+        self.deltas.add(range.start, delta)?;
+        self.weighted
+            .add(range.start, delta * weight(range.start))?;
+        if range.end < self.get_size() {
+            let negated = T::default() - delta;
+            self.deltas.add(range.end, negated)?;
+            self.weighted.add(range.end, negated * weight(range.end))?;
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, index: usize, delta: T) -> Result<(), String> {
+        if index >= self.get_size() {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                index,
+                self.get_size()
+            ));
+        }
+        self.range_add(index..index + 1, delta)
+    }
+
+    pub fn prefix_sum(&self, end: usize) -> Result<T, String> {
+        self.check_range(&(0..end))?;
+        Ok(self.deltas.prefix_sum(end)? * weight(end) - self.weighted.prefix_sum(end)?)
+    }
+
+    pub fn range_sum(&self, range: Range<usize>) -> Result<T, String> {
+        self.check_range(&range)?;
+        Ok(self.prefix_sum(range.end)? - self.prefix_sum(range.start)?)
+    }
+
+    // 单点的值就是差分数组的前缀和
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.get_size() {
+            return None;
+        }
+        self.deltas.prefix_sum(index + 1).ok()
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        (0..self.get_size())
+            .filter_map(|index| self.get(index))
+            .collect()
+    }
+}
+
+// 构造时已经检查过长度，不超过长度的下标都能转换
+fn weight<T: TryFrom<usize>>(index: usize) -> T {
+    T::try_from(index).ok().unwrap()
+}
+
+impl<T> Debug for RangeFenwickTree<T>
+where
+    T: Copy
+        + Default
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + TryFrom<usize>
+        + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
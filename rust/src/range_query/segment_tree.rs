@@ -0,0 +1,349 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, ops::Range};
+
+use super::monoid::Monoid;
+use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+
+// 自底向上的非递归线段树：叶子补齐到 2 的幂，节点 k 的孩子是 2k 和 2k + 1，
+// 根在下标 1；单点修改和区间查询都是 O(log n)
+pub struct SegmentTree<M: Monoid> {
+    len: usize,
+    // 叶子层宽度，不小于 len 的最小 2 的幂
+    size: usize,
+    log: u32,
+    data: Vec<M::Value>,
+}
+
+impl<M: Monoid> SegmentTree<M> {
+    // len 个 value
+    pub fn new(len: usize, value: M::Value) -> Self {
+        Self::from_slice(&vec![value; len])
+    }
+
+    pub fn from_slice(values: &[M::Value]) -> Self {
+        let len = values.len();
+        let size = len.next_power_of_two();
+        let mut data = vec![M::identity(); 2 * size];
+        data[size..size + len].clone_from_slice(values);
+        let mut tree = SegmentTree {
+            len,
+            size,
+            log: size.trailing_zeros(),
+            data,
+        };
+        for k in (1..size).rev() {
+            tree.update(k);
+        }
+        tree
+    }
+
+    fn update(&mut self, k: usize) {
+        self.data[k] = M::combine(&self.data[2 * k], &self.data[2 * k + 1]);
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        if index >= self.len {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                index, self.len
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.len {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start, range.end, self.len
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Option<&M::Value> {
+        self.check_index(index).ok()?;
+        Some(&self.data[self.size + index])
+    }
+
+    // 改叶子后沿父指针一路重算到根
+    pub fn set(&mut self, index: usize, value: M::Value) -> Result<(), String> {
+        self.check_index(index)?;
+        let leaf = self.size + index;
+        self.data[leaf] = value;
+        for i in 1..=self.log {
+            self.update(leaf >> i);
+        }
+        Ok(())
+    }
+
+    // 两个指针从叶子层往上收缩，左边界是右孩子、右边界是左孩子时把节点并进结果；
+    // 运算不要求交换律，所以左右两侧分开累积
+    pub fn query(&self, range: Range<usize>) -> Result<M::Value, String> {
+        self.check_range(&range)?;
+        let mut left_sum = M::identity();
+        let mut right_sum = M::identity();
+        let mut left = range.start + self.size;
+        let mut right = range.end + self.size;
+        while left < right {
+            if left & 1 == 1 {
+                left_sum = M::combine(&left_sum, &self.data[left]);
+                left += 1;
+            }
+            if right & 1 == 1 {
+                right -= 1;
+                right_sum = M::combine(&self.data[right], &right_sum);
+            }
+            left >>= 1;
+            right >>= 1;
+        }
+        Ok(M::combine(&left_sum, &right_sum))
+    }
+
+    pub fn query_all(&self) -> M::Value {
+        self.data[1].clone()
+    }
+
+    // 在 predicate(query(left..r)) 为真的前提下求最大的 r，要求 predicate 单调且对单位元为真；
+    // 先向上找到第一个使 predicate 变假的节点，再向下逐层定位到叶子
+    pub fn max_right<P>(&self, left: usize, predicate: P) -> Result<usize, String>
+    where
+        P: Fn(&M::Value) -> bool,
+    {
+        if left > self.len {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                left, self.len
+            ));
+        }
+        if !predicate(&M::identity()) {
+            return Err("Predicate must hold for the identity element.".to_owned());
+        }
+        if left == self.len {
+            return Ok(self.len);
+        }
+        let mut left = left + self.size;
+        let mut sum = M::identity();
+        loop {
+            while left.is_multiple_of(2) {
+                left >>= 1;
+            }
+            let combined = M::combine(&sum, &self.data[left]);
+            if !predicate(&combined) {
+                while left < self.size {
+                    left *= 2;
+                    let combined = M::combine(&sum, &self.data[left]);
+                    if predicate(&combined) {
+                        sum = combined;
+                        left += 1;
+                    }
+                }
+                return Ok(left - self.size);
+            }
+            sum = combined;
+            left += 1;
+            if left.is_power_of_two() {
+                return Ok(self.len);
+            }
+        }
+    }
+
+    // 与 max_right 对称：在 predicate(query(l..right)) 为真的前提下求最小的 l
+    pub fn min_left<P>(&self, right: usize, predicate: P) -> Result<usize, String>
+    where
+        P: Fn(&M::Value) -> bool,
+    {
+        if right > self.len {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                right, self.len
+            ));
+        }
+        if !predicate(&M::identity()) {
+            return Err("Predicate must hold for the identity element.".to_owned());
+        }
+        if right == 0 {
+            return Ok(0);
+        }
+        let mut right = right + self.size;
+        let mut sum = M::identity();
+        loop {
+            right -= 1;
+            while right > 1 && right % 2 == 1 {
+                right >>= 1;
+            }
+            let combined = M::combine(&self.data[right], &sum);
+            if !predicate(&combined) {
+                while right < self.size {
+                    right = 2 * right + 1;
+                    let combined = M::combine(&self.data[right], &sum);
+                    if predicate(&combined) {
+                        sum = combined;
+                        right -= 1;
+                    }
+                }
+                return Ok(right + 1 - self.size);
+            }
+            sum = combined;
+            if right.is_power_of_two() {
+                return Ok(0);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, M::Value> {
+        self.data[self.size..self.size + self.len].iter()
+    }
+}
+
+impl<M: Monoid> From<&FixedArray<M::Value>> for SegmentTree<M> {
+    fn from(array: &FixedArray<M::Value>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<M: Monoid> From<&DynamicArray<M::Value>> for SegmentTree<M> {
+    fn from(array: &DynamicArray<M::Value>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<M: Monoid> Debug for SegmentTree<M>
+where
+    M::Value: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SegmentTree;
+    use crate::array::dynamic_array::DynamicArray;
+    use crate::range_query::monoid::{Max, Monoid, Sum};
+    use crate::test_util::rng::TestRng;
+
+    // 不满足交换律的拼接，用来检查左右两侧的合并顺序
+    struct Concat;
+
+    impl Monoid for Concat {
+        type Value = Vec<usize>;
+
+        fn identity() -> Vec<usize> {
+            Vec::new()
+        }
+
+        fn combine(left: &Vec<usize>, right: &Vec<usize>) -> Vec<usize> {
+            [left.as_slice(), right.as_slice()].concat()
+        }
+    }
+
+    fn naive_max_right(values: &[u64], left: usize, limit: u64) -> usize {
+        (left..=values.len())
+            .rev()
+            .find(|&r| values[left..r].iter().sum::<u64>() <= limit)
+            .unwrap()
+    }
+
+    fn naive_min_left(values: &[u64], right: usize, limit: u64) -> usize {
+        (0..=right)
+            .find(|&l| values[l..right].iter().sum::<u64>() <= limit)
+            .unwrap()
+    }
+
+    #[test]
+    fn random_binary_searches_match_brute_force() {
+        let mut random = TestRng::with_seed(471);
+        for len in (0..20).chain([31, 32, 33, 64]) {
+            let mut values: Vec<u64> = (0..len).map(|_| random.below(10)).collect();
+            let mut tree = SegmentTree::<Sum<u64>>::from_slice(&values);
+            for _ in 0..200 {
+                if len > 0 && random.next_u64().is_multiple_of(3) {
+                    let index = random.index(len);
+                    values[index] = random.below(10);
+                    tree.set(index, values[index]).unwrap();
+                }
+                let a = random.index(len + 1);
+                let b = random.index(len + 1);
+                let range = a.min(b)..a.max(b);
+                assert_eq!(tree.query(range.clone()), Ok(values[range].iter().sum()));
+
+                let limit = random.below(40);
+                let within = |sum: &u64| *sum <= limit;
+                assert_eq!(
+                    tree.max_right(a, within),
+                    Ok(naive_max_right(&values, a, limit))
+                );
+                assert_eq!(
+                    tree.min_left(b, within),
+                    Ok(naive_min_left(&values, b, limit))
+                );
+            }
+            assert_eq!(tree.query_all(), values.iter().sum());
+            assert!(tree.iter().eq(values.iter()));
+        }
+    }
+
+    #[test]
+    fn non_commutative_queries_keep_order() {
+        let mut random = TestRng::with_seed(472);
+        for len in [1, 5, 8, 13] {
+            let leaves: Vec<Vec<usize>> = (0..len).map(|i| vec![i]).collect();
+            let tree = SegmentTree::<Concat>::from_slice(&leaves);
+            for _ in 0..100 {
+                let a = random.index(len + 1);
+                let b = random.index(len + 1);
+                let range = a.min(b)..a.max(b);
+                assert_eq!(tree.query(range.clone()), Ok(range.collect::<Vec<_>>()));
+                let k = random.index(len + 1);
+                let short = |v: &Vec<usize>| v.len() <= k;
+                assert_eq!(tree.max_right(a, short), Ok((a + k).min(len)));
+                assert_eq!(tree.min_left(b, short), Ok(b.saturating_sub(k)));
+            }
+        }
+    }
+
+    #[test]
+    fn binary_searches_reject_invalid_input() {
+        let tree = SegmentTree::<Max<i32>>::from_slice(&[3, 1, 4, 1, 5]);
+        // Max 的单位元是 i32::MIN，“最大值大于 0”对单位元不成立
+        let positive = |max: &i32| *max > 0;
+        assert!(tree.max_right(0, positive).is_err());
+        assert!(tree.min_left(5, positive).is_err());
+        let below = |max: &i32| *max < 5;
+        assert!(tree.max_right(6, below).is_err());
+        assert!(tree.min_left(6, below).is_err());
+        assert_eq!(tree.max_right(5, below), Ok(5));
+        assert_eq!(tree.min_left(0, below), Ok(0));
+        assert_eq!(tree.max_right(0, below), Ok(4));
+        assert_eq!(tree.min_left(5, below), Ok(5));
+        assert_eq!(tree.min_left(4, below), Ok(0));
+
+        let mut array = DynamicArray::new(4).unwrap();
+        for value in [2, 7, 1] {
+            array.append(value).unwrap();
+        }
+        let mut tree = SegmentTree::<Max<i32>>::from(&array);
+        assert_eq!(tree.query(0..3), Ok(7));
+        assert!(tree.query(2..4).is_err());
+        assert!(tree.set(3, 0).is_err());
+        tree.set(1, 0).unwrap();
+        assert_eq!(tree.query_all(), 2);
+        assert_eq!(tree.get(3), None);
+
+        let empty = SegmentTree::<Sum<u64>>::new(0, 0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.max_right(0, |_| true), Ok(0));
+        assert_eq!(empty.min_left(0, |_| true), Ok(0));
+    }
+}
@@ -0,0 +1,141 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, ops::Range};
+
+use super::monoid::IdempotentMonoid;
+use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+
+// 稀疏表：table[k][i] 是从 i 开始长度为 2^k 的区间的聚合值，O(n log n) 建表后只读。
+// 查询时用两段长度为 2^k 的区间盖住整个区间，重叠部分靠幂等性抵消，查询 O(1)
+pub struct SparseTable<M: IdempotentMonoid> {
+    table: Vec<Vec<M::Value>>,
+}
+
+impl<M: IdempotentMonoid> SparseTable<M> {
+    pub fn from_slice(values: &[M::Value]) -> Self {
+        let mut table = vec![values.to_vec()];
+        let mut width = 1;
+        while 2 * width <= values.len() {
+            let previous = table.last().unwrap();
+            let level = (0..=values.len() - 2 * width)
+                .map(|i| M::combine(&previous[i], &previous[i + width]))
+                .collect();
+            table.push(level);
+            width *= 2;
+        }
+        SparseTable { table }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_size() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&M::Value> {
+        self.table[0].get(index)
+    }
+
+    // 空区间返回单位元
+    pub fn query(&self, range: Range<usize>) -> Result<M::Value, String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        if range.is_empty() {
+            return Ok(M::identity());
+        }
+        let level = range.len().ilog2() as usize;
+        let row = &self.table[level];
+        Ok(M::combine(
+            &row[range.start],
+            &row[range.end - (1 << level)],
+        ))
+    }
+}
+
+impl<M: IdempotentMonoid> From<&FixedArray<M::Value>> for SparseTable<M> {
+    fn from(array: &FixedArray<M::Value>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<M: IdempotentMonoid> From<&DynamicArray<M::Value>> for SparseTable<M> {
+    fn from(array: &DynamicArray<M::Value>) -> Self {
+        Self::from_slice(array.get_elements())
+    }
+}
+
+impl<M: IdempotentMonoid> Debug for SparseTable<M>
+where
+    M::Value: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.table[0]).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseTable;
+    use crate::array::{dynamic_array::DynamicArray, fixed_array::FixedArray};
+    use crate::range_query::monoid::{Gcd, IdempotentMonoid, Max, Min};
+    use crate::test_util::rng::TestRng;
+
+    // 0、1 以及 2^k 前后的长度，覆盖层数刚好增加的边界
+    const LENGTHS: [usize; 15] = [0, 1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 31, 32, 33];
+
+    fn check_all_ranges<M>(table: &SparseTable<M>, values: &[M::Value])
+    where
+        M: IdempotentMonoid,
+        M::Value: PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(table.get_size(), values.len());
+        for start in 0..=values.len() {
+            for end in start..=values.len() {
+                let expected = values[start..end]
+                    .iter()
+                    .fold(M::identity(), |acc, x| M::combine(&acc, x));
+                assert_eq!(table.query(start..end), Ok(expected));
+            }
+        }
+        let (start, end) = (1, 0);
+        assert!(table.query(start..end).is_err());
+        assert!(table.query(0..values.len() + 1).is_err());
+        assert_eq!(table.get(values.len()), None);
+    }
+
+    #[test]
+    fn random_queries_match_brute_force() {
+        let mut random = TestRng::with_seed(49);
+        for len in LENGTHS {
+            let values: Vec<i64> = (0..len).map(|_| random.below(100) as i64 - 50).collect();
+            check_all_ranges(&SparseTable::<Min<i64>>::from_slice(&values), &values);
+            check_all_ranges(&SparseTable::<Max<i64>>::from_slice(&values), &values);
+            let multiples: Vec<u64> = (0..len).map(|_| 6 * random.below(20)).collect();
+            check_all_ranges(&SparseTable::<Gcd<u64>>::from_slice(&multiples), &multiples);
+        }
+    }
+
+    #[test]
+    fn builds_from_fixed_and_dynamic_arrays() {
+        let mut random = TestRng::with_seed(491);
+        for len in LENGTHS {
+            let values: Vec<u32> = (0..len).map(|_| random.below(1000) as u32).collect();
+            let mut fixed = FixedArray::new(len.max(1)).unwrap();
+            let mut dynamic = DynamicArray::new(1).unwrap();
+            for &value in values.iter() {
+                fixed.append(value).unwrap();
+                dynamic.append(value).unwrap();
+            }
+            check_all_ranges(&SparseTable::<Min<u32>>::from(&fixed), &values);
+            check_all_ranges(&SparseTable::<Max<u32>>::from(&dynamic), &values);
+        }
+    }
+}
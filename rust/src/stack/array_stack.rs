@@ -0,0 +1,136 @@
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use crate::array::dynamic_array::DynamicArray;
+
+const DEFAULT_CAPACITY: usize = 8;
+// DynamicArray 只会在容量为 0 或下标越界时报错：容量都是正数，压栈总是追加在末尾，出栈只删最后一个元素
+const CAPACITY_IS_POSITIVE: &str = "Default capacity is positive.";
+const TOP_IN_BOUNDS: &str = "The top of a non-empty stack is in bounds.";
+
+// 用 DynamicArray 的尾部当栈顶，push/pop 都是均摊 O(1)
+pub struct ArrayStack<T> {
+    items: DynamicArray<T>,
+}
+
+impl<T> ArrayStack<T> {
+    pub fn new() -> Self {
+        ArrayStack {
+            items: DynamicArray::new(DEFAULT_CAPACITY).expect(CAPACITY_IS_POSITIVE),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Result<ArrayStack<T>, String> {
+        Ok(ArrayStack {
+            items: DynamicArray::new(capacity)?,
+        })
+    }
+
+    pub fn push(&mut self, element: T) {
+        self.items.append(element).expect(TOP_IN_BOUNDS);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.get_size().checked_sub(1)?;
+        Some(self.items.remove(top).expect(TOP_IN_BOUNDS))
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.items.get_last()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        let top = self.get_size().checked_sub(1)?;
+        self.items.get_mut(top).expect(TOP_IN_BOUNDS)
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.items.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_size() == 0
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.items.get_capacity()
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    // 从栈顶到栈底
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.get_elements().iter().rev()
+    }
+}
+
+impl<T> Default for ArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = ArrayStack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for ArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T> Debug for ArrayStack<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_vec() {
+        let mut random = TestRng::with_seed(391);
+        let mut stack = ArrayStack::new();
+        let mut expected = Vec::new();
+        for step in 0..2000 {
+            match random.below(4) {
+                0 => assert_eq!(stack.pop(), expected.pop()),
+                1 => {
+                    if let Some(top) = stack.peek_mut() {
+                        *top += 1;
+                    }
+                    if let Some(top) = expected.last_mut() {
+                        *top += 1;
+                    }
+                }
+                _ => {
+                    stack.push(step);
+                    expected.push(step);
+                }
+            }
+            assert_eq!(stack.get_size(), expected.len());
+            assert_eq!(stack.peek(), expected.last());
+            assert!(stack.get_capacity() >= stack.get_size());
+        }
+        assert!(stack.iter().eq(expected.iter().rev()));
+        stack.clear();
+        assert!(stack.is_empty());
+        assert!(ArrayStack::<i32>::with_capacity(0).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+
+use super::matcher::{Match, Matcher};
+
+// Boyer–Moore–Horspool：先比较窗口的最后一个字节，不论是否匹配都按这个字节查表跳跃
+#[derive(Clone)]
+pub struct Horspool {
+    pattern: Vec<u8>,
+    // shift[b] 是窗口最后一个字节为 b 时可以安全右移的距离
+    shift: [usize; 256],
+}
+
+impl Horspool {
+    pub fn new(pattern: &[u8]) -> Self {
+        let m = pattern.len();
+        let mut shift = [m.max(1); 256];
+        for (i, &byte) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            shift[byte as usize] = m - 1 - i;
+        }
+        Horspool {
+            pattern: pattern.to_vec(),
+            shift,
+        }
+    }
+
+    pub fn get_pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    // 从 position 开始找下一个匹配，同时返回下一次搜索的起点
+    fn next_match(&self, haystack: &[u8], mut position: usize) -> Option<(Match, usize)> {
+        let m = self.pattern.len();
+        if m == 0 {
+            if position > haystack.len() {
+                return None;
+            }
+            let found = Match {
+                pattern: 0,
+                start: position,
+                end: position,
+            };
+            return Some((found, position + 1));
+        }
+        while position + m <= haystack.len() {
+            let last = haystack[position + m - 1];
+            if last == self.pattern[m - 1]
+                && haystack[position..position + m - 1] == self.pattern[..m - 1]
+            {
+                let found = Match {
+                    pattern: 0,
+                    start: position,
+                    end: position + m,
+                };
+                return Some((found, position + self.shift[last as usize]));
+            }
+            position += self.shift[last as usize];
+        }
+        None
+    }
+}
+
+pub fn horspool_search<'a>(pattern: &'a [u8], haystack: &'a [u8]) -> HorspoolIter<'a> {
+    HorspoolIter {
+        matcher: Cow::Owned(Horspool::new(pattern)),
+        haystack,
+        position: 0,
+    }
+}
+
+impl Matcher for Horspool {
+    type Iter<'a> = HorspoolIter<'a>;
+
+    fn get_name(&self) -> &'static str {
+        "Boyer-Moore-Horspool"
+    }
+
+    fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> HorspoolIter<'a> {
+        HorspoolIter {
+            matcher: Cow::Borrowed(self),
+            haystack,
+            position: 0,
+        }
+    }
+}
+
+pub struct HorspoolIter<'a> {
+    matcher: Cow<'a, Horspool>,
+    haystack: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Iterator for HorspoolIter<'a> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let (found, next) = self.matcher.next_match(self.haystack, self.position)?;
+        self.position = next;
+        Some(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    fn naive_matches(pattern: &[u8], haystack: &[u8]) -> Vec<Match> {
+        (pattern.len()..=haystack.len())
+            .filter(|&end| haystack[..end].ends_with(pattern))
+            .map(|end| Match {
+                pattern: 0,
+                start: end - pattern.len(),
+                end,
+            })
+            .collect()
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_searches_match_brute_force() {
+        let mut random = TestRng::with_seed(363);
+        for _ in 0..500 {
+            let alphabet = 1 + random.below(4);
+            let pattern_len = random.index(6);
+            let pattern = random_text(&mut random, pattern_len, alphabet);
+            let haystack_len = random.index(40);
+            let haystack = random_text(&mut random, haystack_len, alphabet);
+
+            let matcher = Horspool::new(&pattern);
+            let expected = naive_matches(&pattern, &haystack);
+            assert_eq!(matcher.find_iter(&haystack).collect::<Vec<_>>(), expected);
+            assert_eq!(
+                horspool_search(&pattern, &haystack).collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(matcher.find(&haystack), expected.first().copied());
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let matcher = Horspool::new(b"");
+        let starts: Vec<usize> = matcher.find_iter(b"abc").map(|m| m.start).collect();
+        assert_eq!(starts, vec![0, 1, 2, 3]);
+        assert_eq!(matcher.count(b""), 1);
+    }
+}
@@ -0,0 +1,181 @@
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+
+use super::matcher::{Match, Matcher};
+
+// prefix[i] 是 s[..=i] 最长的相等真前缀和真后缀的长度
+pub fn prefix_function(s: &[u8]) -> Vec<usize> {
+    let mut prefix = vec![0; s.len()];
+    for i in 1..s.len() {
+        let mut k = prefix[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = prefix[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        prefix[i] = k;
+    }
+    prefix
+}
+
+pub fn kmp_search<'a>(pattern: &'a [u8], haystack: &'a [u8]) -> KmpIter<'a> {
+    KmpIter {
+        pattern,
+        prefix: Cow::Owned(prefix_function(pattern)),
+        haystack,
+        position: 0,
+        matched: 0,
+    }
+}
+
+// 预先算好前缀函数，可以在多段文本上重复搜索
+pub struct Kmp {
+    pattern: Vec<u8>,
+    prefix: Vec<usize>,
+}
+
+impl Kmp {
+    pub fn new(pattern: &[u8]) -> Self {
+        Kmp {
+            pattern: pattern.to_vec(),
+            prefix: prefix_function(pattern),
+        }
+    }
+
+    pub fn get_pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    pub fn get_prefix_function(&self) -> &[usize] {
+        &self.prefix
+    }
+}
+
+impl Matcher for Kmp {
+    type Iter<'a> = KmpIter<'a>;
+
+    fn get_name(&self) -> &'static str {
+        "KMP"
+    }
+
+    fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> KmpIter<'a> {
+        KmpIter {
+            pattern: &self.pattern,
+            prefix: Cow::Borrowed(&self.prefix),
+            haystack,
+            position: 0,
+            matched: 0,
+        }
+    }
+}
+
+pub struct KmpIter<'a> {
+    pattern: &'a [u8],
+    prefix: Cow<'a, [usize]>,
+    haystack: &'a [u8],
+    position: usize,
+    // 当前已经匹配上的模式串长度
+    matched: usize,
+}
+
+impl<'a> Iterator for KmpIter<'a> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let m = self.pattern.len();
+        // 空模式串在每个位置都匹配
+        if m == 0 {
+            if self.position > self.haystack.len() {
+                return None;
+            }
+            self.position += 1;
+            return Some(Match {
+                pattern: 0,
+                start: self.position - 1,
+                end: self.position - 1,
+            });
+        }
+        while self.position < self.haystack.len() {
+            let byte = self.haystack[self.position];
+            self.position += 1;
+            while self.matched > 0 && byte != self.pattern[self.matched] {
+                self.matched = self.prefix[self.matched - 1];
+            }
+            if byte == self.pattern[self.matched] {
+                self.matched += 1;
+            }
+            if self.matched == m {
+                // 失配链接回退，继续寻找重叠的匹配
+                self.matched = self.prefix[m - 1];
+                return Some(Match {
+                    pattern: 0,
+                    start: self.position - m,
+                    end: self.position,
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    fn naive_prefix_function(s: &[u8]) -> Vec<usize> {
+        (0..s.len())
+            .map(|i| (0..=i).rev().find(|&k| s[..k] == s[i + 1 - k..=i]).unwrap())
+            .collect()
+    }
+
+    fn naive_matches(pattern: &[u8], haystack: &[u8]) -> Vec<Match> {
+        (pattern.len()..=haystack.len())
+            .filter(|&end| haystack[..end].ends_with(pattern))
+            .map(|end| Match {
+                pattern: 0,
+                start: end - pattern.len(),
+                end,
+            })
+            .collect()
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_searches_match_brute_force() {
+        let mut random = TestRng::with_seed(361);
+        for _ in 0..500 {
+            let alphabet = 1 + random.below(3);
+            let pattern_len = random.index(6);
+            let pattern = random_text(&mut random, pattern_len, alphabet);
+            let haystack_len = random.index(40);
+            let haystack = random_text(&mut random, haystack_len, alphabet);
+
+            let kmp = Kmp::new(&pattern);
+            assert_eq!(kmp.get_prefix_function(), naive_prefix_function(&pattern));
+            let expected = naive_matches(&pattern, &haystack);
+            assert_eq!(kmp.find_iter(&haystack).collect::<Vec<_>>(), expected);
+            assert_eq!(
+                kmp_search(&pattern, &haystack).collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(kmp.find(&haystack), expected.first().copied());
+            assert_eq!(kmp.count(&haystack), expected.len());
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let kmp = Kmp::new(b"");
+        let starts: Vec<usize> = kmp.find_iter(b"abc").map(|m| m.start).collect();
+        assert_eq!(starts, vec![0, 1, 2, 3]);
+        assert_eq!(kmp.count(b""), 1);
+    }
+}
@@ -0,0 +1,41 @@
+#![allow(dead_code)]
+
+use crate::trie::aho_corasick::AhoCorasick;
+pub use crate::trie::aho_corasick::Match;
+
+// 各种字符串匹配算法的公共接口，find_iter 按结尾位置从小到大给出所有（可能重叠的）匹配，
+// 结尾相同时按模式串编号从小到大。空模式串在 0..=n 的每个位置都匹配一次，共 n + 1 次，
+// 和 str::matches("") 一致；后缀数组、后缀自动机、后缀树的 count 也遵守这个约定
+pub trait Matcher {
+    type Iter<'a>: Iterator<Item = Match>
+    where
+        Self: 'a;
+
+    fn get_name(&self) -> &'static str;
+
+    fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> Self::Iter<'a>;
+
+    fn find<'a>(&'a self, haystack: &'a [u8]) -> Option<Match> {
+        self.find_iter(haystack).next()
+    }
+
+    fn is_match<'a>(&'a self, haystack: &'a [u8]) -> bool {
+        self.find(haystack).is_some()
+    }
+
+    fn count<'a>(&'a self, haystack: &'a [u8]) -> usize {
+        self.find_iter(haystack).count()
+    }
+}
+
+impl Matcher for AhoCorasick {
+    type Iter<'a> = std::vec::IntoIter<Match>;
+
+    fn get_name(&self) -> &'static str {
+        "Aho-Corasick"
+    }
+
+    fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> Self::Iter<'a> {
+        self.find_all(haystack).into_iter()
+    }
+}
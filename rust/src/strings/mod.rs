@@ -0,0 +1,8 @@
+pub mod horspool;
+pub mod kmp;
+pub mod matcher;
+pub mod rabin_karp;
+pub mod suffix_array;
+pub mod suffix_automaton;
+pub mod suffix_tree;
+pub mod z_function;
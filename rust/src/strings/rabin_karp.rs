@@ -0,0 +1,297 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use super::matcher::{Match, Matcher};
+use crate::hash_table::flat_hash_map::FlatHashMap;
+
+// 2^61 - 1 是梅森素数，取模后两个余数的乘积用 u128 也不会溢出
+pub const DEFAULT_MODULUS: u64 = (1 << 61) - 1;
+pub const DEFAULT_BASE: u64 = 257;
+
+// 长度相同的模式串共用一个滚动哈希窗口
+struct LengthGroup {
+    len: usize,
+    // 窗口哈希值 -> 哈希值相同的模式串编号
+    patterns: FlatHashMap<u64, Vec<usize>>,
+    // base^(len - 1) mod modulus，用于移出窗口最左边的字节
+    high_power: u64,
+}
+
+// 多模式串 Rabin–Karp，哈希命中后逐字节核对，模数取得很小也不会报告错误的匹配
+pub struct RabinKarp {
+    patterns: Vec<Vec<u8>>,
+    groups: Vec<LengthGroup>,
+    // 空模式串没有窗口可滚动，单独记录编号，在每个位置直接报告
+    empty: Vec<usize>,
+    modulus: u64,
+    base: u64,
+}
+
+impl RabinKarp {
+    pub fn new<I, P>(patterns: I) -> Result<RabinKarp, String>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        Self::with_modulus(patterns, DEFAULT_MODULUS, DEFAULT_BASE)
+    }
+
+    pub fn with_modulus<I, P>(patterns: I, modulus: u64, base: u64) -> Result<RabinKarp, String>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        if modulus < 2 {
+            return Err("Modulus must be at least 2.".to_owned());
+        }
+        if base.is_multiple_of(modulus) {
+            return Err("Base must not be a multiple of the modulus.".to_owned());
+        }
+        let mut matcher = RabinKarp {
+            patterns: Vec::new(),
+            groups: Vec::new(),
+            empty: Vec::new(),
+            modulus,
+            base: base % modulus,
+        };
+        for pattern in patterns {
+            matcher.add_pattern(pattern.as_ref());
+        }
+        Ok(matcher)
+    }
+
+    fn add_pattern(&mut self, pattern: &[u8]) {
+        let id = self.patterns.len();
+        self.patterns.push(pattern.to_vec());
+        if pattern.is_empty() {
+            self.empty.push(id);
+            return;
+        }
+        let hash = self.hash(pattern);
+        let index = match self.groups.binary_search_by_key(&pattern.len(), |g| g.len) {
+            Ok(index) => index,
+            Err(index) => {
+                let group = LengthGroup {
+                    len: pattern.len(),
+                    patterns: FlatHashMap::new(),
+                    high_power: self.pow(pattern.len() - 1),
+                };
+                self.groups.insert(index, group);
+                index
+            }
+        };
+        let patterns = &mut self.groups[index].patterns;
+        match patterns.get_mut(&hash) {
+            Some(ids) => ids.push(id),
+            None => {
+                patterns.insert(hash, vec![id]);
+            }
+        }
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 * b as u128) % self.modulus as u128) as u64
+    }
+
+    fn pow(&self, mut exponent: usize) -> u64 {
+        let mut result = 1 % self.modulus;
+        let mut base = self.base;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    // 多项式哈希 s[0] * base^(n-1) + ... + s[n-1]
+    pub fn hash(&self, s: &[u8]) -> u64 {
+        s.iter().fold(0, |hash, &byte| self.push_byte(hash, byte))
+    }
+
+    fn push_byte(&self, hash: u64, byte: u8) -> u64 {
+        (self.mul(hash, self.base) + byte as u64 % self.modulus) % self.modulus
+    }
+
+    fn empty_matches(&self, position: usize) -> impl Iterator<Item = Match> + '_ {
+        self.empty.iter().map(move |&id| Match {
+            pattern: id,
+            start: position,
+            end: position,
+        })
+    }
+
+    pub fn get_pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn get_modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    pub fn get_base(&self) -> u64 {
+        self.base
+    }
+}
+
+impl Matcher for RabinKarp {
+    type Iter<'a> = RabinKarpIter<'a>;
+
+    fn get_name(&self) -> &'static str {
+        "Rabin-Karp"
+    }
+
+    fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> RabinKarpIter<'a> {
+        RabinKarpIter {
+            matcher: self,
+            haystack,
+            position: 0,
+            hashes: vec![0; self.groups.len()],
+            pending: self.empty_matches(0).collect(),
+        }
+    }
+}
+
+pub struct RabinKarpIter<'a> {
+    matcher: &'a RabinKarp,
+    haystack: &'a [u8],
+    // 下一个要移入窗口的字节
+    position: usize,
+    // 每个长度分组当前窗口的哈希值
+    hashes: Vec<u64>,
+    // 以同一位置结尾的匹配可能有多个，先缓存起来
+    pending: VecDeque<Match>,
+}
+
+impl<'a> RabinKarpIter<'a> {
+    fn step(&mut self) {
+        let matcher = self.matcher;
+        let byte = self.haystack[self.position];
+        self.position += 1;
+        let end = self.position;
+        let mut found: Vec<Match> = matcher.empty_matches(end).collect();
+        for (group, hash) in matcher.groups.iter().zip(self.hashes.iter_mut()) {
+            // 窗口已满时先移出最左边的字节
+            if end > group.len {
+                let outgoing = self.haystack[end - group.len - 1] as u64 % matcher.modulus;
+                let removed = matcher.mul(outgoing, group.high_power);
+                *hash = (*hash + matcher.modulus - removed) % matcher.modulus;
+            }
+            *hash = matcher.push_byte(*hash, byte);
+            if end < group.len {
+                continue;
+            }
+            let start = end - group.len;
+            if let Some(ids) = group.patterns.get(hash) {
+                for &id in ids.iter() {
+                    if matcher.patterns[id] == self.haystack[start..end] {
+                        found.push(Match {
+                            pattern: id,
+                            start,
+                            end,
+                        });
+                    }
+                }
+            }
+        }
+        found.sort_unstable_by_key(|m| m.pattern);
+        self.pending.extend(found);
+    }
+}
+
+impl<'a> Iterator for RabinKarpIter<'a> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            if let Some(found) = self.pending.pop_front() {
+                return Some(found);
+            }
+            if self.position >= self.haystack.len() {
+                return None;
+            }
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    // 按结尾位置、再按模式串编号排序
+    fn naive_matches(patterns: &[Vec<u8>], haystack: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for end in 0..=haystack.len() {
+            for (id, pattern) in patterns.iter().enumerate() {
+                if haystack[..end].ends_with(pattern) {
+                    matches.push(Match {
+                        pattern: id,
+                        start: end - pattern.len(),
+                        end,
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_searches_match_brute_force() {
+        let mut random = TestRng::with_seed(364);
+        for round in 0..300 {
+            let alphabet = 1 + random.below(3);
+            let pattern_count = 1 + random.index(5);
+            let patterns: Vec<Vec<u8>> = (0..pattern_count)
+                .map(|_| {
+                    let len = random.index(5);
+                    random_text(&mut random, len, alphabet)
+                })
+                .collect();
+            let haystack_len = random.index(40);
+            let haystack = random_text(&mut random, haystack_len, alphabet);
+
+            // 一半用很小的模数制造大量哈希冲突，结果必须仍然精确
+            let matcher = if round % 2 == 0 {
+                RabinKarp::new(&patterns).unwrap()
+            } else {
+                RabinKarp::with_modulus(&patterns, 7, 3).unwrap()
+            };
+            assert_eq!(matcher.get_pattern_count(), pattern_count);
+            let expected = naive_matches(&patterns, &haystack);
+            assert_eq!(matcher.find_iter(&haystack).collect::<Vec<_>>(), expected);
+            assert_eq!(matcher.count(&haystack), expected.len());
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let matcher = RabinKarp::new([&b"b"[..], b"", b""]).unwrap();
+        let found: Vec<(usize, usize)> = matcher
+            .find_iter(b"ab")
+            .map(|m| (m.pattern, m.start))
+            .collect();
+        let expected = vec![(1, 0), (2, 0), (1, 1), (2, 1), (0, 1), (1, 2), (2, 2)];
+        assert_eq!(found, expected);
+        assert_eq!(matcher.count(b""), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        assert!(RabinKarp::with_modulus([b"a"], 1, 3).is_err());
+        assert!(RabinKarp::with_modulus([b"a"], 7, 14).is_err());
+        let matcher = RabinKarp::with_modulus([b"a"], 7, 10).unwrap();
+        assert_eq!(matcher.get_base(), 3);
+        assert_eq!(matcher.hash(b"ab"), (97 * 3 + 98) % 7);
+    }
+}
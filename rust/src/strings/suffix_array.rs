@@ -0,0 +1,253 @@
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+
+pub struct SuffixArray {
+    text: Vec<u8>,
+    // suffixes[i] 是字典序第 i 小的后缀的起点
+    suffixes: Vec<usize>,
+    // rank[p] 是从 p 开始的后缀在 suffixes 中的位置
+    rank: Vec<usize>,
+    // lcp[i] 是 suffixes[i - 1] 和 suffixes[i] 的最长公共前缀，lcp[0] = 0
+    lcp: Vec<usize>,
+}
+
+impl SuffixArray {
+    pub fn new(text: &[u8]) -> Self {
+        let suffixes = Self::build_suffixes(text);
+        let mut rank = vec![0; text.len()];
+        for (i, &p) in suffixes.iter().enumerate() {
+            rank[p] = i;
+        }
+        let lcp = Self::build_lcp(text, &suffixes, &rank);
+        SuffixArray {
+            text: text.to_vec(),
+            suffixes,
+            rank,
+            lcp,
+        }
+    }
+
+    // 倍增法：每一轮按 (前 k 个字符的排名, 后 k 个字符的排名) 排序，得到前 2k 个字符的排名
+    fn build_suffixes(text: &[u8]) -> Vec<usize> {
+        let n = text.len();
+        let mut suffixes: Vec<usize> = (0..n).collect();
+        if n == 0 {
+            return suffixes;
+        }
+        let mut rank: Vec<usize> = text.iter().map(|&b| b as usize).collect();
+        let mut next_rank = vec![0; n];
+        let mut k = 1;
+        loop {
+            // 后半段超出文本时视为最小
+            let key = |i: usize| (rank[i], if i + k < n { rank[i + k] + 1 } else { 0 });
+            suffixes.sort_unstable_by_key(|&i| key(i));
+            next_rank[suffixes[0]] = 0;
+            for i in 1..n {
+                let bump = key(suffixes[i - 1]) != key(suffixes[i]);
+                next_rank[suffixes[i]] = next_rank[suffixes[i - 1]] + bump as usize;
+            }
+            std::mem::swap(&mut rank, &mut next_rank);
+            // 所有排名都不同时排序已经完成
+            if rank[suffixes[n - 1]] == n - 1 {
+                break;
+            }
+            k *= 2;
+        }
+        suffixes
+    }
+
+    // Kasai 算法：按文本顺序处理后缀，相邻两次的 lcp 最多减少 1，总复杂度 O(n)
+    fn build_lcp(text: &[u8], suffixes: &[usize], rank: &[usize]) -> Vec<usize> {
+        let n = text.len();
+        let mut lcp = vec![0; n];
+        let mut h: usize = 0;
+        for p in 0..n {
+            if rank[p] == 0 {
+                h = 0;
+                continue;
+            }
+            let q = suffixes[rank[p] - 1];
+            while p + h < n && q + h < n && text[p + h] == text[q + h] {
+                h += 1;
+            }
+            lcp[rank[p]] = h;
+            h = h.saturating_sub(1);
+        }
+        lcp
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn get_text(&self) -> &[u8] {
+        &self.text
+    }
+
+    pub fn get_suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    pub fn get_rank(&self) -> &[usize] {
+        &self.rank
+    }
+
+    pub fn get_lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    // 只比较后缀的前 pattern.len() 个字节
+    fn compare_prefix(&self, suffix: usize, pattern: &[u8]) -> Ordering {
+        let end = (suffix + pattern.len()).min(self.text.len());
+        self.text[suffix..end].cmp(pattern)
+    }
+
+    // 所有以 pattern 开头的后缀在 suffixes 中是连续的一段，用两次二分找出这一段
+    fn pattern_range(&self, pattern: &[u8]) -> (usize, usize) {
+        let lower = self
+            .suffixes
+            .partition_point(|&s| self.compare_prefix(s, pattern) == Ordering::Less);
+        let upper = self
+            .suffixes
+            .partition_point(|&s| self.compare_prefix(s, pattern) != Ordering::Greater);
+        (lower, upper)
+    }
+
+    // 空模式串在 0..=n 的每个位置都出现一次，和 Matcher 的约定一致
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        if pattern.is_empty() {
+            return self.text.len() + 1;
+        }
+        let (lower, upper) = self.pattern_range(pattern);
+        upper - lower
+    }
+
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.count(pattern) > 0
+    }
+
+    // 按出现位置升序返回
+    pub fn find_all(&self, pattern: &[u8]) -> Vec<usize> {
+        if pattern.is_empty() {
+            return (0..=self.text.len()).collect();
+        }
+        let (lower, upper) = self.pattern_range(pattern);
+        let mut positions = self.suffixes[lower..upper].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+
+    pub fn longest_repeated_substring(&self) -> Option<&[u8]> {
+        let (index, &len) = self
+            .lcp
+            .iter()
+            .enumerate()
+            .max_by_key(|&(i, &len)| (len, std::cmp::Reverse(i)))?;
+        if len == 0 {
+            return None;
+        }
+        let start = self.suffixes[index];
+        Some(&self.text[start..start + len])
+    }
+
+    // 每个后缀贡献 (后缀长度 - 与前一个后缀的 lcp) 个新的子串
+    pub fn distinct_substrings(&self) -> usize {
+        let n = self.text.len();
+        n * (n + 1) / 2 - self.lcp.iter().sum::<usize>()
+    }
+}
+
+impl From<&str> for SuffixArray {
+    fn from(text: &str) -> Self {
+        SuffixArray::new(text.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+    use std::collections::HashSet;
+
+    fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..=text.len())
+            .filter(|&i| text[i..].starts_with(pattern))
+            .collect()
+    }
+
+    fn naive_distinct_substrings(text: &[u8]) -> usize {
+        let mut substrings = HashSet::new();
+        for i in 0..text.len() {
+            for j in i + 1..=text.len() {
+                substrings.insert(&text[i..j]);
+            }
+        }
+        substrings.len()
+    }
+
+    fn naive_longest_repeated(text: &[u8]) -> usize {
+        (1..text.len())
+            .rev()
+            .find(|&len| {
+                let mut seen = HashSet::new();
+                text.windows(len).any(|w| !seen.insert(w))
+            })
+            .unwrap_or(0)
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_texts_match_brute_force() {
+        let mut random = TestRng::with_seed(34);
+        for _ in 0..200 {
+            let len = random.index(30);
+            let alphabet = 1 + random.below(3);
+            let text = random_text(&mut random, len, alphabet);
+            let array = SuffixArray::new(&text);
+
+            let mut sorted: Vec<usize> = (0..len).collect();
+            sorted.sort_by_key(|&i| &text[i..]);
+            assert_eq!(array.get_suffixes(), sorted.as_slice());
+            assert_eq!(
+                array.distinct_substrings(),
+                naive_distinct_substrings(&text)
+            );
+            let repeated = array.longest_repeated_substring().map_or(0, |s| s.len());
+            assert_eq!(repeated, naive_longest_repeated(&text));
+            if let Some(substring) = array.longest_repeated_substring() {
+                assert!(naive_find_all(&text, substring).len() >= 2);
+            }
+
+            for _ in 0..20 {
+                let pattern_len = random.index(5);
+                let pattern = random_text(&mut random, pattern_len, alphabet);
+                let expected = naive_find_all(&text, &pattern);
+                assert_eq!(array.find_all(&pattern), expected);
+                assert_eq!(array.count(&pattern), expected.len());
+                assert_eq!(array.contains(&pattern), !expected.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let array = SuffixArray::from("abc");
+        assert_eq!(array.count(b""), 4);
+        assert_eq!(array.find_all(b""), vec![0, 1, 2, 3]);
+        let empty = SuffixArray::from("");
+        assert_eq!(empty.count(b""), 1);
+        assert_eq!(empty.find_all(b""), vec![0]);
+        assert_eq!(empty.distinct_substrings(), 0);
+        assert_eq!(empty.longest_repeated_substring(), None);
+    }
+}
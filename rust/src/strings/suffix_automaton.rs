@@ -0,0 +1,321 @@
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+
+const ROOT: usize = 0;
+
+// 每个状态对应一组 endpos 集合相同的子串，len 是其中最长的那个的长度
+struct State {
+    len: usize,
+    link: Option<usize>,
+    transitions: Vec<(u8, usize)>,
+    // 这组子串第一次出现时的结尾位置（不含）
+    first_end: usize,
+    // 克隆出来的状态不对应新的结尾位置，统计出现次数时初始为 0
+    is_clone: bool,
+}
+
+impl State {
+    fn new(len: usize, first_end: usize) -> Self {
+        State {
+            len,
+            link: None,
+            transitions: Vec::new(),
+            first_end,
+            is_clone: false,
+        }
+    }
+
+    fn goto(&self, byte: u8) -> Option<usize> {
+        self.transitions
+            .binary_search_by(|(b, _)| b.cmp(&byte))
+            .ok()
+            .map(|index| self.transitions[index].1)
+    }
+
+    fn set_transition(&mut self, byte: u8, target: usize) {
+        match self.transitions.binary_search_by(|(b, _)| b.cmp(&byte)) {
+            Ok(index) => self.transitions[index].1 = target,
+            Err(index) => self.transitions.insert(index, (byte, target)),
+        }
+    }
+}
+
+pub struct SuffixAutomaton {
+    text: Vec<u8>,
+    states: Vec<State>,
+    // 整个文本对应的状态
+    last: usize,
+    // 每个状态的出现次数，文本变化后失效，查询时再重新计算
+    occurrences: RefCell<Option<Vec<usize>>>,
+}
+
+impl SuffixAutomaton {
+    pub fn new() -> Self {
+        SuffixAutomaton {
+            text: Vec::new(),
+            states: vec![State::new(0, 0)],
+            last: ROOT,
+            occurrences: RefCell::new(None),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn get_text(&self) -> &[u8] {
+        &self.text
+    }
+
+    pub fn get_state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    // 在线构造：每追加一个字节均摊 O(1) 个新状态和转移
+    pub fn push(&mut self, byte: u8) {
+        self.text.push(byte);
+        let end = self.text.len();
+        let current = self.states.len();
+        self.states
+            .push(State::new(self.states[self.last].len + 1, end));
+        let mut p = Some(self.last);
+        while let Some(index) = p
+            && self.states[index].goto(byte).is_none()
+        {
+            self.states[index].set_transition(byte, current);
+            p = self.states[index].link;
+        }
+        let link = match p {
+            None => ROOT,
+            Some(p) => {
+                let q = self.states[p].goto(byte).unwrap();
+                if self.states[p].len + 1 == self.states[q].len {
+                    q
+                } else {
+                    // q 里混有更长的子串，拆出一个长度为 len(p) + 1 的克隆状态
+                    let clone = self.states.len();
+                    self.states.push(State {
+                        len: self.states[p].len + 1,
+                        link: self.states[q].link,
+                        transitions: self.states[q].transitions.clone(),
+                        first_end: self.states[q].first_end,
+                        is_clone: true,
+                    });
+                    let mut p = Some(p);
+                    while let Some(index) = p
+                        && self.states[index].goto(byte) == Some(q)
+                    {
+                        self.states[index].set_transition(byte, clone);
+                        p = self.states[index].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    clone
+                }
+            }
+        };
+        self.states[current].link = Some(link);
+        self.last = current;
+        self.occurrences.replace(None);
+    }
+
+    fn find_state(&self, pattern: &[u8]) -> Option<usize> {
+        let mut current = ROOT;
+        for &byte in pattern {
+            current = self.states[current].goto(byte)?;
+        }
+        Some(current)
+    }
+
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.find_state(pattern).is_some()
+    }
+
+    // 第一次出现的起点
+    pub fn find_first(&self, pattern: &[u8]) -> Option<usize> {
+        let state = self.find_state(pattern)?;
+        Some(self.states[state].first_end - pattern.len())
+    }
+
+    // 空串在 0..=n 的每个位置都出现一次，和 Matcher 的约定一致
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        let state = match self.find_state(pattern) {
+            None => return 0,
+            Some(state) => state,
+        };
+        if state == ROOT {
+            return self.text.len() + 1;
+        }
+        let mut cache = self.occurrences.borrow_mut();
+        cache.get_or_insert_with(|| self.compute_occurrences())[state]
+    }
+
+    // 按 len 从大到小把出现次数沿后缀链接累加到父状态
+    fn compute_occurrences(&self) -> Vec<usize> {
+        let mut counts: Vec<usize> = self
+            .states
+            .iter()
+            .map(|state| (!state.is_clone) as usize)
+            .collect();
+        counts[ROOT] = 0;
+        let mut order: Vec<usize> = (0..self.states.len()).collect();
+        order.sort_unstable_by_key(|&index| std::cmp::Reverse(self.states[index].len));
+        for index in order {
+            if let Some(link) = self.states[index].link {
+                counts[link] += counts[index];
+            }
+        }
+        counts
+    }
+
+    // 每个状态贡献 len - len(link) 个不同的子串
+    pub fn distinct_substrings(&self) -> usize {
+        self.states
+            .iter()
+            .filter_map(|state| state.link.map(|link| state.len - self.states[link].len))
+            .sum()
+    }
+
+    // 用 other 在自动机上行走，失配时沿后缀链接回退，记录匹配长度的最大值
+    pub fn longest_common_substring<'b>(&self, other: &'b [u8]) -> &'b [u8] {
+        let mut current = ROOT;
+        let mut length = 0;
+        let mut best = (0, 0);
+        for (i, &byte) in other.iter().enumerate() {
+            while current != ROOT && self.states[current].goto(byte).is_none() {
+                current = self.states[current].link.unwrap();
+                length = self.states[current].len;
+            }
+            match self.states[current].goto(byte) {
+                Some(next) => {
+                    current = next;
+                    length += 1;
+                }
+                None => length = 0,
+            }
+            if length > best.1 {
+                best = (i + 1 - length, length);
+            }
+        }
+        &other[best.0..best.0 + best.1]
+    }
+}
+
+impl Default for SuffixAutomaton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&[u8]> for SuffixAutomaton {
+    fn from(text: &[u8]) -> Self {
+        let mut automaton = SuffixAutomaton::new();
+        automaton.extend(text.iter().copied());
+        automaton
+    }
+}
+
+impl From<&str> for SuffixAutomaton {
+    fn from(text: &str) -> Self {
+        SuffixAutomaton::from(text.as_bytes())
+    }
+}
+
+impl Extend<u8> for SuffixAutomaton {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+    use std::collections::HashSet;
+
+    fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..=text.len())
+            .filter(|&i| text[i..].starts_with(pattern))
+            .collect()
+    }
+
+    fn naive_distinct_substrings(text: &[u8]) -> usize {
+        let mut substrings = HashSet::new();
+        for i in 0..text.len() {
+            for j in i + 1..=text.len() {
+                substrings.insert(&text[i..j]);
+            }
+        }
+        substrings.len()
+    }
+
+    fn naive_longest_common(text: &[u8], other: &[u8]) -> usize {
+        (1..=other.len())
+            .rev()
+            .find(|&len| {
+                other
+                    .windows(len)
+                    .any(|w| !naive_find_all(text, w).is_empty())
+            })
+            .unwrap_or(0)
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_texts_match_brute_force() {
+        let mut random = TestRng::with_seed(35);
+        for _ in 0..100 {
+            let len = random.index(30);
+            let alphabet = 1 + random.below(3);
+            let text = random_text(&mut random, len, alphabet);
+            // 逐字节追加，每个前缀都检查一遍，顺便确认出现次数的缓存会失效
+            let mut automaton = SuffixAutomaton::new();
+            for end in 0..=len {
+                if end > 0 {
+                    automaton.push(text[end - 1]);
+                }
+                let prefix = &text[..end];
+                assert!(automaton.get_state_count() <= 2 * end.max(1));
+                assert_eq!(
+                    automaton.distinct_substrings(),
+                    naive_distinct_substrings(prefix)
+                );
+                for _ in 0..5 {
+                    let pattern_len = random.index(5);
+                    let pattern = random_text(&mut random, pattern_len, alphabet);
+                    let expected = naive_find_all(prefix, &pattern);
+                    assert_eq!(automaton.count(&pattern), expected.len());
+                    assert_eq!(automaton.contains(&pattern), !expected.is_empty());
+                    assert_eq!(automaton.find_first(&pattern), expected.first().copied());
+                }
+            }
+            let other_len = random.index(20);
+            let other = random_text(&mut random, other_len, alphabet);
+            let common = automaton.longest_common_substring(&other);
+            assert_eq!(common.len(), naive_longest_common(&text, &other));
+            assert!(automaton.contains(common));
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let automaton = SuffixAutomaton::from("abc");
+        assert_eq!(automaton.count(b""), 4);
+        assert_eq!(automaton.find_first(b""), Some(0));
+        let empty = SuffixAutomaton::new();
+        assert_eq!(empty.count(b""), 1);
+        assert_eq!(empty.distinct_substrings(), 0);
+        assert_eq!(empty.longest_common_substring(b"abc"), b"");
+    }
+}
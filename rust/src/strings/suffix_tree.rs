@@ -0,0 +1,365 @@
+#![allow(dead_code)]
+
+use std::fmt::Write;
+
+const ROOT: usize = 0;
+// 文本末尾追加一个不会和任何字节相同的终止符，保证每个后缀都结束在叶子上
+const TERMINATOR: u16 = 256;
+
+// 节点入边的标签是 text[start..end]，叶子的 end 随构造过程一起增长，用 None 表示
+struct Node {
+    start: usize,
+    end: Option<usize>,
+    children: Vec<(u16, usize)>,
+    link: usize,
+    // 叶子对应的后缀起点
+    suffix: Option<usize>,
+}
+
+impl Node {
+    fn new(start: usize, end: Option<usize>) -> Self {
+        Node {
+            start,
+            end,
+            children: Vec::new(),
+            link: ROOT,
+            suffix: None,
+        }
+    }
+
+    fn child(&self, symbol: u16) -> Option<usize> {
+        self.children
+            .binary_search_by(|(s, _)| s.cmp(&symbol))
+            .ok()
+            .map(|index| self.children[index].1)
+    }
+
+    fn set_child(&mut self, symbol: u16, child: usize) {
+        match self.children.binary_search_by(|(s, _)| s.cmp(&symbol)) {
+            Ok(index) => self.children[index].1 = child,
+            Err(index) => self.children.insert(index, (symbol, child)),
+        }
+    }
+}
+
+pub struct SuffixTree {
+    text: Vec<u16>,
+    nodes: Vec<Node>,
+}
+
+impl SuffixTree {
+    pub fn new(text: &[u8]) -> Self {
+        let mut tree = SuffixTree {
+            text: text.iter().map(|&b| b as u16).collect(),
+            nodes: vec![Node::new(0, Some(0))],
+        };
+        tree.text.push(TERMINATOR);
+        tree.build();
+        tree
+    }
+
+    fn edge_len(&self, node: usize, position: usize) -> usize {
+        self.nodes[node].end.unwrap_or(position + 1) - self.nodes[node].start
+    }
+
+    // Ukkonen 算法：活动点 (active_node, active_edge, active_length) 记录还没显式插入的最长后缀，
+    // remainder 是这一轮还需要插入的后缀个数
+    fn build(&mut self) {
+        let mut active_node = ROOT;
+        let mut active_edge = 0;
+        let mut active_length = 0;
+        let mut remainder = 0;
+        for i in 0..self.text.len() {
+            remainder += 1;
+            let symbol = self.text[i];
+            let mut last_internal: Option<usize> = None;
+            while remainder > 0 {
+                if active_length == 0 {
+                    active_edge = i;
+                }
+                let next = match self.nodes[active_node].child(self.text[active_edge]) {
+                    None => {
+                        let leaf = self.add_leaf(i, i + 1 - remainder);
+                        self.nodes[active_node].set_child(symbol, leaf);
+                        if let Some(internal) = last_internal.take() {
+                            self.nodes[internal].link = active_node;
+                        }
+                        self.advance(
+                            &mut active_node,
+                            &mut active_edge,
+                            &mut active_length,
+                            &mut remainder,
+                            i,
+                        );
+                        continue;
+                    }
+                    Some(next) => next,
+                };
+                // 活动长度跨过了整条边，先走到下一个节点
+                let edge_len = self.edge_len(next, i);
+                if active_length >= edge_len {
+                    active_node = next;
+                    active_edge += edge_len;
+                    active_length -= edge_len;
+                    continue;
+                }
+                if self.text[self.nodes[next].start + active_length] == symbol {
+                    // 当前后缀已经隐式存在，这一轮剩下的后缀也都存在，留到下一轮
+                    if let Some(internal) = last_internal.take()
+                        && active_node != ROOT
+                    {
+                        self.nodes[internal].link = active_node;
+                    }
+                    active_length += 1;
+                    break;
+                }
+                // 在边的中间拆出一个内部节点，再挂上新叶子
+                let split_at = self.nodes[next].start + active_length;
+                let split = self.nodes.len();
+                self.nodes
+                    .push(Node::new(self.nodes[next].start, Some(split_at)));
+                self.nodes[active_node].set_child(self.text[active_edge], split);
+                let leaf = self.add_leaf(i, i + 1 - remainder);
+                self.nodes[split].set_child(symbol, leaf);
+                self.nodes[next].start = split_at;
+                self.nodes[split].set_child(self.text[split_at], next);
+                if let Some(internal) = last_internal {
+                    self.nodes[internal].link = split;
+                }
+                last_internal = Some(split);
+                self.advance(
+                    &mut active_node,
+                    &mut active_edge,
+                    &mut active_length,
+                    &mut remainder,
+                    i,
+                );
+            }
+        }
+        let end = self.text.len();
+        for node in self.nodes.iter_mut() {
+            if node.end.is_none() {
+                node.end = Some(end);
+            }
+        }
+    }
+
+    fn add_leaf(&mut self, start: usize, suffix: usize) -> usize {
+        let mut leaf = Node::new(start, None);
+        leaf.suffix = Some(suffix);
+        self.nodes.push(leaf);
+        self.nodes.len() - 1
+    }
+
+    // 插入一个后缀后移动活动点：根节点上缩短活动长度，否则沿后缀链接跳转
+    fn advance(
+        &self,
+        active_node: &mut usize,
+        active_edge: &mut usize,
+        active_length: &mut usize,
+        remainder: &mut usize,
+        position: usize,
+    ) {
+        *remainder -= 1;
+        if *active_node == ROOT && *active_length > 0 {
+            *active_length -= 1;
+            *active_edge = position + 1 - *remainder;
+        } else if *active_node != ROOT {
+            *active_node = self.nodes[*active_node].link;
+        }
+    }
+
+    // 不含终止符的文本长度
+    pub fn get_size(&self) -> usize {
+        self.text.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_size() == 0
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn get_leaf_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.suffix.is_some())
+            .count()
+    }
+
+    // 沿着 pattern 往下走，返回 pattern 结束位置所在边的下端节点
+    fn locate(&self, pattern: &[u8]) -> Option<usize> {
+        let mut node = ROOT;
+        let mut matched = 0;
+        while matched < pattern.len() {
+            node = self.nodes[node].child(pattern[matched] as u16)?;
+            let start = self.nodes[node].start;
+            let end = self.nodes[node].end.unwrap();
+            for &symbol in self.text[start..end].iter() {
+                if matched == pattern.len() {
+                    break;
+                }
+                if symbol != pattern[matched] as u16 {
+                    return None;
+                }
+                matched += 1;
+            }
+        }
+        Some(node)
+    }
+
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.locate(pattern).is_some()
+    }
+
+    // 只含终止符的后缀是根的直接子节点，只有空模式串会走到它，对应位置 n 上的空匹配
+    fn leaves_below(&self, node: usize) -> Vec<usize> {
+        let mut suffixes = Vec::new();
+        let mut stack = vec![node];
+        while let Some(current) = stack.pop() {
+            match self.nodes[current].suffix {
+                Some(suffix) => suffixes.push(suffix),
+                None => stack.extend(self.nodes[current].children.iter().map(|&(_, c)| c)),
+            }
+        }
+        suffixes
+    }
+
+    // 出现次数就是对应子树里的叶子数
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        self.locate(pattern)
+            .map_or(0, |node| self.leaves_below(node).len())
+    }
+
+    // 按出现位置升序返回
+    pub fn find_all(&self, pattern: &[u8]) -> Vec<usize> {
+        let mut positions = self
+            .locate(pattern)
+            .map_or_else(Vec::new, |node| self.leaves_below(node));
+        positions.sort_unstable();
+        positions
+    }
+
+    fn edge_label(&self, node: usize) -> String {
+        let start = self.nodes[node].start;
+        let end = self.nodes[node].end.unwrap();
+        let mut label = String::new();
+        for &symbol in self.text[start..end].iter() {
+            match symbol {
+                TERMINATOR => label.push('$'),
+                b if (0x20..0x7F).contains(&b) => {
+                    let c = b as u8 as char;
+                    if c == '"' || c == '\\' {
+                        label.push('\\');
+                    }
+                    label.push(c);
+                }
+                b => {
+                    let _ = write!(label, "\\\\x{:02x}", b);
+                }
+            }
+        }
+        label
+    }
+
+    // Graphviz 格式，叶子标上后缀起点，虚线是内部节点的后缀链接
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph SuffixTree {\n");
+        dot.push_str("    node [shape=circle, label=\"\"];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let Some(suffix) = node.suffix {
+                let _ = writeln!(dot, "    n{} [shape=box, label=\"{}\"];", index, suffix);
+            }
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            for &(_, child) in node.children.iter() {
+                let _ = writeln!(
+                    dot,
+                    "    n{} -> n{} [label=\"{}\"];",
+                    index,
+                    child,
+                    self.edge_label(child)
+                );
+            }
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            if index != ROOT && node.suffix.is_none() {
+                let _ = writeln!(
+                    dot,
+                    "    n{} -> n{} [style=dashed, arrowhead=none];",
+                    index, node.link
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl From<&str> for SuffixTree {
+    fn from(text: &str) -> Self {
+        SuffixTree::new(text.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..=text.len())
+            .filter(|&i| text[i..].starts_with(pattern))
+            .collect()
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_texts_match_brute_force() {
+        let mut random = TestRng::with_seed(36);
+        for _ in 0..200 {
+            let len = random.index(40);
+            let alphabet = 1 + random.below(3);
+            let text = random_text(&mut random, len, alphabet);
+            let tree = SuffixTree::new(&text);
+            assert_eq!(tree.get_size(), len);
+            // 每个后缀（含只有终止符的空后缀）各对应一个叶子
+            assert_eq!(tree.get_leaf_count(), len + 1);
+            assert!(tree.get_node_count() <= 2 * (len + 1));
+            for _ in 0..20 {
+                let pattern_len = random.index(6);
+                let pattern = random_text(&mut random, pattern_len, alphabet);
+                let expected = naive_find_all(&text, &pattern);
+                assert_eq!(tree.find_all(&pattern), expected);
+                assert_eq!(tree.count(&pattern), expected.len());
+                assert_eq!(tree.contains(&pattern), !expected.is_empty());
+            }
+            // 文本自身的每个子串都能找到
+            let start = random.index(len + 1);
+            let end = start + random.index(len - start + 1);
+            assert_eq!(
+                tree.find_all(&text[start..end]),
+                naive_find_all(&text, &text[start..end])
+            );
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let tree = SuffixTree::new(b"abc");
+        assert_eq!(tree.count(b""), 4);
+        assert_eq!(tree.find_all(b""), vec![0, 1, 2, 3]);
+        assert_eq!(tree.find_all(b"c"), vec![2]);
+        let empty = SuffixTree::new(b"");
+        assert_eq!(empty.count(b""), 1);
+        assert_eq!(empty.find_all(b""), vec![0]);
+        assert!(!empty.contains(b"a"));
+    }
+}
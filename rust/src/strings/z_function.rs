@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+
+use super::matcher::{Match, Matcher};
+
+// z[i] 是 s[i..] 和 s 的最长公共前缀，约定 z[0] = s.len()
+pub fn z_function(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    // [left, right) 是目前右端点最靠右的、和 s 的前缀相等的区间
+    let (mut left, mut right) = (0, 0);
+    for i in 1..n {
+        let mut k = if i < right {
+            z[i - left].min(right - i)
+        } else {
+            0
+        };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        if i + k > right {
+            left = i;
+            right = i + k;
+        }
+        z[i] = k;
+    }
+    z
+}
+
+pub fn z_search<'a>(pattern: &'a [u8], haystack: &'a [u8]) -> ZIter<'a> {
+    ZIter {
+        pattern,
+        z: Cow::Owned(z_function(pattern)),
+        haystack,
+        position: 0,
+        left: 0,
+        right: 0,
+    }
+}
+
+pub struct ZMatcher {
+    pattern: Vec<u8>,
+    z: Vec<usize>,
+}
+
+impl ZMatcher {
+    pub fn new(pattern: &[u8]) -> Self {
+        ZMatcher {
+            pattern: pattern.to_vec(),
+            z: z_function(pattern),
+        }
+    }
+
+    pub fn get_pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+}
+
+impl Matcher for ZMatcher {
+    type Iter<'a> = ZIter<'a>;
+
+    fn get_name(&self) -> &'static str {
+        "Z-function"
+    }
+
+    fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> ZIter<'a> {
+        ZIter {
+            pattern: &self.pattern,
+            z: Cow::Borrowed(&self.z),
+            haystack,
+            position: 0,
+            left: 0,
+            right: 0,
+        }
+    }
+}
+
+// 相当于对 pattern + 分隔符 + haystack 求 Z 函数，但不拼接，只用 pattern 的 Z 函数逐位置计算
+pub struct ZIter<'a> {
+    pattern: &'a [u8],
+    z: Cow<'a, [usize]>,
+    haystack: &'a [u8],
+    position: usize,
+    // haystack[left..right] 等于 pattern[..right - left]
+    left: usize,
+    right: usize,
+}
+
+impl<'a> Iterator for ZIter<'a> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let m = self.pattern.len();
+        let n = self.haystack.len();
+        while self.position <= n {
+            let i = self.position;
+            self.position += 1;
+            if m == 0 {
+                return Some(Match {
+                    pattern: 0,
+                    start: i,
+                    end: i,
+                });
+            }
+            if i + m > n {
+                return None;
+            }
+            let mut k = if i < self.right {
+                self.z[i - self.left].min(self.right - i)
+            } else {
+                0
+            };
+            while k < m && i + k < n && self.haystack[i + k] == self.pattern[k] {
+                k += 1;
+            }
+            if i + k > self.right {
+                self.left = i;
+                self.right = i + k;
+            }
+            if k == m {
+                return Some(Match {
+                    pattern: 0,
+                    start: i,
+                    end: i + m,
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    fn naive_z_function(s: &[u8]) -> Vec<usize> {
+        (0..s.len())
+            .map(|i| s[i..].iter().zip(s).take_while(|(a, b)| a == b).count())
+            .collect()
+    }
+
+    fn naive_matches(pattern: &[u8], haystack: &[u8]) -> Vec<Match> {
+        (pattern.len()..=haystack.len())
+            .filter(|&end| haystack[..end].ends_with(pattern))
+            .map(|end| Match {
+                pattern: 0,
+                start: end - pattern.len(),
+                end,
+            })
+            .collect()
+    }
+
+    fn random_text(random: &mut TestRng, len: usize, alphabet: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + random.below(alphabet) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn random_searches_match_brute_force() {
+        let mut random = TestRng::with_seed(362);
+        for _ in 0..500 {
+            let alphabet = 1 + random.below(3);
+            let pattern_len = random.index(6);
+            let pattern = random_text(&mut random, pattern_len, alphabet);
+            let haystack_len = random.index(40);
+            let haystack = random_text(&mut random, haystack_len, alphabet);
+
+            assert_eq!(z_function(&haystack), naive_z_function(&haystack));
+            let matcher = ZMatcher::new(&pattern);
+            let expected = naive_matches(&pattern, &haystack);
+            assert_eq!(matcher.find_iter(&haystack).collect::<Vec<_>>(), expected);
+            assert_eq!(z_search(&pattern, &haystack).collect::<Vec<_>>(), expected);
+            assert_eq!(matcher.is_match(&haystack), !expected.is_empty());
+            assert_eq!(matcher.count(&haystack), expected.len());
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let matcher = ZMatcher::new(b"");
+        let starts: Vec<usize> = matcher.find_iter(b"abc").map(|m| m.start).collect();
+        assert_eq!(starts, vec![0, 1, 2, 3]);
+        assert_eq!(matcher.count(b""), 1);
+    }
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+// 测试用的 xorshift 随机数，固定种子保证每次运行的操作序列相同
+pub struct TestRng {
+    state: u64,
+}
+
+impl TestRng {
+    pub fn with_seed(seed: u64) -> Self {
+        // 全 0 是 xorshift 的不动点
+        let state = if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        };
+        TestRng { state }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // [0, bound)，bound 必须为正
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+}
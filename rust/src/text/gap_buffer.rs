@@ -0,0 +1,310 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, ops::Range};
+
+use super::TextBuffer;
+use crate::array::dynamic_array::DynamicArray;
+
+// 底层存储是 DynamicArray，每个槽位是 Option，中间留一段空隙 [gap_start, gap_end)，
+// 光标就在空隙处，光标附近的插入删除都是 O(1)
+pub struct GapBuffer<T> {
+    items: DynamicArray<Option<T>>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<T> GapBuffer<T> {
+    pub fn new(initial_capacity: usize) -> Result<GapBuffer<T>, String> {
+        let mut items = DynamicArray::new(initial_capacity)?;
+        for _ in 0..initial_capacity {
+            items.append(None)?;
+        }
+        Ok(GapBuffer {
+            items,
+            gap_start: 0,
+            gap_end: initial_capacity,
+        })
+    }
+
+    fn slot(&mut self, index: usize) -> &mut Option<T> {
+        self.items.get_mut(index).unwrap().unwrap()
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.items.get_size() - self.get_gap_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_size() == 0
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.items.get_size()
+    }
+
+    pub fn get_gap_size(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    pub fn get_cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    // 空隙用完时槽位数翻倍：在末尾追加空槽，再把空隙后面的元素整体挪到新的末尾
+    fn ensure_gap(&mut self, needed: usize) {
+        if self.get_gap_size() >= needed {
+            return;
+        }
+        let size = self.get_size();
+        let len = self.items.get_size();
+        let mut capacity = len.max(1);
+        while capacity - size < needed {
+            capacity *= 2;
+        }
+        let added = capacity - len;
+        for _ in 0..added {
+            self.items.append(None).unwrap();
+        }
+        for index in (self.gap_end..len).rev() {
+            let item = self.slot(index).take();
+            *self.slot(index + added) = item;
+        }
+        self.gap_end += added;
+    }
+
+    // 把空隙移动到 index 处，代价和移动的距离成正比
+    pub fn move_cursor(&mut self, index: usize) -> Result<(), String> {
+        let size = self.get_size();
+        if index > size {
+            return Err(format!(
+                "Cursor index is out of bounds. Valid range: 0 to {}. Requested: {}",
+                size, index
+            ));
+        }
+        while self.gap_start > index {
+            self.gap_start -= 1;
+            self.gap_end -= 1;
+            let item = self.slot(self.gap_start).take();
+            *self.slot(self.gap_end) = item;
+        }
+        while self.gap_start < index {
+            let item = self.slot(self.gap_end).take();
+            *self.slot(self.gap_start) = item;
+            self.gap_start += 1;
+            self.gap_end += 1;
+        }
+        Ok(())
+    }
+
+    // 在光标处插入，光标移到新元素之后
+    pub fn insert(&mut self, element: T) {
+        self.ensure_gap(1);
+        *self.slot(self.gap_start) = Some(element);
+        self.gap_start += 1;
+    }
+
+    pub fn insert_at(&mut self, index: usize, element: T) -> Result<(), String> {
+        self.move_cursor(index)?;
+        self.insert(element);
+        Ok(())
+    }
+
+    pub fn insert_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.ensure_gap(iter.size_hint().0);
+        for element in iter {
+            self.insert(element);
+        }
+    }
+
+    // 退格：删除光标前的元素
+    pub fn delete_before(&mut self) -> Option<T> {
+        if self.gap_start == 0 {
+            return None;
+        }
+        self.gap_start -= 1;
+        self.slot(self.gap_start).take()
+    }
+
+    // 删除光标后的元素
+    pub fn delete_after(&mut self) -> Option<T> {
+        if self.gap_end == self.items.get_size() {
+            return None;
+        }
+        self.gap_end += 1;
+        self.slot(self.gap_end - 1).take()
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, String> {
+        self.check_index(index)?;
+        self.move_cursor(index)?;
+        Ok(self.delete_after().unwrap())
+    }
+
+    pub fn remove_range(&mut self, range: Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        self.move_cursor(range.start)?;
+        for _ in range {
+            self.delete_after();
+        }
+        Ok(())
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        if index >= self.get_size() {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                index,
+                self.get_size()
+            ));
+        }
+        Ok(())
+    }
+
+    fn physical_index(&self, index: usize) -> usize {
+        if index < self.gap_start {
+            index
+        } else {
+            index + self.get_gap_size()
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.check_index(index).ok()?;
+        self.items.get_elements()[self.physical_index(index)].as_ref()
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.check_index(index).ok()?;
+        let index = self.physical_index(index);
+        self.slot(index).as_mut()
+    }
+
+    pub fn clear(&mut self) {
+        for index in 0..self.items.get_size() {
+            *self.slot(index) = None;
+        }
+        self.gap_start = 0;
+        self.gap_end = self.items.get_size();
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        let items = self.items.get_elements();
+        items[..self.gap_start]
+            .iter()
+            .chain(items[self.gap_end..].iter())
+            .map(|item| item.as_ref().unwrap())
+    }
+}
+
+impl<T> Default for GapBuffer<T> {
+    fn default() -> Self {
+        Self::new(16).unwrap()
+    }
+}
+
+impl<T> FromIterator<T> for GapBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut buffer = GapBuffer::default();
+        buffer.insert_iter(iter);
+        buffer
+    }
+}
+
+impl<T> Extend<T> for GapBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.insert_iter(iter);
+    }
+}
+
+impl<T> Debug for GapBuffer<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl From<&str> for GapBuffer<char> {
+    fn from(text: &str) -> Self {
+        text.chars().collect()
+    }
+}
+
+impl TextBuffer for GapBuffer<char> {
+    fn get_char_len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn insert(&mut self, char_index: usize, text: &str) -> Result<(), String> {
+        self.move_cursor(char_index)?;
+        self.insert_iter(text.chars());
+        Ok(())
+    }
+
+    fn remove(&mut self, range: Range<usize>) -> Result<(), String> {
+        self.remove_range(range)
+    }
+
+    fn slice(&self, range: Range<usize>) -> Result<String, String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        Ok(range.map(|i| *self.get(i).unwrap()).collect())
+    }
+
+    fn get_line_count(&self) -> usize {
+        self.iter().filter(|&&c| c == '\n').count() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GapBuffer;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_edits_match_vec() {
+        let mut random = TestRng::with_seed(38);
+        let mut buffer = GapBuffer::new(1).unwrap();
+        let mut expected = Vec::new();
+        for step in 0..2000 {
+            let index = random.index(expected.len() + 1);
+            match random.below(4) {
+                0 if index < expected.len() => {
+                    assert_eq!(buffer.remove(index), Ok(expected.remove(index)));
+                }
+                1 => {
+                    buffer.move_cursor(index).unwrap();
+                    buffer.insert_iter([step, step + 1, step + 2]);
+                    expected.splice(index..index, [step, step + 1, step + 2]);
+                }
+                _ => {
+                    buffer.insert_at(index, step).unwrap();
+                    expected.insert(index, step);
+                }
+            }
+            assert_eq!(buffer.get_size(), expected.len());
+            assert_eq!(
+                buffer.get_capacity(),
+                buffer.get_size() + buffer.get_gap_size()
+            );
+        }
+        assert!(buffer.iter().eq(expected.iter()));
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+pub mod gap_buffer;
+pub mod piece_table;
+pub mod rope;
+
+use std::ops::Range;
+
+// 编辑器缓冲区的公共接口，下标都按字符计算
+pub trait TextBuffer {
+    fn get_char_len(&self) -> usize;
+
+    fn insert(&mut self, char_index: usize, text: &str) -> Result<(), String>;
+
+    fn remove(&mut self, range: Range<usize>) -> Result<(), String>;
+
+    fn slice(&self, range: Range<usize>) -> Result<String, String>;
+
+    fn get_line_count(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.get_char_len() == 0
+    }
+
+    fn get_text(&self) -> String {
+        self.slice(0..self.get_char_len()).unwrap()
+    }
+}
@@ -0,0 +1,373 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::{Debug, Display},
+    ops::Range,
+};
+
+use super::TextBuffer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Original,
+    Add,
+}
+
+// 一个片段引用某个缓冲区里的 [start, start + len) 字节，chars 是其中的字符数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    source: Source,
+    start: usize,
+    len: usize,
+    chars: usize,
+}
+
+// 一次编辑把 pieces[index..index + removed.len()] 换成了 inserted，
+// 撤销时反过来换回 removed，重做时再换成 inserted
+#[derive(Debug, Clone)]
+struct Edit {
+    index: usize,
+    removed: Vec<Piece>,
+    inserted: Vec<Piece>,
+}
+
+// 原始文本只读，新插入的文本只追加到 add 缓冲区，编辑只改动片段列表；
+// 历史里只记每次编辑换下和换上的几个片段，与文档大小无关
+pub struct PieceTable {
+    original: String,
+    add: String,
+    pieces: Vec<Piece>,
+    char_len: usize,
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+}
+
+impl PieceTable {
+    pub fn new() -> Self {
+        PieceTable::from("")
+    }
+
+    pub fn get_char_len(&self) -> usize {
+        self.char_len
+    }
+
+    pub fn get_byte_len(&self) -> usize {
+        self.pieces.iter().map(|piece| piece.len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.char_len == 0
+    }
+
+    pub fn get_piece_count(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn get_add_buffer_len(&self) -> usize {
+        self.add.len()
+    }
+
+    fn text_of(&self, piece: &Piece) -> &str {
+        let buffer = match piece.source {
+            Source::Original => &self.original,
+            Source::Add => &self.add,
+        };
+        &buffer[piece.start..piece.start + piece.len]
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.char_len {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start, range.end, self.char_len
+            ));
+        }
+        Ok(())
+    }
+
+    // char_index 所在的片段下标和片段内的字符偏移；落在片段边界上时偏移为 0，落在末尾时下标为片段数
+    fn locate(&self, char_index: usize) -> (usize, usize) {
+        let mut offset = 0;
+        for (index, piece) in self.pieces.iter().enumerate() {
+            if char_index < offset + piece.chars {
+                return (index, char_index - offset);
+            }
+            offset += piece.chars;
+        }
+        (self.pieces.len(), 0)
+    }
+
+    // 在片段内第 local 个字符处一分为二
+    fn split_piece(&self, piece: Piece, local: usize) -> (Piece, Piece) {
+        let bytes = self
+            .text_of(&piece)
+            .char_indices()
+            .nth(local)
+            .map(|(i, _)| i)
+            .unwrap();
+        let left = Piece {
+            len: bytes,
+            chars: local,
+            ..piece
+        };
+        let right = Piece {
+            source: piece.source,
+            start: piece.start + bytes,
+            len: piece.len - bytes,
+            chars: piece.chars - local,
+        };
+        (left, right)
+    }
+
+    // 把 pieces[index..index + removed.len()] 换成 inserted，同步字符数
+    fn replace(&mut self, index: usize, removed: &[Piece], inserted: &[Piece]) {
+        self.pieces
+            .splice(index..index + removed.len(), inserted.iter().copied());
+        let removed: usize = removed.iter().map(|piece| piece.chars).sum();
+        let inserted: usize = inserted.iter().map(|piece| piece.chars).sum();
+        self.char_len = self.char_len - removed + inserted;
+    }
+
+    // 所有编辑都归结为替换一段连续的片段，同时记下逆操作
+    fn splice(&mut self, range: Range<usize>, inserted: Vec<Piece>) {
+        let removed = self.pieces[range.clone()].to_vec();
+        self.replace(range.start, &removed, &inserted);
+        self.undo_stack.push(Edit {
+            index: range.start,
+            removed,
+            inserted,
+        });
+        self.redo_stack.clear();
+    }
+
+    pub fn insert(&mut self, char_index: usize, text: &str) -> Result<(), String> {
+        if char_index > self.char_len {
+            return Err(format!(
+                "Insertion index is out of bounds. Valid range: 0 to {}. Requested: {}",
+                self.char_len, char_index
+            ));
+        }
+        if text.is_empty() {
+            return Ok(());
+        }
+        let piece = Piece {
+            source: Source::Add,
+            start: self.add.len(),
+            len: text.len(),
+            chars: text.chars().count(),
+        };
+        let (index, local) = self.locate(char_index);
+        if local > 0 {
+            let (left, right) = self.split_piece(self.pieces[index], local);
+            self.splice(index..index + 1, vec![left, piece, right]);
+        } else if index > 0
+            && let previous = self.pieces[index - 1]
+            && previous.source == Source::Add
+            && previous.start + previous.len == self.add.len()
+        {
+            // 连续输入时前一个片段正好以 add 缓冲区末尾结束，换成延长后的片段
+            let extended = Piece {
+                len: previous.len + piece.len,
+                chars: previous.chars + piece.chars,
+                ..previous
+            };
+            self.splice(index - 1..index, vec![extended]);
+        } else {
+            self.splice(index..index, vec![piece]);
+        }
+        self.add.push_str(text);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, range: Range<usize>) -> Result<(), String> {
+        self.check_range(&range)?;
+        if range.is_empty() {
+            return Ok(());
+        }
+        // 两端落在片段中间时保留片段在区间外的部分
+        let (start, start_local) = self.locate(range.start);
+        let (end, end_local) = self.locate(range.end);
+        let mut kept = Vec::new();
+        if start_local > 0 {
+            kept.push(self.split_piece(self.pieces[start], start_local).0);
+        }
+        let mut last = end;
+        if end_local > 0 {
+            kept.push(self.split_piece(self.pieces[end], end_local).1);
+            last += 1;
+        }
+        self.splice(start..last, kept);
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop() {
+            None => false,
+            Some(edit) => {
+                self.replace(edit.index, &edit.inserted, &edit.removed);
+                self.redo_stack.push(edit);
+                true
+            }
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            None => false,
+            Some(edit) => {
+                self.replace(edit.index, &edit.removed, &edit.inserted);
+                self.undo_stack.push(edit);
+                true
+            }
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn pieces(&self) -> impl Iterator<Item = &str> {
+        self.pieces.iter().map(|piece| self.text_of(piece))
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.pieces().flat_map(|text| text.chars())
+    }
+
+    pub fn slice(&self, range: Range<usize>) -> Result<String, String> {
+        self.check_range(&range)?;
+        Ok(self
+            .chars()
+            .skip(range.start)
+            .take(range.end - range.start)
+            .collect())
+    }
+}
+
+impl Default for PieceTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for PieceTable {
+    fn from(text: &str) -> Self {
+        let mut pieces = Vec::new();
+        let char_len = text.chars().count();
+        if !text.is_empty() {
+            pieces.push(Piece {
+                source: Source::Original,
+                start: 0,
+                len: text.len(),
+                chars: char_len,
+            });
+        }
+        PieceTable {
+            original: text.to_owned(),
+            add: String::new(),
+            pieces,
+            char_len,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+}
+
+impl Display for PieceTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for text in self.pieces() {
+            f.write_str(text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for PieceTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.pieces()).finish()
+    }
+}
+
+impl TextBuffer for PieceTable {
+    fn get_char_len(&self) -> usize {
+        self.char_len
+    }
+
+    fn insert(&mut self, char_index: usize, text: &str) -> Result<(), String> {
+        PieceTable::insert(self, char_index, text)
+    }
+
+    fn remove(&mut self, range: Range<usize>) -> Result<(), String> {
+        PieceTable::remove(self, range)
+    }
+
+    fn slice(&self, range: Range<usize>) -> Result<String, String> {
+        PieceTable::slice(self, range)
+    }
+
+    fn get_line_count(&self) -> usize {
+        self.pieces()
+            .map(|text| text.bytes().filter(|&b| b == b'\n').count())
+            .sum::<usize>()
+            + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PieceTable;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_edits_undo_and_redo() {
+        let mut random = TestRng::with_seed(38);
+        let mut table = PieceTable::from("héllo wörld");
+        let mut history = vec![table.to_string()];
+        for step in 0..300 {
+            let text: Vec<char> = history.last().unwrap().chars().collect();
+            let a = random.index(text.len() + 1);
+            let b = random.index(text.len() + 1);
+            let mut expected: Vec<char> = text.clone();
+            if random.next_u64().is_multiple_of(2) {
+                let inserted = format!("{}é", step % 10);
+                table.insert(a, &inserted).unwrap();
+                expected.splice(a..a, inserted.chars());
+            } else {
+                table.remove(a.min(b)..a.max(b)).unwrap();
+                expected.drain(a.min(b)..a.max(b));
+            }
+            let expected: String = expected.into_iter().collect();
+            assert_eq!(table.to_string(), expected);
+            assert_eq!(table.get_char_len(), expected.chars().count());
+            if expected != *history.last().unwrap() {
+                history.push(expected);
+            }
+        }
+        while table.undo() {}
+        assert_eq!(table.to_string(), history[0]);
+        while table.redo() {}
+        assert_eq!(table.to_string(), *history.last().unwrap());
+    }
+
+    #[test]
+    fn undo_restores_each_edit() {
+        let mut table = PieceTable::from("abc");
+        table.insert(3, "d").unwrap();
+        table.insert(4, "e").unwrap();
+        table.remove(1..2).unwrap();
+        assert_eq!(table.to_string(), "acde");
+        assert!(table.undo());
+        assert_eq!(table.to_string(), "abcde");
+        assert!(table.undo());
+        assert_eq!(table.to_string(), "abcd");
+        assert!(table.redo());
+        assert_eq!(table.to_string(), "abcde");
+        table.insert(0, "x").unwrap();
+        assert!(!table.can_redo());
+        assert_eq!(table.to_string(), "xabcde");
+    }
+}
@@ -0,0 +1,822 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::{Debug, Display},
+    ops::Range,
+};
+
+use super::TextBuffer;
+
+// 叶子最多存放的字节数，切分时保证落在字符边界上
+const MAX_LEAF: usize = 512;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Metrics {
+    bytes: usize,
+    chars: usize,
+    newlines: usize,
+}
+
+impl Metrics {
+    fn of(s: &str) -> Self {
+        Metrics {
+            bytes: s.len(),
+            chars: s.chars().count(),
+            newlines: s.bytes().filter(|&b| b == b'\n').count(),
+        }
+    }
+
+    fn add(self, other: Metrics) -> Self {
+        Metrics {
+            bytes: self.bytes + other.bytes,
+            chars: self.chars + other.chars,
+            newlines: self.newlines + other.newlines,
+        }
+    }
+}
+
+// 叶子存放文本片段，内部节点缓存整棵子树的字节数、字符数和换行数，按 AVL 的高度约束保持平衡
+#[derive(Clone)]
+enum Node {
+    Leaf(String, Metrics),
+    Branch {
+        left: Box<Node>,
+        right: Box<Node>,
+        height: usize,
+        metrics: Metrics,
+    },
+}
+
+impl Node {
+    fn leaf(s: String) -> Box<Node> {
+        let metrics = Metrics::of(&s);
+        Box::new(Node::Leaf(s, metrics))
+    }
+
+    fn branch(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+        Box::new(Node::Branch {
+            height: left.height().max(right.height()) + 1,
+            metrics: left.metrics().add(right.metrics()),
+            left,
+            right,
+        })
+    }
+
+    fn metrics(&self) -> Metrics {
+        match self {
+            Node::Leaf(_, metrics) => *metrics,
+            Node::Branch { metrics, .. } => *metrics,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Node::Leaf(..) => 1,
+            Node::Branch { height, .. } => *height,
+        }
+    }
+
+    fn into_children(self) -> (Box<Node>, Box<Node>) {
+        match self {
+            Node::Branch { left, right, .. } => (left, right),
+            Node::Leaf(..) => unreachable!("a leaf has no children"),
+        }
+    }
+
+    fn rotate_left(self) -> Box<Node> {
+        let (left, right) = self.into_children();
+        let (middle, right) = right.into_children();
+        Node::branch(Node::branch(left, middle), right)
+    }
+
+    fn rotate_right(self) -> Box<Node> {
+        let (left, right) = self.into_children();
+        let (left, middle) = left.into_children();
+        Node::branch(left, Node::branch(middle, right))
+    }
+
+    // 两侧高度差不超过 2 时，用单旋或双旋恢复平衡
+    fn balanced(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+        let (hl, hr) = (left.height(), right.height());
+        if hl > hr + 1 {
+            let left = match left.as_ref() {
+                Node::Branch {
+                    left: ll,
+                    right: lr,
+                    ..
+                } if lr.height() > ll.height() => left.rotate_left(),
+                _ => left,
+            };
+            Node::branch(left, right).rotate_right()
+        } else if hr > hl + 1 {
+            let right = match right.as_ref() {
+                Node::Branch {
+                    left: rl,
+                    right: rr,
+                    ..
+                } if rl.height() > rr.height() => right.rotate_right(),
+                _ => right,
+            };
+            Node::branch(left, right).rotate_left()
+        } else {
+            Node::branch(left, right)
+        }
+    }
+
+    // AVL 拼接：沿较高一侧的边界往下走到高度相近的位置再挂上去，复杂度 O(高度差)
+    fn join(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+        if left.metrics().bytes == 0 {
+            return right;
+        }
+        if right.metrics().bytes == 0 {
+            return left;
+        }
+        if let (Node::Leaf(a, _), Node::Leaf(b, _)) = (left.as_ref(), right.as_ref())
+            && a.len() + b.len() <= MAX_LEAF
+        {
+            return Node::leaf(format!("{}{}", a, b));
+        }
+        let (hl, hr) = (left.height(), right.height());
+        if hl > hr + 1 {
+            let (ll, lr) = left.into_children();
+            Node::balanced(ll, Node::join(lr, right))
+        } else if hr > hl + 1 {
+            let (rl, rr) = right.into_children();
+            Node::balanced(Node::join(left, rl), rr)
+        } else {
+            Node::branch(left, right)
+        }
+    }
+
+    // 在字节偏移 at 处拆成两棵树，调用方保证 at 在字符边界上
+    fn split(self, at: usize) -> (Box<Node>, Box<Node>) {
+        match self {
+            Node::Leaf(mut s, _) => {
+                let right = s.split_off(at);
+                (Node::leaf(s), Node::leaf(right))
+            }
+            Node::Branch { left, right, .. } => {
+                let left_bytes = left.metrics().bytes;
+                if at < left_bytes {
+                    let (a, b) = left.split(at);
+                    (a, Node::join(b, right))
+                } else if at > left_bytes {
+                    let (a, b) = right.split(at - left_bytes);
+                    (Node::join(left, a), b)
+                } else {
+                    (left, right)
+                }
+            }
+        }
+    }
+
+    // 把文本切成不超过 MAX_LEAF 字节的叶子，再对半分建成平衡树
+    fn build(text: &str) -> Box<Node> {
+        let mut leaves = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let mut end = rest.len().min(MAX_LEAF);
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            leaves.push(Node::leaf(rest[..end].to_owned()));
+            rest = &rest[end..];
+        }
+        if leaves.is_empty() {
+            return Node::leaf(String::new());
+        }
+        let count = leaves.len();
+        Node::from_leaves(&mut leaves.into_iter(), count)
+    }
+
+    // 两半的叶子数最多差一，高度也最多差一；两两合并的做法在叶子数为奇数时会差二
+    fn from_leaves(leaves: &mut impl Iterator<Item = Box<Node>>, count: usize) -> Box<Node> {
+        if count == 1 {
+            return leaves.next().unwrap();
+        }
+        let left = Node::from_leaves(leaves, count / 2);
+        let right = Node::from_leaves(leaves, count - count / 2);
+        Node::branch(left, right)
+    }
+}
+
+#[derive(Clone)]
+pub struct Rope {
+    root: Box<Node>,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Rope {
+            root: Node::leaf(String::new()),
+        }
+    }
+
+    pub fn get_byte_len(&self) -> usize {
+        self.root.metrics().bytes
+    }
+
+    pub fn get_char_len(&self) -> usize {
+        self.root.metrics().chars
+    }
+
+    // 行数等于换行符个数加一，空文本也算一行
+    pub fn get_line_count(&self) -> usize {
+        self.root.metrics().newlines + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_byte_len() == 0
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.root.height()
+    }
+
+    fn out_of_bounds(index: usize, size: usize) -> String {
+        format!("Index out of bounds: {}. Current size: {}.", index, size)
+    }
+
+    fn check_byte(&self, byte: usize) -> Result<(), String> {
+        if byte > self.get_byte_len() {
+            return Err(Self::out_of_bounds(byte, self.get_byte_len()));
+        }
+        if !self.is_char_boundary(byte) {
+            return Err(format!("Byte offset {} is not on a char boundary.", byte));
+        }
+        Ok(())
+    }
+
+    pub fn is_char_boundary(&self, byte: usize) -> bool {
+        if byte > self.get_byte_len() {
+            return false;
+        }
+        let mut node = self.root.as_ref();
+        let mut local = byte;
+        loop {
+            match node {
+                Node::Leaf(s, _) => return s.is_char_boundary(local),
+                Node::Branch { left, right, .. } => {
+                    let left_bytes = left.metrics().bytes;
+                    if local <= left_bytes {
+                        node = left;
+                    } else {
+                        local -= left_bytes;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn char_to_byte(&self, char_index: usize) -> Result<usize, String> {
+        if char_index > self.get_char_len() {
+            return Err(Self::out_of_bounds(char_index, self.get_char_len()));
+        }
+        let mut node = self.root.as_ref();
+        let mut local = char_index;
+        let mut byte = 0;
+        loop {
+            match node {
+                Node::Leaf(s, _) => {
+                    let offset = s.char_indices().nth(local).map_or(s.len(), |(i, _)| i);
+                    return Ok(byte + offset);
+                }
+                Node::Branch { left, right, .. } => {
+                    let metrics = left.metrics();
+                    if local <= metrics.chars {
+                        node = left;
+                    } else {
+                        local -= metrics.chars;
+                        byte += metrics.bytes;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn byte_to_char(&self, byte: usize) -> Result<usize, String> {
+        self.check_byte(byte)?;
+        let mut node = self.root.as_ref();
+        let mut local = byte;
+        let mut chars = 0;
+        loop {
+            match node {
+                Node::Leaf(s, _) => return Ok(chars + s[..local].chars().count()),
+                Node::Branch { left, right, .. } => {
+                    let metrics = left.metrics();
+                    if local <= metrics.bytes {
+                        node = left;
+                    } else {
+                        local -= metrics.bytes;
+                        chars += metrics.chars;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    // 第 line 行（从 0 开始）的起始字节偏移，也就是第 line 个换行符之后的位置
+    pub fn line_to_byte(&self, line: usize) -> Result<usize, String> {
+        if line >= self.get_line_count() {
+            return Err(Self::out_of_bounds(line, self.get_line_count()));
+        }
+        if line == 0 {
+            return Ok(0);
+        }
+        let mut node = self.root.as_ref();
+        let mut local = line;
+        let mut byte = 0;
+        loop {
+            match node {
+                Node::Leaf(s, _) => {
+                    let (index, _) = s
+                        .bytes()
+                        .enumerate()
+                        .filter(|&(_, b)| b == b'\n')
+                        .nth(local - 1)
+                        .unwrap();
+                    return Ok(byte + index + 1);
+                }
+                Node::Branch { left, right, .. } => {
+                    let metrics = left.metrics();
+                    if local <= metrics.newlines {
+                        node = left;
+                    } else {
+                        local -= metrics.newlines;
+                        byte += metrics.bytes;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn line_to_char(&self, line: usize) -> Result<usize, String> {
+        self.byte_to_char(self.line_to_byte(line)?)
+    }
+
+    // byte 所在的行号，等于它前面的换行符个数
+    pub fn byte_to_line(&self, byte: usize) -> Result<usize, String> {
+        if byte > self.get_byte_len() {
+            return Err(Self::out_of_bounds(byte, self.get_byte_len()));
+        }
+        let mut node = self.root.as_ref();
+        let mut local = byte;
+        let mut line = 0;
+        loop {
+            match node {
+                Node::Leaf(s, _) => {
+                    return Ok(line
+                        + s.as_bytes()[..local]
+                            .iter()
+                            .filter(|&&b| b == b'\n')
+                            .count());
+                }
+                Node::Branch { left, right, .. } => {
+                    let metrics = left.metrics();
+                    if local <= metrics.bytes {
+                        node = left;
+                    } else {
+                        local -= metrics.bytes;
+                        line += metrics.newlines;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn char_to_line(&self, char_index: usize) -> Result<usize, String> {
+        self.byte_to_line(self.char_to_byte(char_index)?)
+    }
+
+    pub fn char_at(&self, char_index: usize) -> Option<char> {
+        if char_index >= self.get_char_len() {
+            return None;
+        }
+        let byte = self.char_to_byte(char_index).ok()?;
+        self.chunks_from(byte).next()?.chars().next()
+    }
+
+    fn take_root(&mut self) -> Box<Node> {
+        std::mem::replace(&mut self.root, Node::leaf(String::new()))
+    }
+
+    pub fn insert_at_byte(&mut self, byte: usize, text: &str) -> Result<(), String> {
+        self.check_byte(byte)?;
+        if text.is_empty() {
+            return Ok(());
+        }
+        let (left, right) = self.take_root().split(byte);
+        self.root = Node::join(Node::join(left, Node::build(text)), right);
+        Ok(())
+    }
+
+    pub fn insert(&mut self, char_index: usize, text: &str) -> Result<(), String> {
+        let byte = self.char_to_byte(char_index)?;
+        self.insert_at_byte(byte, text)
+    }
+
+    pub fn remove_bytes(&mut self, range: Range<usize>) -> Result<(), String> {
+        if range.start > range.end {
+            return Err(format!(
+                "Invalid range: start {} is greater than end {}.",
+                range.start, range.end
+            ));
+        }
+        self.check_byte(range.start)?;
+        self.check_byte(range.end)?;
+        let (left, rest) = self.take_root().split(range.start);
+        let (_, right) = rest.split(range.end - range.start);
+        self.root = Node::join(left, right);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, range: Range<usize>) -> Result<(), String> {
+        if range.start > range.end {
+            return Err(format!(
+                "Invalid range: start {} is greater than end {}.",
+                range.start, range.end
+            ));
+        }
+        let start = self.char_to_byte(range.start)?;
+        let end = self.char_to_byte(range.end)?;
+        self.remove_bytes(start..end)
+    }
+
+    // 保留 [0, byte) 部分，返回剩下的部分
+    pub fn split_off_at_byte(&mut self, byte: usize) -> Result<Rope, String> {
+        self.check_byte(byte)?;
+        let (left, right) = self.take_root().split(byte);
+        self.root = left;
+        Ok(Rope { root: right })
+    }
+
+    pub fn split_off(&mut self, char_index: usize) -> Result<Rope, String> {
+        let byte = self.char_to_byte(char_index)?;
+        self.split_off_at_byte(byte)
+    }
+
+    pub fn append(&mut self, other: Rope) {
+        self.root = Node::join(self.take_root(), other.root);
+    }
+
+    pub fn concat(mut left: Rope, right: Rope) -> Rope {
+        left.append(right);
+        left
+    }
+
+    // 只访问和区间相交的子树
+    pub fn slice_bytes(&self, range: Range<usize>) -> Result<String, String> {
+        if range.start > range.end {
+            return Err(format!(
+                "Invalid range: start {} is greater than end {}.",
+                range.start, range.end
+            ));
+        }
+        self.check_byte(range.start)?;
+        self.check_byte(range.end)?;
+        let mut out = String::with_capacity(range.end - range.start);
+        let mut stack = vec![(self.root.as_ref(), 0)];
+        while let Some((node, offset)) = stack.pop() {
+            let end = offset + node.metrics().bytes;
+            if end <= range.start || offset >= range.end {
+                continue;
+            }
+            match node {
+                Node::Leaf(s, _) => {
+                    let from = range.start.max(offset) - offset;
+                    let to = range.end.min(end) - offset;
+                    out.push_str(&s[from..to]);
+                }
+                Node::Branch { left, right, .. } => {
+                    stack.push((right, offset + left.metrics().bytes));
+                    stack.push((left, offset));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn slice(&self, range: Range<usize>) -> Result<String, String> {
+        if range.start > range.end {
+            return Err(format!(
+                "Invalid range: start {} is greater than end {}.",
+                range.start, range.end
+            ));
+        }
+        let start = self.char_to_byte(range.start)?;
+        let end = self.char_to_byte(range.end)?;
+        self.slice_bytes(start..end)
+    }
+
+    // 不含结尾的换行符
+    pub fn get_line(&self, line: usize) -> Result<String, String> {
+        let start = self.line_to_byte(line)?;
+        let end = if line + 1 < self.get_line_count() {
+            self.line_to_byte(line + 1)? - 1
+        } else {
+            self.get_byte_len()
+        };
+        self.slice_bytes(start..end)
+    }
+
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks {
+            stack: vec![self.root.as_ref()],
+            skip: 0,
+        }
+    }
+
+    // 从 byte 所在的叶子开始，第一块从 byte 处截断
+    fn chunks_from(&self, byte: usize) -> Chunks<'_> {
+        let mut stack = Vec::new();
+        let mut node = self.root.as_ref();
+        let mut local = byte;
+        while let Node::Branch { left, right, .. } = node {
+            let left_bytes = left.metrics().bytes;
+            if local < left_bytes {
+                stack.push(right.as_ref());
+                node = left;
+            } else {
+                local -= left_bytes;
+                node = right;
+            }
+        }
+        stack.push(node);
+        Chunks { stack, skip: local }
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks().flat_map(|chunk| chunk.chars())
+    }
+
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.chunks().flat_map(|chunk| chunk.bytes())
+    }
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        Rope {
+            root: Node::build(text),
+        }
+    }
+}
+
+impl From<String> for Rope {
+    fn from(text: String) -> Self {
+        Rope::from(text.as_str())
+    }
+}
+
+impl Display for Rope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for chunk in self.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Rope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.chunks()).finish()
+    }
+}
+
+impl PartialEq for Rope {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_byte_len() == other.get_byte_len() && self.bytes().eq(other.bytes())
+    }
+}
+
+impl Eq for Rope {}
+
+// 按从左到右的顺序给出每个叶子里的文本
+pub struct Chunks<'a> {
+    stack: Vec<&'a Node>,
+    // 第一个叶子需要跳过的字节数
+    skip: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while let Some(node) = self.stack.pop() {
+            match node {
+                Node::Leaf(s, _) => {
+                    let chunk = &s[self.skip..];
+                    self.skip = 0;
+                    if !chunk.is_empty() {
+                        return Some(chunk);
+                    }
+                }
+                Node::Branch { left, right, .. } => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl TextBuffer for Rope {
+    fn get_char_len(&self) -> usize {
+        Rope::get_char_len(self)
+    }
+
+    fn insert(&mut self, char_index: usize, text: &str) -> Result<(), String> {
+        Rope::insert(self, char_index, text)
+    }
+
+    fn remove(&mut self, range: Range<usize>) -> Result<(), String> {
+        Rope::remove(self, range)
+    }
+
+    fn slice(&self, range: Range<usize>) -> Result<String, String> {
+        Rope::slice(self, range)
+    }
+
+    fn get_line_count(&self) -> usize {
+        Rope::get_line_count(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    // 检查缓存的高度和统计量，AVL 平衡，叶子大小，以及除了空树以外没有空叶子
+    fn check_node(node: &Node, is_root: bool) -> (usize, Metrics) {
+        match node {
+            Node::Leaf(s, metrics) => {
+                assert_eq!(*metrics, Metrics::of(s));
+                assert!(s.len() <= MAX_LEAF);
+                assert!(is_root || !s.is_empty());
+                (1, *metrics)
+            }
+            Node::Branch {
+                left,
+                right,
+                height,
+                metrics,
+            } => {
+                let (hl, ml) = check_node(left, false);
+                let (hr, mr) = check_node(right, false);
+                assert!(hl.abs_diff(hr) <= 1);
+                assert_eq!(*height, hl.max(hr) + 1);
+                assert_eq!(*metrics, ml.add(mr));
+                (*height, *metrics)
+            }
+        }
+    }
+
+    fn check_rope(rope: &Rope, expected: &str) {
+        check_node(&rope.root, true);
+        assert_eq!(rope.to_string(), expected);
+        assert_eq!(rope.get_byte_len(), expected.len());
+        assert_eq!(rope.get_char_len(), expected.chars().count());
+        assert_eq!(rope.get_line_count(), expected.matches('\n').count() + 1);
+    }
+
+    fn random_text(random: &mut TestRng, max_chars: usize) -> String {
+        const ALPHABET: [char; 6] = ['a', 'b', '\n', 'é', '中', '😀'];
+        let len = random.index(max_chars + 1);
+        (0..len)
+            .map(|_| ALPHABET[random.index(ALPHABET.len())])
+            .collect()
+    }
+
+    fn char_to_byte(text: &str, char_index: usize) -> usize {
+        text.char_indices()
+            .nth(char_index)
+            .map_or(text.len(), |(i, _)| i)
+    }
+
+    #[test]
+    fn random_edits_match_string() {
+        let mut random = TestRng::with_seed(37);
+        let mut rope = Rope::new();
+        let mut expected = String::new();
+        for _ in 0..400 {
+            let chars = expected.chars().count();
+            let a = random.index(chars + 1);
+            let b = random.index(chars + 1);
+            match random.below(4) {
+                0 | 1 => {
+                    let text = random_text(&mut random, 600);
+                    rope.insert(a, &text).unwrap();
+                    expected.insert_str(char_to_byte(&expected, a), &text);
+                }
+                2 => {
+                    let (start, end) = (a.min(b), a.max(b));
+                    rope.remove(start..end).unwrap();
+                    let range = char_to_byte(&expected, start)..char_to_byte(&expected, end);
+                    expected.replace_range(range, "");
+                }
+                _ => {
+                    // 拆开再拼回去，两半和结果都必须保持平衡
+                    let right = rope.split_off(a).unwrap();
+                    let at = char_to_byte(&expected, a);
+                    check_rope(&rope, &expected[..at]);
+                    check_rope(&right, &expected[at..]);
+                    rope.append(right);
+                }
+            }
+            check_rope(&rope, &expected);
+            let chars = expected.chars().count();
+            let (a, b) = (random.index(chars + 1), random.index(chars + 1));
+            let (start, end) = (a.min(b), a.max(b));
+            let expected_slice: String = expected.chars().skip(start).take(end - start).collect();
+            assert_eq!(rope.slice(start..end).unwrap(), expected_slice);
+        }
+        assert!(rope.get_height() > 2);
+    }
+
+    #[test]
+    fn concat_of_uneven_ropes_stays_balanced() {
+        let mut random = TestRng::with_seed(371);
+        for _ in 0..50 {
+            let left = "x".repeat(random.index(40 * MAX_LEAF));
+            let right = "y".repeat(random.index(40 * MAX_LEAF));
+            let rope = Rope::concat(Rope::from(left.as_str()), Rope::from(right.as_str()));
+            check_rope(&rope, &(left + &right));
+        }
+        for leaves in 1..40 {
+            let text = "z".repeat(leaves * MAX_LEAF);
+            check_rope(&Rope::from(text.as_str()), &text);
+        }
+    }
+
+    #[test]
+    fn line_indexing_matches_string() {
+        let mut random = TestRng::with_seed(372);
+        for _ in 0..20 {
+            let text = random_text(&mut random, 3000);
+            let rope = Rope::from(text.as_str());
+            let lines: Vec<&str> = text.split('\n').collect();
+            assert_eq!(rope.get_line_count(), lines.len());
+            let mut byte = 0;
+            for (line, content) in lines.iter().enumerate() {
+                assert_eq!(rope.line_to_byte(line), Ok(byte));
+                assert_eq!(rope.line_to_char(line), Ok(text[..byte].chars().count()));
+                assert_eq!(rope.get_line(line).unwrap(), *content);
+                assert_eq!(rope.byte_to_line(byte), Ok(line));
+                byte += content.len() + 1;
+            }
+            let line_count = lines.len();
+            assert!(rope.line_to_byte(line_count).is_err());
+            assert!(rope.get_line(line_count).is_err());
+            for (char_index, (byte, _)) in text.char_indices().enumerate() {
+                let line = text[..byte].matches('\n').count();
+                assert_eq!(rope.char_to_line(char_index), Ok(line));
+                assert_eq!(rope.char_to_byte(char_index), Ok(byte));
+                assert_eq!(rope.byte_to_char(byte), Ok(char_index));
+            }
+        }
+    }
+
+    #[test]
+    fn byte_offsets_inside_a_char_are_rejected() {
+        // 每个叶子末尾都截在字符边界上，多字节字符不会被拆到两个叶子里
+        let text = "中".repeat(MAX_LEAF);
+        let mut rope = Rope::from(text.as_str());
+        check_rope(&rope, &text);
+        // 第一个叶子在 leaf_end 处结束，inside 落在第二个叶子的第一个字符中间
+        let leaf_end = MAX_LEAF / 3 * 3;
+        let inside = leaf_end + 1;
+        assert!(rope.is_char_boundary(leaf_end));
+        assert!(!rope.is_char_boundary(inside));
+        assert!(rope.byte_to_char(inside).is_err());
+        assert!(rope.insert_at_byte(inside, "x").is_err());
+        assert!(rope.remove_bytes(0..inside).is_err());
+        assert!(rope.slice_bytes(inside..text.len()).is_err());
+        assert!(rope.split_off_at_byte(inside).is_err());
+        let (start, end) = (6, 3);
+        assert!(rope.remove_bytes(start..end).is_err());
+        assert!(rope.slice(start..end).is_err());
+        assert!(rope.insert(rope.get_char_len() + 1, "x").is_err());
+        assert!(!rope.is_char_boundary(text.len() + 1));
+        check_rope(&rope, &text);
+
+        rope.insert_at_byte(3, "é").unwrap();
+        assert_eq!(rope.char_at(1), Some('é'));
+        assert_eq!(rope.char_at(2), Some('中'));
+        assert!(rope.byte_to_char(4).is_err());
+        assert_eq!(rope.byte_to_char(5), Ok(2));
+        assert_eq!(rope.char_at(rope.get_char_len()), None);
+    }
+}
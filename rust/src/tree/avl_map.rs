@@ -0,0 +1,365 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, cmp::Ordering, fmt::Debug, ops::RangeBounds};
+
+use super::avl_tree::{self, AvlNode};
+use super::search_tree::{self, IterMut, Range, SearchNode, SplitMut};
+
+type Link<K, V> = avl_tree::Link<Node<K, V>>;
+
+pub struct Node<K, V> {
+    key: K,
+    value: V,
+    // 以该节点为根的子树高度，叶子为 1
+    height: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> SearchNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.key,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+impl<K, V> AvlNode for Node<K, V> {
+    fn leaf(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn left_link(&mut self) -> &mut Link<K, V> {
+        &mut self.left
+    }
+
+    fn right_link(&mut self) -> &mut Link<K, V> {
+        &mut self.right
+    }
+
+    // 只需要维护高度
+    fn update(&mut self) {
+        self.height = 1 + avl_tree::height_of(self.left()).max(avl_tree::height_of(self.right()));
+    }
+}
+
+// 高度平衡的二叉搜索树，任意节点左右子树高度差不超过 1，树高不超过约 1.44 log n
+pub struct AvlMap<K, V> {
+    root: Link<K, V>,
+    size: usize,
+    // 累计旋转次数，双旋记两次
+    rotations: usize,
+}
+
+impl<K, V> AvlMap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        AvlMap {
+            root: None,
+            size: 0,
+            rotations: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = avl_tree::insert(&mut self.root, key, value, &mut self.rotations);
+        if old.is_none() {
+            self.size += 1;
+        }
+        old
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(search_tree::entry)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let compare = |node: &Node<K, V>| key.cmp(node.key.borrow());
+        let node = avl_tree::remove(&mut self.root, &compare, &mut self.rotations)?;
+        self.size -= 1;
+        Some((node.key, node.value))
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        let node = avl_tree::take_min(&mut self.root, &mut self.rotations);
+        self.size -= 1;
+        Some((node.key, node.value))
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        let node = avl_tree::take_max(&mut self.root, &mut self.rotations);
+        self.size -= 1;
+        Some((node.key, node.value))
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn successor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn iter(&self) -> Range<'_, Node<K, V>> {
+        search_tree::iter(self.root.as_deref())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        search_tree::range(self.root.as_deref(), range)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Node<K, V>> {
+        search_tree::iter_mut(self.root.as_deref_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    // 直接读根节点记录的高度
+    pub fn height(&self) -> usize {
+        avl_tree::height_of(self.root.as_deref())
+    }
+
+    pub fn get_rotation_count(&self) -> usize {
+        self.rotations
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.size = 0;
+    }
+
+    // 除了有序性和节点数，还要核对每个节点的高度和平衡因子
+    pub fn check_invariants(&self) -> Result<(), String> {
+        search_tree::check_order(self.root.as_deref(), self.size)?;
+        avl_tree::check_balance(self.root.as_deref())?;
+        Ok(())
+    }
+}
+
+impl<K, V> Default for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a AvlMap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, Node<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for AvlMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::AvlMap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(41);
+        let mut map = AvlMap::new();
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            match random.below(8) {
+                0..=2 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                3 => assert_eq!(map.pop_min(), expected.pop_first()),
+                4 => assert_eq!(map.pop_max(), expected.pop_last()),
+                _ => assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2)),
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+    }
+
+    #[test]
+    fn sequential_inserts_stay_balanced() {
+        let map: AvlMap<u32, ()> = (0..1023).map(|key| (key, ())).collect();
+        map.check_invariants().unwrap();
+        assert_eq!(map.height(), 10);
+        assert!(map.get_rotation_count() > 0);
+    }
+}
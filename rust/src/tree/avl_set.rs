@@ -0,0 +1,236 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, fmt::Debug, ops::RangeBounds};
+
+use super::avl_map::{self, AvlMap};
+use super::search_tree;
+
+// 集合就是值为 () 的 AVL 树
+pub struct AvlSet<T> {
+    map: AvlMap<T, ()>,
+}
+
+impl<T> AvlSet<T>
+where
+    T: Ord,
+{
+    pub fn new() -> Self {
+        AvlSet { map: AvlMap::new() }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.map.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.map.contains_key(&value) {
+            return false;
+        }
+        self.map.insert(value, ());
+        true
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get_key_value(value).map(|(k, _)| k)
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(value).map(|(k, _)| k)
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.map.pop_min().map(|(k, _)| k)
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.map.pop_max().map(|(k, _)| k)
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.map.min().map(|(k, _)| k)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.map.max().map(|(k, _)| k)
+    }
+
+    pub fn floor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.floor(value).map(|(k, _)| k)
+    }
+
+    pub fn ceiling<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.ceiling(value).map(|(k, _)| k)
+    }
+
+    pub fn predecessor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.predecessor(value).map(|(k, _)| k)
+    }
+
+    pub fn successor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.successor(value).map(|(k, _)| k)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.map.iter(),
+        }
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Iter {
+            inner: self.map.range(range),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.map.height()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        self.map.check_invariants()
+    }
+}
+
+pub struct Iter<'a, T> {
+    inner: search_tree::Range<'a, avl_map::Node<T, ()>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+}
+
+impl<T> Default for AvlSet<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for AvlSet<T>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = AvlSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<T> for AvlSet<T>
+where
+    T: Ord,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a AvlSet<T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Debug for AvlSet<T>
+where
+    T: Ord + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::AvlSet;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_set() {
+        let mut random = TestRng::with_seed(41);
+        let mut set = AvlSet::new();
+        let mut expected = BTreeSet::new();
+        for _ in 0..2000 {
+            let value = random.below(200);
+            if random.next_u64().is_multiple_of(3) {
+                assert_eq!(set.remove(&value), expected.remove(&value));
+            } else {
+                assert_eq!(set.insert(value), expected.insert(value));
+            }
+            set.check_invariants().unwrap();
+        }
+        assert!(set.iter().eq(expected.iter()));
+        let (start, end) = (150, 50);
+        assert_eq!(set.range(start..end).count(), 0);
+    }
+}
@@ -0,0 +1,189 @@
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+
+use super::search_tree::SearchNode;
+
+pub type Link<N> = Option<Box<N>>;
+
+// 基于 AVL 的各种树共用旋转、重新平衡和递归插入删除，节点只需给出孩子链接和高度，
+// 并在 update 里根据孩子刷新自己的高度以及子树大小、最大右端点之类的附加信息
+pub trait AvlNode: SearchNode {
+    fn leaf(key: Self::Key, value: Self::Value) -> Box<Self>;
+
+    fn height(&self) -> usize;
+
+    fn left_link(&mut self) -> &mut Link<Self>;
+
+    fn right_link(&mut self) -> &mut Link<Self>;
+
+    // 孩子变化之后调用，先刷新孩子再刷新父节点
+    fn update(&mut self);
+}
+
+pub fn height_of<N: AvlNode>(node: Option<&N>) -> usize {
+    node.map_or(0, |node| node.height())
+}
+
+// 左高为正，右高为负
+fn balance_factor<N: AvlNode>(node: &N) -> isize {
+    height_of(node.left()) as isize - height_of(node.right()) as isize
+}
+
+//     node              left
+//     /  \             /    \
+//   left  c    =>     a     node
+//   /  \                    /  \
+//  a    b                  b    c
+fn rotate_right<N: AvlNode>(mut node: Box<N>, rotations: &mut usize) -> Box<N> {
+    *rotations += 1;
+    let mut left = node.left_link().take().unwrap();
+    *node.left_link() = left.right_link().take();
+    node.update();
+    *left.right_link() = Some(node);
+    left.update();
+    left
+}
+
+fn rotate_left<N: AvlNode>(mut node: Box<N>, rotations: &mut usize) -> Box<N> {
+    *rotations += 1;
+    let mut right = node.right_link().take().unwrap();
+    *node.right_link() = right.left_link().take();
+    node.update();
+    *right.left_link() = Some(node);
+    right.update();
+    right
+}
+
+// 左右子树高度差超过 1 时旋转：LL、RR 单旋，LR、RL 先把孩子转成同向再单旋
+fn rebalance<N: AvlNode>(mut node: Box<N>, rotations: &mut usize) -> Box<N> {
+    node.update();
+    let factor = balance_factor(node.as_ref());
+    if factor > 1 {
+        if balance_factor(node.left().unwrap()) < 0 {
+            let left = node.left_link().take().unwrap();
+            *node.left_link() = Some(rotate_left(left, rotations));
+        }
+        return rotate_right(node, rotations);
+    }
+    if factor < -1 {
+        if balance_factor(node.right().unwrap()) > 0 {
+            let right = node.right_link().take().unwrap();
+            *node.right_link() = Some(rotate_right(right, rotations));
+        }
+        return rotate_left(node, rotations);
+    }
+    node
+}
+
+fn rebalance_link<N: AvlNode>(link: &mut Link<N>, rotations: &mut usize) {
+    if let Some(node) = link.take() {
+        *link = Some(rebalance(node, rotations));
+    }
+}
+
+// 树高是 O(log n)，插入和删除直接递归，回溯时沿途重新平衡；只替换值时附加信息不变，不必回溯
+pub fn insert<N>(
+    link: &mut Link<N>,
+    key: N::Key,
+    value: N::Value,
+    rotations: &mut usize,
+) -> Option<N::Value>
+where
+    N: AvlNode,
+    N::Key: Ord,
+{
+    let Some(node) = link else {
+        *link = Some(N::leaf(key, value));
+        return None;
+    };
+    let old = match key.cmp(node.key()) {
+        Ordering::Less => insert(node.left_link(), key, value, rotations),
+        Ordering::Greater => insert(node.right_link(), key, value, rotations),
+        Ordering::Equal => return Some(std::mem::replace(node.split_mut().1, value)),
+    };
+    if old.is_none() {
+        rebalance_link(link, rotations);
+    }
+    old
+}
+
+// compare 给出要删除的键相对于节点键的大小，返回摘下来的节点
+pub fn remove<N, F>(link: &mut Link<N>, compare: &F, rotations: &mut usize) -> Option<Box<N>>
+where
+    N: AvlNode,
+    F: Fn(&N) -> Ordering,
+{
+    let node = link.as_mut()?;
+    let removed = match compare(node) {
+        Ordering::Less => remove(node.left_link(), compare, rotations),
+        Ordering::Greater => remove(node.right_link(), compare, rotations),
+        Ordering::Equal => {
+            let mut node = link.take().unwrap();
+            *link = match (node.left_link().take(), node.right_link().take()) {
+                (None, right) => right,
+                (left, None) => left,
+                (left, mut right) => {
+                    let mut successor = take_min(&mut right, rotations);
+                    *successor.left_link() = left;
+                    *successor.right_link() = right;
+                    Some(successor)
+                }
+            };
+            Some(node)
+        }
+    };
+    if removed.is_some() {
+        rebalance_link(link, rotations);
+    }
+    removed
+}
+
+// 摘下子树里的最小节点，回溯时重新平衡
+pub fn take_min<N: AvlNode>(link: &mut Link<N>, rotations: &mut usize) -> Box<N> {
+    let node = link.as_mut().unwrap();
+    if node.left().is_some() {
+        let min = take_min(node.left_link(), rotations);
+        rebalance_link(link, rotations);
+        return min;
+    }
+    let mut node = link.take().unwrap();
+    *link = node.right_link().take();
+    node
+}
+
+pub fn take_max<N: AvlNode>(link: &mut Link<N>, rotations: &mut usize) -> Box<N> {
+    let node = link.as_mut().unwrap();
+    if node.right().is_some() {
+        let max = take_max(node.right_link(), rotations);
+        rebalance_link(link, rotations);
+        return max;
+    }
+    let mut node = link.take().unwrap();
+    *link = node.left_link().take();
+    node
+}
+
+// 后序检查每个节点记录的高度和平衡因子，返回子树高度
+pub fn check_balance<N: AvlNode>(node: Option<&N>) -> Result<usize, String> {
+    let Some(node) = node else {
+        return Ok(0);
+    };
+    let left = check_balance(node.left())?;
+    let right = check_balance(node.right())?;
+    let height = 1 + left.max(right);
+    if node.height() != height {
+        return Err(format!(
+            "Height mismatch: recorded {}, actual {}.",
+            node.height(),
+            height
+        ));
+    }
+    if left.abs_diff(right) > 1 {
+        return Err(format!(
+            "Unbalanced node: left height {}, right height {}.",
+            left, right
+        ));
+    }
+    Ok(height)
+}
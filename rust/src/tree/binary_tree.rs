@@ -0,0 +1,764 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, hash::Hash};
+
+use crate::hash_table::hash_set::HashSet;
+use crate::queue::array_queue::ArrayQueue;
+use crate::stack::array_stack::ArrayStack;
+
+pub type NodeId = usize;
+
+struct TreeNode<T> {
+    value: T,
+    left: Option<NodeId>,
+    right: Option<NodeId>,
+}
+
+// 节点存放在数组里，用下标互相引用；Morris 遍历需要临时改写右指针，下标比 Box 更方便
+pub struct BinaryTree<T> {
+    nodes: Vec<TreeNode<T>>,
+    root: Option<NodeId>,
+}
+
+impl<T> BinaryTree<T> {
+    pub fn new() -> Self {
+        BinaryTree {
+            nodes: Vec::new(),
+            root: None,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn get_root(&self) -> Option<NodeId> {
+        self.root
+    }
+
+    fn add_node(&mut self, value: T) -> NodeId {
+        self.nodes.push(TreeNode {
+            value,
+            left: None,
+            right: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn check_node(&self, node: NodeId) -> Result<(), String> {
+        if node >= self.nodes.len() {
+            return Err(format!(
+                "Node {} does not exist. Current size: {}.",
+                node,
+                self.nodes.len()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn set_root(&mut self, value: T) -> Result<NodeId, String> {
+        if self.root.is_some() {
+            return Err("Tree already has a root.".to_owned());
+        }
+        let root = self.add_node(value);
+        self.root = Some(root);
+        Ok(root)
+    }
+
+    pub fn insert_left(&mut self, parent: NodeId, value: T) -> Result<NodeId, String> {
+        self.check_node(parent)?;
+        if self.nodes[parent].left.is_some() {
+            return Err(format!("Node {} already has a left child.", parent));
+        }
+        let child = self.add_node(value);
+        self.nodes[parent].left = Some(child);
+        Ok(child)
+    }
+
+    pub fn insert_right(&mut self, parent: NodeId, value: T) -> Result<NodeId, String> {
+        self.check_node(parent)?;
+        if self.nodes[parent].right.is_some() {
+            return Err(format!("Node {} already has a right child.", parent));
+        }
+        let child = self.add_node(value);
+        self.nodes[parent].right = Some(child);
+        Ok(child)
+    }
+
+    pub fn get(&self, node: NodeId) -> Option<&T> {
+        self.nodes.get(node).map(|n| &n.value)
+    }
+
+    pub fn get_mut(&mut self, node: NodeId) -> Option<&mut T> {
+        self.nodes.get_mut(node).map(|n| &mut n.value)
+    }
+
+    pub fn get_left(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes.get(node)?.left
+    }
+
+    pub fn get_right(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes.get(node)?.right
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    // 按层序给出的序列建树，None 表示空位，空位的孩子不再出现在序列里
+    pub fn from_level_order<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = Option<T>>,
+    {
+        let mut tree = BinaryTree::new();
+        let mut values = values.into_iter();
+        let root = match values.next().flatten() {
+            None => return tree,
+            Some(value) => tree.add_node(value),
+        };
+        tree.root = Some(root);
+        let mut queue = ArrayQueue::new();
+        queue.enqueue(root);
+        while let Some(parent) = queue.dequeue() {
+            match values.next() {
+                None => break,
+                Some(None) => {}
+                Some(Some(value)) => {
+                    let child = tree.add_node(value);
+                    tree.nodes[parent].left = Some(child);
+                    queue.enqueue(child);
+                }
+            }
+            match values.next() {
+                None => break,
+                Some(None) => {}
+                Some(Some(value)) => {
+                    let child = tree.add_node(value);
+                    tree.nodes[parent].right = Some(child);
+                    queue.enqueue(child);
+                }
+            }
+        }
+        tree
+    }
+
+    // 层序序列，去掉末尾多余的 None，和 from_level_order 互逆
+    pub fn to_level_order(&self) -> Vec<Option<&T>> {
+        let mut out = Vec::new();
+        let mut queue = ArrayQueue::new();
+        queue.enqueue(self.root);
+        while let Some(slot) = queue.dequeue() {
+            match slot {
+                None => out.push(None),
+                Some(node) => {
+                    out.push(Some(&self.nodes[node].value));
+                    queue.enqueue(self.nodes[node].left);
+                    queue.enqueue(self.nodes[node].right);
+                }
+            }
+        }
+        while let Some(None) = out.last() {
+            out.pop();
+        }
+        out
+    }
+
+    pub fn preorder(&self) -> Preorder<'_, T> {
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root);
+        Preorder { tree: self, stack }
+    }
+
+    pub fn inorder(&self) -> Inorder<'_, T> {
+        Inorder {
+            tree: self,
+            stack: ArrayStack::new(),
+            current: self.root,
+        }
+    }
+
+    pub fn postorder(&self) -> Postorder<'_, T> {
+        Postorder {
+            tree: self,
+            stack: ArrayStack::new(),
+            current: self.root,
+            last: None,
+        }
+    }
+
+    pub fn level_order(&self) -> LevelOrder<'_, T> {
+        let mut queue = ArrayQueue::new();
+        queue.extend(self.root);
+        LevelOrder { tree: self, queue }
+    }
+
+    pub fn preorder_recursive(&self) -> Vec<&T> {
+        let mut out = Vec::with_capacity(self.nodes.len());
+        self.preorder_from(self.root, &mut out);
+        out
+    }
+
+    fn preorder_from<'a>(&'a self, node: Option<NodeId>, out: &mut Vec<&'a T>) {
+        if let Some(node) = node {
+            out.push(&self.nodes[node].value);
+            self.preorder_from(self.nodes[node].left, out);
+            self.preorder_from(self.nodes[node].right, out);
+        }
+    }
+
+    pub fn inorder_recursive(&self) -> Vec<&T> {
+        let mut out = Vec::with_capacity(self.nodes.len());
+        self.inorder_from(self.root, &mut out);
+        out
+    }
+
+    fn inorder_from<'a>(&'a self, node: Option<NodeId>, out: &mut Vec<&'a T>) {
+        if let Some(node) = node {
+            self.inorder_from(self.nodes[node].left, out);
+            out.push(&self.nodes[node].value);
+            self.inorder_from(self.nodes[node].right, out);
+        }
+    }
+
+    pub fn postorder_recursive(&self) -> Vec<&T> {
+        let mut out = Vec::with_capacity(self.nodes.len());
+        self.postorder_from(self.root, &mut out);
+        out
+    }
+
+    fn postorder_from<'a>(&'a self, node: Option<NodeId>, out: &mut Vec<&'a T>) {
+        if let Some(node) = node {
+            self.postorder_from(self.nodes[node].left, out);
+            self.postorder_from(self.nodes[node].right, out);
+            out.push(&self.nodes[node].value);
+        }
+    }
+
+    // Morris 中序遍历：把左子树最右节点的右指针临时指回当前节点代替栈，
+    // 第二次走到这里时再恢复，额外空间 O(1)
+    pub fn morris_inorder<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&T),
+    {
+        let mut current = self.root;
+        while let Some(node) = current {
+            let left = match self.nodes[node].left {
+                None => {
+                    visit(&self.nodes[node].value);
+                    current = self.nodes[node].right;
+                    continue;
+                }
+                Some(left) => left,
+            };
+            let mut predecessor = left;
+            while let Some(right) = self.nodes[predecessor].right
+                && right != node
+            {
+                predecessor = right;
+            }
+            if self.nodes[predecessor].right.is_none() {
+                self.nodes[predecessor].right = Some(node);
+                current = Some(left);
+            } else {
+                self.nodes[predecessor].right = None;
+                visit(&self.nodes[node].value);
+                current = self.nodes[node].right;
+            }
+        }
+    }
+
+    // 按后序顺序算出每个节点的高度，避免退化成链时递归过深
+    fn heights(&self) -> Vec<usize> {
+        let mut heights = vec![0; self.nodes.len()];
+        let mut order = self.postorder();
+        while let Some(node) = order.next_node() {
+            let left = self.nodes[node].left.map_or(0, |l| heights[l]);
+            let right = self.nodes[node].right.map_or(0, |r| heights[r]);
+            heights[node] = left.max(right) + 1;
+        }
+        heights
+    }
+
+    // 空树高度为 0，只有根节点时为 1
+    pub fn height(&self) -> usize {
+        self.root.map_or(0, |root| self.heights()[root])
+    }
+
+    // 任意两个节点之间最长路径上的边数
+    pub fn diameter(&self) -> usize {
+        let heights = self.heights();
+        (0..self.nodes.len())
+            .map(|node| {
+                self.nodes[node].left.map_or(0, |l| heights[l])
+                    + self.nodes[node].right.map_or(0, |r| heights[r])
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    // 每个节点左右子树的高度差都不超过 1
+    pub fn is_balanced(&self) -> bool {
+        let heights = self.heights();
+        self.nodes.iter().all(|node| {
+            let left = node.left.map_or(0, |l| heights[l]);
+            let right = node.right.map_or(0, |r| heights[r]);
+            left.abs_diff(right) <= 1
+        })
+    }
+
+    // 层序遍历中一旦遇到空位，后面就不能再有节点
+    pub fn is_complete(&self) -> bool {
+        let mut queue = ArrayQueue::new();
+        queue.enqueue(self.root);
+        let mut seen_gap = false;
+        while let Some(slot) = queue.dequeue() {
+            match slot {
+                None => seen_gap = true,
+                Some(node) => {
+                    if seen_gap {
+                        return false;
+                    }
+                    queue.enqueue(self.nodes[node].left);
+                    queue.enqueue(self.nodes[node].right);
+                }
+            }
+        }
+        true
+    }
+
+    // 交换每个节点的左右孩子，与访问顺序无关，直接扫一遍节点数组
+    pub fn mirror(&mut self) {
+        for node in self.nodes.iter_mut() {
+            std::mem::swap(&mut node.left, &mut node.right);
+        }
+    }
+}
+
+impl<T> BinaryTree<T>
+where
+    T: Clone + Eq + Hash,
+{
+    // 由前序和中序序列还原二叉树，要求元素互不相同。
+    // 沿前序依次建节点：栈顶等于当前中序元素时说明左子树已经结束，弹出的最后一个节点就是新节点的父节点
+    pub fn from_preorder_inorder(preorder: &[T], inorder: &[T]) -> Result<Self, String> {
+        if preorder.len() != inorder.len() {
+            return Err(format!(
+                "Preorder and inorder lengths differ: {} and {}.",
+                preorder.len(),
+                inorder.len()
+            ));
+        }
+        let mut seen = HashSet::new();
+        for value in preorder.iter() {
+            if !seen.insert(value.clone()) {
+                return Err("Values must be distinct.".to_owned());
+            }
+        }
+        let mut tree = BinaryTree::new();
+        let (first, rest) = match preorder.split_first() {
+            None => return Ok(tree),
+            Some(split) => split,
+        };
+        let root = tree.add_node(first.clone());
+        tree.root = Some(root);
+        let mut stack = ArrayStack::new();
+        stack.push(root);
+        let mut index = 0;
+        for value in rest {
+            let mut parent = None;
+            while let Some(&top) = stack.peek()
+                && index < inorder.len()
+                && tree.nodes[top].value == inorder[index]
+            {
+                parent = stack.pop();
+                index += 1;
+            }
+            let child = tree.add_node(value.clone());
+            match parent {
+                Some(parent) => tree.nodes[parent].right = Some(child),
+                None => {
+                    let top = *stack.peek().unwrap();
+                    tree.nodes[top].left = Some(child);
+                }
+            }
+            stack.push(child);
+        }
+        // 序列互相矛盾时建出来的树中序对不上
+        if !tree.inorder().eq(inorder.iter()) {
+            return Err("Preorder and inorder sequences are inconsistent.".to_owned());
+        }
+        Ok(tree)
+    }
+}
+
+impl<T> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for BinaryTree<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.to_level_order()).finish()
+    }
+}
+
+pub struct Preorder<'a, T> {
+    tree: &'a BinaryTree<T>,
+    stack: ArrayStack<NodeId>,
+}
+
+impl<'a, T> Iterator for Preorder<'a, T> {
+    type Item = &'a T;
+
+    // 右孩子先入栈，左孩子先出栈
+    fn next(&mut self) -> Option<&'a T> {
+        let node = &self.tree.nodes[self.stack.pop()?];
+        self.stack.extend(node.right);
+        self.stack.extend(node.left);
+        Some(&node.value)
+    }
+}
+
+pub struct Inorder<'a, T> {
+    tree: &'a BinaryTree<T>,
+    stack: ArrayStack<NodeId>,
+    current: Option<NodeId>,
+}
+
+impl<'a, T> Iterator for Inorder<'a, T> {
+    type Item = &'a T;
+
+    // 一路向左压栈，弹出时访问，再转向右子树
+    fn next(&mut self) -> Option<&'a T> {
+        while let Some(node) = self.current {
+            self.stack.push(node);
+            self.current = self.tree.nodes[node].left;
+        }
+        let node = self.stack.pop()?;
+        self.current = self.tree.nodes[node].right;
+        Some(&self.tree.nodes[node].value)
+    }
+}
+
+pub struct Postorder<'a, T> {
+    tree: &'a BinaryTree<T>,
+    stack: ArrayStack<NodeId>,
+    current: Option<NodeId>,
+    // 上一个访问的节点，用来判断右子树是否已经处理完
+    last: Option<NodeId>,
+}
+
+impl<'a, T> Postorder<'a, T> {
+    fn next_node(&mut self) -> Option<NodeId> {
+        loop {
+            while let Some(node) = self.current {
+                self.stack.push(node);
+                self.current = self.tree.nodes[node].left;
+            }
+            let top = *self.stack.peek()?;
+            match self.tree.nodes[top].right {
+                Some(right) if self.last != Some(right) => self.current = Some(right),
+                _ => {
+                    self.stack.pop();
+                    self.last = Some(top);
+                    return Some(top);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for Postorder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next_node()?;
+        Some(&self.tree.nodes[node].value)
+    }
+}
+
+pub struct LevelOrder<'a, T> {
+    tree: &'a BinaryTree<T>,
+    queue: ArrayQueue<NodeId>,
+}
+
+impl<'a, T> Iterator for LevelOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = &self.tree.nodes[self.queue.dequeue()?];
+        self.queue.extend(node.left);
+        self.queue.extend(node.right);
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    // 每次挑一个还有空位的节点随机挂上左孩子或右孩子，节点的值就是它的编号
+    fn random_tree(random: &mut TestRng, size: usize) -> BinaryTree<usize> {
+        let mut tree = BinaryTree::new();
+        if size == 0 {
+            return tree;
+        }
+        tree.set_root(0).unwrap();
+        let mut open = vec![0];
+        while tree.get_size() < size {
+            let index = random.index(open.len());
+            let parent = open[index];
+            let value = tree.get_size();
+            let child = match (tree.get_left(parent), tree.get_right(parent)) {
+                (None, None) if random.next_u64().is_multiple_of(2) => {
+                    tree.insert_left(parent, value)
+                }
+                (None, _) => tree.insert_left(parent, value),
+                _ => tree.insert_right(parent, value),
+            }
+            .unwrap();
+            if tree.get_left(parent).is_some() && tree.get_right(parent).is_some() {
+                open.swap_remove(index);
+            }
+            open.push(child);
+        }
+        tree
+    }
+
+    fn links(tree: &BinaryTree<usize>) -> Vec<(Option<NodeId>, Option<NodeId>)> {
+        (0..tree.get_size())
+            .map(|node| (tree.get_left(node), tree.get_right(node)))
+            .collect()
+    }
+
+    fn naive_height(tree: &BinaryTree<usize>, node: Option<NodeId>) -> usize {
+        node.map_or(0, |node| {
+            1 + naive_height(tree, tree.get_left(node))
+                .max(naive_height(tree, tree.get_right(node)))
+        })
+    }
+
+    fn naive_is_balanced(tree: &BinaryTree<usize>, node: Option<NodeId>) -> bool {
+        node.is_none_or(|node| {
+            let (left, right) = (tree.get_left(node), tree.get_right(node));
+            naive_height(tree, left).abs_diff(naive_height(tree, right)) <= 1
+                && naive_is_balanced(tree, left)
+                && naive_is_balanced(tree, right)
+        })
+    }
+
+    // 所有节点对之间距离的最大值，距离用到根的路径求出
+    fn naive_diameter(tree: &BinaryTree<usize>) -> usize {
+        let n = tree.get_size();
+        let mut parent = vec![None; n];
+        for node in 0..n {
+            for child in [tree.get_left(node), tree.get_right(node)]
+                .into_iter()
+                .flatten()
+            {
+                parent[child] = Some(node);
+            }
+        }
+        let path = |mut node: NodeId| {
+            let mut path = vec![node];
+            while let Some(p) = parent[node] {
+                path.push(p);
+                node = p;
+            }
+            path
+        };
+        let mut best = 0;
+        for a in 0..n {
+            for b in 0..n {
+                let (pa, pb) = (path(a), path(b));
+                let common = pa
+                    .iter()
+                    .rev()
+                    .zip(pb.iter().rev())
+                    .take_while(|(x, y)| x == y)
+                    .count();
+                best = best.max(pa.len() + pb.len() - 2 * common);
+            }
+        }
+        best
+    }
+
+    // 按堆的编号规则（根为 0，孩子为 2i+1 和 2i+2），完全二叉树的编号恰好是 0..n
+    fn naive_is_complete(tree: &BinaryTree<usize>) -> bool {
+        let mut indices = Vec::new();
+        let mut stack = Vec::from_iter(tree.get_root().map(|root| (root, 0u128)));
+        while let Some((node, index)) = stack.pop() {
+            indices.push(index);
+            stack.extend(tree.get_left(node).map(|l| (l, 2 * index + 1)));
+            stack.extend(tree.get_right(node).map(|r| (r, 2 * index + 2)));
+        }
+        indices.sort_unstable();
+        indices
+            .iter()
+            .enumerate()
+            .all(|(i, &index)| i as u128 == index)
+    }
+
+    #[test]
+    fn random_trees_match_brute_force() {
+        let mut random = TestRng::with_seed(39);
+        for round in 0..300 {
+            let size = random.index(25);
+            // 一部分轮次用层序建满的完全二叉树，保证 is_complete 两种结果都覆盖到
+            let mut tree = if round % 5 == 0 {
+                BinaryTree::from_level_order((0..size).map(Some))
+            } else {
+                random_tree(&mut random, size)
+            };
+
+            let preorder: Vec<usize> = tree.preorder().copied().collect();
+            let inorder: Vec<usize> = tree.inorder().copied().collect();
+            let postorder: Vec<usize> = tree.postorder().copied().collect();
+            assert_eq!(
+                preorder,
+                tree.preorder_recursive()
+                    .into_iter()
+                    .copied()
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                inorder,
+                tree.inorder_recursive()
+                    .into_iter()
+                    .copied()
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                postorder,
+                tree.postorder_recursive()
+                    .into_iter()
+                    .copied()
+                    .collect::<Vec<_>>()
+            );
+            let level: Vec<usize> = tree.level_order().copied().collect();
+            let expected_level: Vec<usize> = tree
+                .to_level_order()
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect();
+            assert_eq!(level, expected_level);
+
+            // Morris 遍历结束后所有临时改写的右指针都要恢复
+            let before = links(&tree);
+            let mut morris = Vec::new();
+            tree.morris_inorder(|&value| morris.push(value));
+            assert_eq!(morris, inorder);
+            assert_eq!(links(&tree), before);
+
+            assert_eq!(tree.height(), naive_height(&tree, tree.get_root()));
+            assert_eq!(tree.diameter(), naive_diameter(&tree));
+            assert_eq!(
+                tree.is_balanced(),
+                naive_is_balanced(&tree, tree.get_root())
+            );
+            assert_eq!(tree.is_complete(), naive_is_complete(&tree));
+            if round % 5 == 0 {
+                assert!(tree.is_complete());
+            }
+
+            let level_order: Vec<Option<usize>> = tree
+                .to_level_order()
+                .into_iter()
+                .map(|v| v.copied())
+                .collect();
+            let rebuilt = BinaryTree::from_level_order(level_order.clone());
+            assert_eq!(
+                rebuilt
+                    .to_level_order()
+                    .into_iter()
+                    .map(|v| v.copied())
+                    .collect::<Vec<_>>(),
+                level_order
+            );
+
+            let rebuilt = BinaryTree::from_preorder_inorder(&preorder, &inorder).unwrap();
+            assert_eq!(rebuilt.postorder().copied().collect::<Vec<_>>(), postorder);
+
+            tree.mirror();
+            let mirrored: Vec<usize> = tree.inorder().copied().collect();
+            assert_eq!(mirrored, inorder.iter().rev().copied().collect::<Vec<_>>());
+            tree.mirror();
+            assert_eq!(links(&tree), before);
+        }
+    }
+
+    #[test]
+    fn from_preorder_inorder_rejects_invalid_input() {
+        let tree = BinaryTree::from_preorder_inorder(&[1, 2, 3], &[2, 1, 3]).unwrap();
+        assert_eq!(tree.to_level_order(), vec![Some(&1), Some(&2), Some(&3)]);
+        assert!(
+            BinaryTree::<i32>::from_preorder_inorder(&[], &[])
+                .unwrap()
+                .is_empty()
+        );
+        // 长度不同
+        assert!(BinaryTree::from_preorder_inorder(&[1, 2], &[1]).is_err());
+        // 元素重复
+        assert!(BinaryTree::from_preorder_inorder(&[1, 1], &[1, 1]).is_err());
+        assert!(BinaryTree::from_preorder_inorder(&[1, 2, 1], &[2, 1, 3]).is_err());
+        // 元素集合不同
+        assert!(BinaryTree::from_preorder_inorder(&[1, 2], &[1, 3]).is_err());
+        // 元素相同但顺序矛盾：根 1 的左子树按中序只有 3，前序却先给出 2
+        assert!(BinaryTree::from_preorder_inorder(&[3, 1, 2], &[1, 2, 3]).is_ok());
+        assert!(BinaryTree::from_preorder_inorder(&[1, 2, 3], &[3, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn shape_queries_on_small_trees() {
+        let empty: BinaryTree<i32> = BinaryTree::new();
+        assert_eq!(empty.height(), 0);
+        assert_eq!(empty.diameter(), 0);
+        assert!(empty.is_complete());
+        assert!(empty.is_balanced());
+
+        // 缺了最后一层中间的节点：不完全
+        let tree = BinaryTree::from_level_order([Some(1), Some(2), Some(3), None, Some(5)]);
+        assert!(!tree.is_complete());
+        let tree = BinaryTree::from_level_order([Some(1), Some(2), Some(3), Some(4)]);
+        assert!(tree.is_complete());
+        assert_eq!(tree.diameter(), 3);
+
+        // 最长路径不经过根：左子树里两条深链
+        let mut tree = BinaryTree::new();
+        let root = tree.set_root(0).unwrap();
+        let hub = tree.insert_left(root, 1).unwrap();
+        let (mut left, mut right) = (hub, hub);
+        for depth in 0..4 {
+            left = tree.insert_left(left, 10 + depth).unwrap();
+            right = tree.insert_right(right, 20 + depth).unwrap();
+        }
+        assert_eq!(tree.diameter(), 8);
+        assert_eq!(tree.height(), 6);
+        assert!(!tree.is_balanced());
+    }
+
+    #[test]
+    fn deep_chain_does_not_overflow() {
+        let mut tree = BinaryTree::new();
+        let mut node = tree.set_root(0).unwrap();
+        for value in 1..200_000 {
+            node = tree.insert_right(node, value).unwrap();
+        }
+        assert_eq!(tree.height(), 200_000);
+        assert_eq!(tree.diameter(), 199_999);
+        assert!(!tree.is_balanced());
+        assert_eq!(tree.inorder().count(), 200_000);
+        assert_eq!(tree.postorder().next(), Some(&199_999));
+    }
+}
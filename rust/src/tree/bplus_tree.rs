@@ -0,0 +1,771 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    fmt::Debug,
+    ops::{Bound, RangeBounds},
+};
+
+use super::btree::{DEFAULT_MIN_DEGREE, NodeFillStats, check_min_degree, check_sorted, share};
+use crate::stack::array_stack::ArrayStack;
+
+type NodeId = usize;
+
+// 叶子存键值并用 next 串成链表；内部节点只存分隔键，
+// 分隔键 keys[i] 满足：children[i] 里的键都小于它，children[i+1] 里的键都不小于它
+struct Node<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    children: Vec<NodeId>,
+    next: Option<NodeId>,
+}
+
+impl<K, V> Node<K, V> {
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<K, V> Default for Node<K, V> {
+    fn default() -> Self {
+        Node {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+            next: None,
+        }
+    }
+}
+
+// B+ 树：数据都在叶子上，叶子按顺序链接，区间扫描找到起点后只沿链表走。
+// 节点放在数组里用下标互相引用，删除后空出的槽位留给之后的分配
+pub struct BPlusTree<K, V> {
+    nodes: Vec<Node<K, V>>,
+    free: Vec<NodeId>,
+    root: NodeId,
+    min_degree: usize,
+    size: usize,
+}
+
+impl<K, V> BPlusTree<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new() -> Self {
+        Self::with_min_degree(DEFAULT_MIN_DEGREE).unwrap()
+    }
+
+    pub fn with_min_degree(min_degree: usize) -> Result<Self, String> {
+        check_min_degree(min_degree)?;
+        Ok(BPlusTree {
+            nodes: vec![Node::default()],
+            free: Vec::new(),
+            root: 0,
+            min_degree,
+            size: 0,
+        })
+    }
+
+    // 先把数据平均装进最少的叶子并串起来，再逐层往上建内部节点，分隔键取右侧子树的最小键
+    pub fn from_sorted<I>(entries: I, min_degree: usize) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut tree = Self::with_min_degree(min_degree)?;
+        let entries: Vec<(K, V)> = entries.into_iter().collect();
+        check_sorted(&entries)?;
+        if entries.is_empty() {
+            return Ok(tree);
+        }
+        tree.nodes.clear();
+        let size = entries.len();
+        let leaf_count = size.div_ceil(tree.get_max_keys());
+        let mut entries = entries.into_iter();
+        // 每个节点和它子树里的最小键
+        let mut level: Vec<(NodeId, K)> = Vec::with_capacity(leaf_count);
+        for index in 0..leaf_count {
+            let mut leaf = Node::default();
+            for (key, value) in entries.by_ref().take(share(size, leaf_count, index)) {
+                leaf.keys.push(key);
+                leaf.values.push(value);
+            }
+            if index + 1 < leaf_count {
+                leaf.next = Some(tree.nodes.len() + 1);
+            }
+            let min = leaf.keys[0].clone();
+            level.push((tree.nodes.len(), min));
+            tree.nodes.push(leaf);
+        }
+        while level.len() > 1 {
+            let child_count = level.len();
+            let parent_count = child_count.div_ceil(2 * min_degree);
+            let mut children = level.into_iter();
+            level = Vec::with_capacity(parent_count);
+            for index in 0..parent_count {
+                let mut parent = Node::default();
+                let mut min = None;
+                for (child, key) in children
+                    .by_ref()
+                    .take(share(child_count, parent_count, index))
+                {
+                    parent.children.push(child);
+                    if min.is_none() {
+                        min = Some(key);
+                    } else {
+                        parent.keys.push(key);
+                    }
+                }
+                level.push((tree.nodes.len(), min.unwrap()));
+                tree.nodes.push(parent);
+            }
+        }
+        tree.root = level[0].0;
+        tree.size = size;
+        Ok(tree)
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_min_degree(&self) -> usize {
+        self.min_degree
+    }
+
+    pub fn get_max_keys(&self) -> usize {
+        2 * self.min_degree - 1
+    }
+
+    fn allocate(&mut self, node: Node<K, V>) -> NodeId {
+        match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, id: NodeId) {
+        self.nodes[id] = Node::default();
+        self.free.push(id);
+    }
+
+    // 等于分隔键的键在右侧子树里
+    fn child_index<Q>(node: &Node<K, V>, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node.keys.partition_point(|k| k.borrow() <= key)
+    }
+
+    fn find_leaf<Q>(&self, key: &Q) -> NodeId
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut id = self.root;
+        while !self.nodes[id].is_leaf() {
+            let node = &self.nodes[id];
+            id = node.children[Self::child_index(node, key)];
+        }
+        id
+    }
+
+    fn first_leaf(&self) -> NodeId {
+        let mut id = self.root;
+        while let Some(&child) = self.nodes[id].children.first() {
+            id = child;
+        }
+        id
+    }
+
+    fn last_leaf(&self) -> NodeId {
+        let mut id = self.root;
+        while let Some(&child) = self.nodes[id].children.last() {
+            id = child;
+        }
+        id
+    }
+
+    // 递归插入，节点溢出时分裂，返回被替换的旧值和需要插入父节点的 (分隔键, 新右兄弟)
+    fn insert_into(&mut self, id: NodeId, key: K, value: V) -> (Option<V>, Option<(K, NodeId)>) {
+        let max_keys = self.get_max_keys();
+        let min_degree = self.min_degree;
+        if self.nodes[id].is_leaf() {
+            let node = &mut self.nodes[id];
+            match node.keys.binary_search(&key) {
+                Ok(index) => {
+                    return (
+                        Some(std::mem::replace(&mut node.values[index], value)),
+                        None,
+                    );
+                }
+                Err(index) => {
+                    node.keys.insert(index, key);
+                    node.values.insert(index, value);
+                }
+            }
+            if node.keys.len() <= max_keys {
+                return (None, None);
+            }
+            let right = Node {
+                keys: node.keys.split_off(min_degree),
+                values: node.values.split_off(min_degree),
+                children: Vec::new(),
+                next: node.next,
+            };
+            let separator = right.keys[0].clone();
+            let right = self.allocate(right);
+            self.nodes[id].next = Some(right);
+            return (None, Some((separator, right)));
+        }
+
+        let index = Self::child_index(&self.nodes[id], &key);
+        let child = self.nodes[id].children[index];
+        let (old, split) = self.insert_into(child, key, value);
+        let Some((separator, right)) = split else {
+            return (old, None);
+        };
+        let node = &mut self.nodes[id];
+        node.keys.insert(index, separator);
+        node.children.insert(index + 1, right);
+        if node.keys.len() <= max_keys {
+            return (old, None);
+        }
+        // 内部节点分裂时中间的分隔键上移，不在两侧保留
+        let keys = node.keys.split_off(min_degree + 1);
+        let children = node.children.split_off(min_degree + 1);
+        let separator = node.keys.pop().unwrap();
+        let right = self.allocate(Node {
+            keys,
+            values: Vec::new(),
+            children,
+            next: None,
+        });
+        (old, Some((separator, right)))
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (old, split) = self.insert_into(self.root, key, value);
+        if let Some((separator, right)) = split {
+            let root = Node {
+                keys: vec![separator],
+                values: Vec::new(),
+                children: vec![self.root, right],
+                next: None,
+            };
+            self.root = self.allocate(root);
+        }
+        if old.is_none() {
+            self.size += 1;
+        }
+        old
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let leaf = &self.nodes[self.find_leaf(key)];
+        let index = leaf
+            .keys
+            .binary_search_by(|probe| probe.borrow().cmp(key))
+            .ok()?;
+        Some((&leaf.keys[index], &leaf.values[index]))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let id = self.find_leaf(key);
+        let leaf = &mut self.nodes[id];
+        let index = leaf
+            .keys
+            .binary_search_by(|probe| probe.borrow().cmp(key))
+            .ok()?;
+        Some(&mut leaf.values[index])
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    // 孩子 index 的键数低于下限：先向左右兄弟借一个，借不到就和兄弟合并
+    fn rebalance_child(&mut self, parent: NodeId, index: usize) {
+        let min_keys = self.min_degree - 1;
+        let child = self.nodes[parent].children[index];
+        if self.nodes[child].keys.len() >= min_keys {
+            return;
+        }
+        let sibling_count = self.nodes[parent].children.len();
+        let left = (index > 0).then(|| self.nodes[parent].children[index - 1]);
+        let right = (index + 1 < sibling_count).then(|| self.nodes[parent].children[index + 1]);
+        let is_leaf = self.nodes[child].is_leaf();
+
+        if let Some(left) = left
+            && self.nodes[left].keys.len() > min_keys
+        {
+            let mut node = std::mem::take(&mut self.nodes[child]);
+            let sibling = &mut self.nodes[left];
+            if is_leaf {
+                node.keys.insert(0, sibling.keys.pop().unwrap());
+                node.values.insert(0, sibling.values.pop().unwrap());
+                self.nodes[parent].keys[index - 1] = node.keys[0].clone();
+            } else {
+                let key = sibling.keys.pop().unwrap();
+                node.children.insert(0, sibling.children.pop().unwrap());
+                let separator = std::mem::replace(&mut self.nodes[parent].keys[index - 1], key);
+                node.keys.insert(0, separator);
+            }
+            self.nodes[child] = node;
+            return;
+        }
+        if let Some(right) = right
+            && self.nodes[right].keys.len() > min_keys
+        {
+            let mut node = std::mem::take(&mut self.nodes[child]);
+            let sibling = &mut self.nodes[right];
+            if is_leaf {
+                node.keys.push(sibling.keys.remove(0));
+                node.values.push(sibling.values.remove(0));
+                self.nodes[parent].keys[index] = self.nodes[right].keys[0].clone();
+            } else {
+                let key = sibling.keys.remove(0);
+                node.children.push(sibling.children.remove(0));
+                let separator = std::mem::replace(&mut self.nodes[parent].keys[index], key);
+                node.keys.push(separator);
+            }
+            self.nodes[child] = node;
+            return;
+        }
+
+        // 把右边的节点并进左边的节点，叶子合并时分隔键直接丢掉
+        let (left_index, left, right) = match left {
+            Some(left) => (index - 1, left, child),
+            None => (index, child, right.unwrap()),
+        };
+        let separator = self.nodes[parent].keys.remove(left_index);
+        self.nodes[parent].children.remove(left_index + 1);
+        let right_node = std::mem::take(&mut self.nodes[right]);
+        let node = &mut self.nodes[left];
+        if is_leaf {
+            node.next = right_node.next;
+        } else {
+            node.keys.push(separator);
+        }
+        node.keys.extend(right_node.keys);
+        node.values.extend(right_node.values);
+        node.children.extend(right_node.children);
+        self.release(right);
+    }
+
+    fn remove_from<Q>(&mut self, id: NodeId, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let node = &mut self.nodes[id];
+        if node.is_leaf() {
+            let index = node
+                .keys
+                .binary_search_by(|probe| probe.borrow().cmp(key))
+                .ok()?;
+            return Some((node.keys.remove(index), node.values.remove(index)));
+        }
+        let index = Self::child_index(node, key);
+        let child = node.children[index];
+        let removed = self.remove_from(child, key)?;
+        self.rebalance_child(id, index);
+        Some(removed)
+    }
+
+    fn shrink_root(&mut self) {
+        let root = &self.nodes[self.root];
+        if root.keys.is_empty() && !root.is_leaf() {
+            let child = root.children[0];
+            self.release(self.root);
+            self.root = child;
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed = self.remove_from(self.root, key)?;
+        self.shrink_root();
+        self.size -= 1;
+        Some(removed)
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let leaf = &self.nodes[self.first_leaf()];
+        Some((leaf.keys.first()?, leaf.values.first()?))
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let leaf = &self.nodes[self.last_leaf()];
+        Some((leaf.keys.last()?, leaf.values.last()?))
+    }
+
+    // 位置落在叶子末尾时挪到下一个叶子的开头，到链表尽头返回 None
+    fn normalize(&self, mut leaf: NodeId, mut index: usize) -> Option<(NodeId, usize)> {
+        while index >= self.nodes[leaf].keys.len() {
+            leaf = self.nodes[leaf].next?;
+            index = 0;
+        }
+        Some((leaf, index))
+    }
+
+    fn lower_bound<Q>(&self, key: &Q, inclusive: bool) -> Option<(NodeId, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let leaf = self.find_leaf(key);
+        let keys = &self.nodes[leaf].keys;
+        let index = if inclusive {
+            keys.partition_point(|k| k.borrow() < key)
+        } else {
+            keys.partition_point(|k| k.borrow() <= key)
+        };
+        self.normalize(leaf, index)
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            tree: self,
+            position: self.normalize(self.first_leaf(), 0),
+            stop: None,
+        }
+    }
+
+    // 定位起点和第一个超出上界的位置后，沿叶子链表扫描
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Unbounded => self.normalize(self.first_leaf(), 0),
+            Bound::Included(start) => self.lower_bound(start, true),
+            Bound::Excluded(start) => self.lower_bound(start, false),
+        };
+        let stop = match range.end_bound() {
+            Bound::Unbounded => None,
+            Bound::Included(end) => self.lower_bound(end, false),
+            Bound::Excluded(end) => self.lower_bound(end, true),
+        };
+        // 下界在上界之后时区间为空
+        let position = match (start, stop) {
+            (Some(start), Some(stop)) if self.key_at(start) > self.key_at(stop) => Some(stop),
+            _ => start,
+        };
+        Iter {
+            tree: self,
+            position,
+            stop,
+        }
+    }
+
+    fn key_at(&self, (leaf, index): (NodeId, usize)) -> &K {
+        &self.nodes[leaf].keys[index]
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn height(&self) -> usize {
+        if self.size == 0 {
+            return 0;
+        }
+        let mut height = 1;
+        let mut id = self.root;
+        while let Some(&child) = self.nodes[id].children.first() {
+            id = child;
+            height += 1;
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.nodes.push(Node::default());
+        self.free.clear();
+        self.root = 0;
+        self.size = 0;
+    }
+
+    pub fn fill_stats(&self) -> NodeFillStats {
+        let mut stats = NodeFillStats {
+            height: self.height(),
+            node_count: 0,
+            leaf_count: 0,
+            key_count: 0,
+            min_keys: usize::MAX,
+            max_keys: 0,
+            fill_ratio: 0.0,
+        };
+        let mut stack = ArrayStack::new();
+        stack.push(self.root);
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id];
+            stats.node_count += 1;
+            stats.leaf_count += usize::from(node.is_leaf());
+            stats.key_count += node.keys.len();
+            stats.min_keys = stats.min_keys.min(node.keys.len());
+            stats.max_keys = stats.max_keys.max(node.keys.len());
+            stack.extend(node.children.iter().copied());
+        }
+        stats.fill_ratio = stats.key_count as f64 / (stats.node_count * self.get_max_keys()) as f64;
+        stats
+    }
+
+    // 检查键数、孩子数、叶子同深和分隔键的范围，再沿叶子链表核对顺序和总数
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let min_keys = self.min_degree - 1;
+        let max_keys = self.get_max_keys();
+        let mut leaf_depth = None;
+        let mut leaves = 0;
+        // (节点, 深度, 下界, 上界)：子树里的键都在 [下界, 上界) 内
+        let mut stack = ArrayStack::new();
+        stack.push((self.root, 1, None, None));
+        while let Some((id, depth, lower, upper)) = stack.pop() {
+            let node = &self.nodes[id];
+            if node.keys.len() > max_keys || (id != self.root && node.keys.len() < min_keys) {
+                return Err(format!(
+                    "Node at depth {} has {} keys. Valid range: {} to {}.",
+                    depth,
+                    node.keys.len(),
+                    min_keys,
+                    max_keys
+                ));
+            }
+            if node.keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err("Keys within a node are not strictly increasing.".to_owned());
+            }
+            let in_bounds = |key: &K| {
+                lower.is_none_or(|lower: &K| lower <= key)
+                    && upper.is_none_or(|upper: &K| key < upper)
+            };
+            if !node.keys.iter().all(in_bounds) {
+                return Err("Key outside the range given by its separators.".to_owned());
+            }
+            if node.is_leaf() {
+                leaves += 1;
+                if node.values.len() != node.keys.len() {
+                    return Err("Key and value counts differ.".to_owned());
+                }
+                if *leaf_depth.get_or_insert(depth) != depth {
+                    return Err("Leaves are not all at the same depth.".to_owned());
+                }
+                continue;
+            }
+            if node.children.len() != node.keys.len() + 1 {
+                return Err(format!(
+                    "Internal node has {} keys but {} children.",
+                    node.keys.len(),
+                    node.children.len()
+                ));
+            }
+            for (index, &child) in node.children.iter().enumerate() {
+                let child_lower = if index == 0 {
+                    lower
+                } else {
+                    Some(&node.keys[index - 1])
+                };
+                let child_upper = node.keys.get(index).or(upper);
+                stack.push((child, depth + 1, child_lower, child_upper));
+            }
+        }
+
+        let mut count = 0;
+        let mut chained = 0;
+        let mut previous: Option<&K> = None;
+        let mut leaf = Some(self.first_leaf());
+        while let Some(id) = leaf {
+            chained += 1;
+            for key in self.nodes[id].keys.iter() {
+                if previous.is_some_and(|previous| previous >= key) {
+                    return Err(format!(
+                        "Keys are not strictly increasing along the leaf chain at position {}.",
+                        count
+                    ));
+                }
+                previous = Some(key);
+                count += 1;
+            }
+            leaf = self.nodes[id].next;
+        }
+        if chained != leaves {
+            return Err(format!(
+                "Leaf chain links {} leaves, tree has {}.",
+                chained, leaves
+            ));
+        }
+        if count != self.size {
+            return Err(format!(
+                "Size mismatch: recorded {}, counted {}.",
+                self.size, count
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    tree: &'a BPlusTree<K, V>,
+    position: Option<(NodeId, usize)>,
+    stop: Option<(NodeId, usize)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + Clone,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (leaf, index) = self.position?;
+        if self.stop == self.position {
+            self.position = None;
+            return None;
+        }
+        self.position = self.tree.normalize(leaf, index + 1);
+        let node = &self.tree.nodes[leaf];
+        Some((&node.keys[index], &node.values[index]))
+    }
+}
+
+impl<K, V> Default for BPlusTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for BPlusTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BPlusTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K, V> Extend<(K, V)> for BPlusTree<K, V>
+where
+    K: Ord + Clone,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BPlusTree<K, V>
+where
+    K: Ord + Clone,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for BPlusTree<K, V>
+where
+    K: Ord + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::BPlusTree;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        for min_degree in [2, 3, 8] {
+            let mut random = TestRng::with_seed(44);
+            let mut map = BPlusTree::with_min_degree(min_degree).unwrap();
+            let mut expected = BTreeMap::new();
+            for _ in 0..2000 {
+                let key = random.below(200);
+                if random.next_u64().is_multiple_of(3) {
+                    assert_eq!(map.remove(&key), expected.remove(&key));
+                } else {
+                    assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2));
+                }
+                map.check_invariants().unwrap();
+                assert_eq!(map.get_size(), expected.len());
+            }
+            assert!(map.iter().eq(expected.iter()));
+            assert!(map.range(50..150).eq(expected.range(50..150)));
+            assert!(map.range(..=80).eq(expected.range(..=80)));
+        }
+    }
+
+    #[test]
+    fn from_sorted_and_inverted_range() {
+        let map = BPlusTree::from_sorted((0..100).map(|key| (key, key)), 2).unwrap();
+        map.check_invariants().unwrap();
+        assert!(map.keys().copied().eq(0..100));
+        let (start, end) = (50, 30);
+        assert_eq!(map.range(start..=end).count(), 0);
+        assert_eq!(map.range(end..end).count(), 0);
+        assert!(map.range(end..=end).eq([(&30, &30)]));
+    }
+}
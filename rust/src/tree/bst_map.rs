@@ -0,0 +1,402 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, cmp::Ordering, fmt::Debug, ops::RangeBounds};
+
+use super::search_tree::{self, IterMut, Range, SearchNode, SplitMut};
+use crate::stack::array_stack::ArrayStack;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+pub struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            left: None,
+            right: None,
+        })
+    }
+}
+
+impl<K, V> SearchNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.key,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+// 不做平衡的二叉搜索树，所有操作都写成循环，退化成链表时也不会爆栈
+pub struct BstMap<K, V> {
+    root: Link<K, V>,
+    size: usize,
+}
+
+impl<K, V> BstMap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        BstMap {
+            root: None,
+            size: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            link = match key.cmp(&node.key) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return Some(std::mem::replace(&mut node.value, value)),
+            };
+        }
+        *link = Some(Node::new(key, value));
+        self.size += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| (&node.key, &node.value))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    // 两个孩子都在时，用右子树的最小节点（后继）顶替被删除的节点
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = &mut self.root;
+        while link
+            .as_ref()
+            .is_some_and(|node| key.cmp(node.key.borrow()) != Ordering::Equal)
+        {
+            let node = link.as_mut().unwrap();
+            link = if key < node.key.borrow() {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        let mut node = link.take()?;
+        *link = match (node.left.take(), node.right.take()) {
+            (None, right) => right,
+            (left, None) => left,
+            (left, mut right) => {
+                let mut successor = Self::take_min(&mut right);
+                successor.left = left;
+                successor.right = right;
+                Some(successor)
+            }
+        };
+        self.size -= 1;
+        Some((node.key, node.value))
+    }
+
+    // 摘下子树里的最小节点，它的右子树接回原来的位置
+    fn take_min(mut link: &mut Link<K, V>) -> Box<Node<K, V>> {
+        while link.as_ref().unwrap().left.is_some() {
+            link = &mut link.as_mut().unwrap().left;
+        }
+        let mut node = link.take().unwrap();
+        *link = node.right.take();
+        node
+    }
+
+    fn take_max(mut link: &mut Link<K, V>) -> Box<Node<K, V>> {
+        while link.as_ref().unwrap().right.is_some() {
+            link = &mut link.as_mut().unwrap().right;
+        }
+        let mut node = link.take().unwrap();
+        *link = node.left.take();
+        node
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        let node = Self::take_min(&mut self.root);
+        self.size -= 1;
+        Some((node.key, node.value))
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        let node = Self::take_max(&mut self.root);
+        self.size -= 1;
+        Some((node.key, node.value))
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    // 不超过 key 的最大键
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    // 不小于 key 的最小键
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    // 严格小于 key 的最大键，key 本身不必在树里
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn successor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn iter(&self) -> Range<'_, Node<K, V>> {
+        search_tree::iter(self.root.as_deref())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        search_tree::range(self.root.as_deref(), range)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Node<K, V>> {
+        search_tree::iter_mut(self.root.as_deref_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    pub fn height(&self) -> usize {
+        search_tree::height(self.root.as_deref())
+    }
+
+    pub fn clear(&mut self) {
+        drop_links(self.root.take());
+        self.size = 0;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        search_tree::check_order(self.root.as_deref(), self.size)
+    }
+}
+
+// 逐个节点释放，避免退化的长链在递归 drop 时爆栈
+fn drop_links<K, V>(root: Link<K, V>) {
+    let mut stack = ArrayStack::new();
+    stack.extend(root);
+    while let Some(mut node) = stack.pop() {
+        stack.extend(node.left.take());
+        stack.extend(node.right.take());
+    }
+}
+
+impl<K, V> Drop for BstMap<K, V> {
+    fn drop(&mut self) {
+        drop_links(self.root.take());
+    }
+}
+
+impl<K, V> Default for BstMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for BstMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = BstMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for BstMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BstMap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, Node<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for BstMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::BstMap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(40);
+        let mut map = BstMap::new();
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            if random.next_u64().is_multiple_of(3) {
+                assert_eq!(map.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2));
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+        assert!(map.range(..=80).eq(expected.range(..=80)));
+    }
+
+    #[test]
+    fn inverted_range_is_empty() {
+        let map: BstMap<i32, i32> = (0..10).map(|key| (key, key)).collect();
+        let (start, end) = (5, 3);
+        assert_eq!(map.range(start..=end).count(), 0);
+        assert_eq!(map.range(start..end).count(), 0);
+        assert_eq!(map.range(end..end).count(), 0);
+        assert!(map.range(end..=end).eq([(&3, &3)]));
+    }
+}
@@ -0,0 +1,720 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::stack::array_stack::ArrayStack;
+
+pub const DEFAULT_MIN_DEGREE: usize = 6;
+
+// 节点填充情况，fill_ratio 是实际键数占所有节点最大容量的比例
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeFillStats {
+    pub height: usize,
+    pub node_count: usize,
+    pub leaf_count: usize,
+    pub key_count: usize,
+    pub min_keys: usize,
+    pub max_keys: usize,
+    pub fill_ratio: f64,
+}
+
+pub(super) fn check_min_degree(min_degree: usize) -> Result<(), String> {
+    if min_degree < 2 {
+        return Err(format!(
+            "Minimum degree must be at least 2. Requested: {}",
+            min_degree
+        ));
+    }
+    Ok(())
+}
+
+// 批量建树前检查输入严格递增
+pub(super) fn check_sorted<K: Ord, V>(entries: &[(K, V)]) -> Result<(), String> {
+    match entries.windows(2).position(|pair| pair[0].0 >= pair[1].0) {
+        Some(position) => Err(format!(
+            "Input is not strictly sorted at position {}.",
+            position + 1
+        )),
+        None => Ok(()),
+    }
+}
+
+// 把 total 个元素尽量平均地分成 parts 份，返回第 index 份的个数
+pub(super) fn share(total: usize, parts: usize, index: usize) -> usize {
+    total / parts + usize::from(index < total % parts)
+}
+
+struct Node<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    // 叶子没有孩子，内部节点的孩子数比键数多 1
+    children: Vec<Node<K, V>>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new() -> Self {
+        Node {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+// 《算法导论》里的 B 树：最小度数为 t 时，除根以外每个节点有 t-1 到 2t-1 个键，
+// 插入时自顶向下提前分裂满节点，删除时自顶向下保证要进入的孩子至少有 t 个键，都只需一趟
+pub struct BTree<K, V> {
+    root: Node<K, V>,
+    min_degree: usize,
+    size: usize,
+}
+
+impl<K, V> BTree<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Self::with_min_degree(DEFAULT_MIN_DEGREE).unwrap()
+    }
+
+    pub fn with_min_degree(min_degree: usize) -> Result<Self, String> {
+        check_min_degree(min_degree)?;
+        Ok(BTree {
+            root: Node::new(),
+            min_degree,
+            size: 0,
+        })
+    }
+
+    // 自底向上逐层建树，每层用最少的节点并把键平均分配，除根以外的节点都不会少于 t-1 个键
+    pub fn from_sorted<I>(entries: I, min_degree: usize) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut tree = Self::with_min_degree(min_degree)?;
+        let entries: Vec<(K, V)> = entries.into_iter().collect();
+        check_sorted(&entries)?;
+        if entries.is_empty() {
+            return Ok(tree);
+        }
+        let size = entries.len();
+        let max_keys = tree.get_max_keys();
+        let mut entries = entries.into_iter();
+
+        // 叶子之间各夹一个键作为上一层的分隔键
+        let leaf_count = (size + 1).div_ceil(max_keys + 1);
+        let leaf_keys = size - (leaf_count - 1);
+        let mut nodes = Vec::with_capacity(leaf_count);
+        let mut separators = Vec::with_capacity(leaf_count - 1);
+        for index in 0..leaf_count {
+            let mut leaf = Node::new();
+            for (key, value) in entries.by_ref().take(share(leaf_keys, leaf_count, index)) {
+                leaf.keys.push(key);
+                leaf.values.push(value);
+            }
+            nodes.push(leaf);
+            if index + 1 < leaf_count {
+                separators.push(entries.next().unwrap());
+            }
+        }
+
+        while nodes.len() > 1 {
+            let child_count = nodes.len();
+            let parent_count = child_count.div_ceil(2 * min_degree);
+            let mut children = nodes.into_iter();
+            let mut keys = separators.into_iter();
+            nodes = Vec::with_capacity(parent_count);
+            separators = Vec::with_capacity(parent_count - 1);
+            for index in 0..parent_count {
+                let mut parent = Node::new();
+                let count = share(child_count, parent_count, index);
+                parent.children.extend(children.by_ref().take(count));
+                for (key, value) in keys.by_ref().take(count - 1) {
+                    parent.keys.push(key);
+                    parent.values.push(value);
+                }
+                nodes.push(parent);
+                if index + 1 < parent_count {
+                    separators.push(keys.next().unwrap());
+                }
+            }
+        }
+        tree.root = nodes.pop().unwrap();
+        tree.size = size;
+        Ok(tree)
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_min_degree(&self) -> usize {
+        self.min_degree
+    }
+
+    pub fn get_max_keys(&self) -> usize {
+        2 * self.min_degree - 1
+    }
+
+    // 把满的孩子 i 从中间拆开，中间的键上移到 node
+    fn split_child(node: &mut Node<K, V>, index: usize, min_degree: usize) {
+        let child = &mut node.children[index];
+        let mut right = Node::new();
+        right.keys = child.keys.split_off(min_degree);
+        right.values = child.values.split_off(min_degree);
+        if !child.is_leaf() {
+            right.children = child.children.split_off(min_degree);
+        }
+        let key = child.keys.pop().unwrap();
+        let value = child.values.pop().unwrap();
+        node.keys.insert(index, key);
+        node.values.insert(index, value);
+        node.children.insert(index + 1, right);
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let min_degree = self.min_degree;
+        if self.root.keys.len() == self.get_max_keys() {
+            let old_root = std::mem::replace(&mut self.root, Node::new());
+            self.root.children.push(old_root);
+            Self::split_child(&mut self.root, 0, min_degree);
+        }
+        let mut node = &mut self.root;
+        loop {
+            let mut index = match node.keys.binary_search(&key) {
+                Ok(index) => return Some(std::mem::replace(&mut node.values[index], value)),
+                Err(index) => index,
+            };
+            if node.is_leaf() {
+                node.keys.insert(index, key);
+                node.values.insert(index, value);
+                self.size += 1;
+                return None;
+            }
+            if node.children[index].keys.len() == 2 * min_degree - 1 {
+                Self::split_child(node, index, min_degree);
+                match key.cmp(&node.keys[index]) {
+                    Ordering::Less => {}
+                    Ordering::Equal => {
+                        return Some(std::mem::replace(&mut node.values[index], value));
+                    }
+                    Ordering::Greater => index += 1,
+                }
+            }
+            node = &mut node.children[index];
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = &self.root;
+        loop {
+            match node.keys.binary_search_by(|probe| probe.borrow().cmp(key)) {
+                Ok(index) => return Some((&node.keys[index], &node.values[index])),
+                Err(index) => node = node.children.get(index)?,
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|(_, value)| value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = &mut self.root;
+        loop {
+            match node.keys.binary_search_by(|probe| probe.borrow().cmp(key)) {
+                Ok(index) => return Some(&mut node.values[index]),
+                Err(index) => node = node.children.get_mut(index)?,
+            }
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    // 把孩子 index、分隔键和孩子 index+1 合并成一个节点
+    fn merge_children(node: &mut Node<K, V>, index: usize) {
+        let right = node.children.remove(index + 1);
+        let key = node.keys.remove(index);
+        let value = node.values.remove(index);
+        let left = &mut node.children[index];
+        left.keys.push(key);
+        left.values.push(value);
+        left.keys.extend(right.keys);
+        left.values.extend(right.values);
+        left.children.extend(right.children);
+    }
+
+    // 保证孩子 index 至少有 t 个键：先向左右兄弟借，借不到就和兄弟合并；返回合并后孩子的下标
+    fn fill_child(node: &mut Node<K, V>, index: usize, min_degree: usize) -> usize {
+        if node.children[index].keys.len() >= min_degree {
+            return index;
+        }
+        if index > 0 && node.children[index - 1].keys.len() >= min_degree {
+            let (before, after) = node.children.split_at_mut(index);
+            let left = &mut before[index - 1];
+            let child = &mut after[0];
+            let key = std::mem::replace(&mut node.keys[index - 1], left.keys.pop().unwrap());
+            let value = std::mem::replace(&mut node.values[index - 1], left.values.pop().unwrap());
+            child.keys.insert(0, key);
+            child.values.insert(0, value);
+            if let Some(grandchild) = left.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+            return index;
+        }
+        if index + 1 < node.children.len() && node.children[index + 1].keys.len() >= min_degree {
+            let (before, after) = node.children.split_at_mut(index + 1);
+            let child = &mut before[index];
+            let right = &mut after[0];
+            let key = std::mem::replace(&mut node.keys[index], right.keys.remove(0));
+            let value = std::mem::replace(&mut node.values[index], right.values.remove(0));
+            child.keys.push(key);
+            child.values.push(value);
+            if !right.is_leaf() {
+                child.children.push(right.children.remove(0));
+            }
+            return index;
+        }
+        if index < node.keys.len() {
+            Self::merge_children(node, index);
+            index
+        } else {
+            Self::merge_children(node, index - 1);
+            index - 1
+        }
+    }
+
+    fn pop_min_from(mut node: &mut Node<K, V>, min_degree: usize) -> (K, V) {
+        while !node.is_leaf() {
+            let index = Self::fill_child(node, 0, min_degree);
+            node = &mut node.children[index];
+        }
+        (node.keys.remove(0), node.values.remove(0))
+    }
+
+    fn pop_max_from(mut node: &mut Node<K, V>, min_degree: usize) -> (K, V) {
+        while !node.is_leaf() {
+            let last = node.children.len() - 1;
+            let index = Self::fill_child(node, last, min_degree);
+            node = &mut node.children[index];
+        }
+        (node.keys.pop().unwrap(), node.values.pop().unwrap())
+    }
+
+    fn remove_from<Q>(mut node: &mut Node<K, V>, key: &Q, min_degree: usize) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        loop {
+            let search = node.keys.binary_search_by(|probe| probe.borrow().cmp(key));
+            if node.is_leaf() {
+                let index = search.ok()?;
+                return Some((node.keys.remove(index), node.values.remove(index)));
+            }
+            match search {
+                Ok(index) => {
+                    // 键在内部节点：用前驱或后继顶替，两边孩子都只有 t-1 个键时先合并再往下删
+                    if node.children[index].keys.len() >= min_degree {
+                        let (k, v) = Self::pop_max_from(&mut node.children[index], min_degree);
+                        let key = std::mem::replace(&mut node.keys[index], k);
+                        let value = std::mem::replace(&mut node.values[index], v);
+                        return Some((key, value));
+                    }
+                    if node.children[index + 1].keys.len() >= min_degree {
+                        let (k, v) = Self::pop_min_from(&mut node.children[index + 1], min_degree);
+                        let key = std::mem::replace(&mut node.keys[index], k);
+                        let value = std::mem::replace(&mut node.values[index], v);
+                        return Some((key, value));
+                    }
+                    Self::merge_children(node, index);
+                    node = &mut node.children[index];
+                }
+                Err(index) => {
+                    let index = Self::fill_child(node, index, min_degree);
+                    node = &mut node.children[index];
+                }
+            }
+        }
+    }
+
+    // 根的键被合并光以后，让唯一的孩子当新根，树高减一
+    fn shrink_root(&mut self) {
+        if self.root.keys.is_empty() && !self.root.is_leaf() {
+            self.root = self.root.children.pop().unwrap();
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed = Self::remove_from(&mut self.root, key, self.min_degree);
+        self.shrink_root();
+        if removed.is_some() {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        if self.size == 0 {
+            return None;
+        }
+        let entry = Self::pop_min_from(&mut self.root, self.min_degree);
+        self.shrink_root();
+        self.size -= 1;
+        Some(entry)
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        if self.size == 0 {
+            return None;
+        }
+        let entry = Self::pop_max_from(&mut self.root, self.min_degree);
+        self.shrink_root();
+        self.size -= 1;
+        Some(entry)
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            node = child;
+        }
+        Some((node.keys.first()?, node.values.first()?))
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(child) = node.children.last() {
+            node = child;
+        }
+        Some((node.keys.last()?, node.values.last()?))
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range::<K, _>(..)
+    }
+
+    // 下界沿途每层压一个 (节点, 下一个要输出的键) 帧；上界预先找出第一个超出的键，遇到它就停
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut stack = ArrayStack::new();
+        let mut current = Some(&self.root);
+        while let Some(node) = current {
+            let index = match range.start_bound() {
+                Bound::Unbounded => 0,
+                Bound::Included(start) => node.keys.partition_point(|k| k.borrow() < start),
+                Bound::Excluded(start) => node.keys.partition_point(|k| k.borrow() <= start),
+            };
+            stack.push((node, index));
+            current = node.children.get(index);
+        }
+        let mut stop = None;
+        let mut current = Some(&self.root);
+        while let Some(node) = current {
+            let index = match range.end_bound() {
+                Bound::Unbounded => break,
+                Bound::Included(end) => node.keys.partition_point(|k| k.borrow() <= end),
+                Bound::Excluded(end) => node.keys.partition_point(|k| k.borrow() < end),
+            };
+            if let Some(key) = node.keys.get(index) {
+                stop = Some(key);
+            }
+            current = node.children.get(index);
+        }
+        // 下界在上界之后时区间为空
+        let first = stack.iter().find_map(|&(node, index)| node.keys.get(index));
+        if let (Some(first), Some(stop)) = (first, stop)
+            && first > stop
+        {
+            stack.clear();
+        }
+        Iter { stack, stop }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // 所有叶子在同一层，沿最左路径数层数即可
+    pub fn height(&self) -> usize {
+        if self.size == 0 {
+            return 0;
+        }
+        let mut height = 1;
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            node = child;
+            height += 1;
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+        self.size = 0;
+    }
+
+    pub fn fill_stats(&self) -> NodeFillStats {
+        let mut stats = NodeFillStats {
+            height: self.height(),
+            node_count: 0,
+            leaf_count: 0,
+            key_count: 0,
+            min_keys: usize::MAX,
+            max_keys: 0,
+            fill_ratio: 0.0,
+        };
+        let mut stack = ArrayStack::new();
+        stack.push(&self.root);
+        while let Some(node) = stack.pop() {
+            stats.node_count += 1;
+            stats.leaf_count += usize::from(node.is_leaf());
+            stats.key_count += node.keys.len();
+            stats.min_keys = stats.min_keys.min(node.keys.len());
+            stats.max_keys = stats.max_keys.max(node.keys.len());
+            stack.extend(node.children.iter());
+        }
+        stats.fill_ratio = stats.key_count as f64 / (stats.node_count * self.get_max_keys()) as f64;
+        stats
+    }
+
+    // 检查每个节点的键数和孩子数、所有叶子同深，再用中序遍历检查有序和总数
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let min_keys = self.min_degree - 1;
+        let max_keys = self.get_max_keys();
+        let mut leaf_depth = None;
+        let mut stack = ArrayStack::new();
+        stack.push((&self.root, 1));
+        while let Some((node, depth)) = stack.pop() {
+            let is_root = std::ptr::eq(node, &self.root);
+            if node.keys.len() > max_keys || (!is_root && node.keys.len() < min_keys) {
+                return Err(format!(
+                    "Node at depth {} has {} keys. Valid range: {} to {}.",
+                    depth,
+                    node.keys.len(),
+                    min_keys,
+                    max_keys
+                ));
+            }
+            if node.values.len() != node.keys.len() {
+                return Err("Key and value counts differ.".to_owned());
+            }
+            if node.is_leaf() {
+                if *leaf_depth.get_or_insert(depth) != depth {
+                    return Err("Leaves are not all at the same depth.".to_owned());
+                }
+            } else if node.children.len() != node.keys.len() + 1 {
+                return Err(format!(
+                    "Internal node has {} keys but {} children.",
+                    node.keys.len(),
+                    node.children.len()
+                ));
+            }
+            stack.extend(node.children.iter().map(|child| (child, depth + 1)));
+        }
+        let mut count = 0;
+        let mut previous: Option<&K> = None;
+        for (key, _) in self.iter() {
+            if previous.is_some_and(|previous| previous >= key) {
+                return Err(format!(
+                    "Keys are not strictly increasing in order at position {}.",
+                    count
+                ));
+            }
+            previous = Some(key);
+            count += 1;
+        }
+        if count != self.size {
+            return Err(format!(
+                "Size mismatch: recorded {}, counted {}.",
+                self.size, count
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    stack: ArrayStack<(&'a Node<K, V>, usize)>,
+    // 第一个超出上界的键
+    stop: Option<&'a K>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, index) = self.stack.pop()?;
+            if index >= node.keys.len() {
+                continue;
+            }
+            let key = &node.keys[index];
+            if self.stop.is_some_and(|stop| std::ptr::eq(stop, key)) {
+                self.stack.clear();
+                return None;
+            }
+            // 输出第 index 个键之后，下一步要先走完孩子 index+1 的最左路径
+            self.stack.push((node, index + 1));
+            let mut current = node.children.get(index + 1);
+            while let Some(child) = current {
+                self.stack.push((child, 0));
+                current = child.children.first();
+            }
+            return Some((key, &node.values[index]));
+        }
+    }
+}
+
+impl<K, V> Default for BTree<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for BTree<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K, V> Extend<(K, V)> for BTree<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for BTree<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::BTree;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        for min_degree in [2, 3, 8] {
+            let mut random = TestRng::with_seed(44);
+            let mut map = BTree::with_min_degree(min_degree).unwrap();
+            let mut expected = BTreeMap::new();
+            for _ in 0..2000 {
+                let key = random.below(200);
+                if random.next_u64().is_multiple_of(3) {
+                    assert_eq!(map.remove(&key), expected.remove(&key));
+                } else {
+                    assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2));
+                }
+                map.check_invariants().unwrap();
+                assert_eq!(map.get_size(), expected.len());
+            }
+            assert!(map.iter().eq(expected.iter()));
+            assert!(map.range(50..150).eq(expected.range(50..150)));
+            assert!(map.range(..=80).eq(expected.range(..=80)));
+        }
+    }
+
+    #[test]
+    fn from_sorted_and_inverted_range() {
+        let map = BTree::from_sorted((0..100).map(|key| (key, key)), 2).unwrap();
+        map.check_invariants().unwrap();
+        assert!(map.keys().copied().eq(0..100));
+        let (start, end) = (50, 30);
+        assert_eq!(map.range(start..=end).count(), 0);
+        assert_eq!(map.range(end..end).count(), 0);
+        assert!(map.range(end..=end).eq([(&30, &30)]));
+    }
+}
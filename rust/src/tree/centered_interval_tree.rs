@@ -0,0 +1,263 @@
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use crate::stack::array_stack::ArrayStack;
+
+type NodeId = usize;
+
+struct Node<T> {
+    center: T,
+    // 跨过 center 的区间下标，分别按左端点升序、右端点降序排列
+    by_start: Vec<usize>,
+    by_end: Vec<usize>,
+    left: Option<NodeId>,
+    right: Option<NodeId>,
+}
+
+// 中心区间树：一次性批量建好之后只读。每个节点取端点的中位数作为 center，
+// 跨过 center 的区间留在节点上，完全在左边或右边的区间分给左右子树，
+// 树高 O(log n)，点查询 O(log n + k)
+pub struct CenteredIntervalTree<T, V> {
+    intervals: Vec<(T, T, V)>,
+    nodes: Vec<Node<T>>,
+    root: Option<NodeId>,
+}
+
+impl<T, V> CenteredIntervalTree<T, V>
+where
+    T: Ord + Clone,
+{
+    pub fn new(intervals: Vec<(T, T, V)>) -> Result<Self, String> {
+        if let Some(index) = intervals.iter().position(|(start, end, _)| start > end) {
+            return Err(format!(
+                "Invalid interval at position {}: start is greater than end.",
+                index
+            ));
+        }
+        let mut tree = CenteredIntervalTree {
+            intervals,
+            nodes: Vec::new(),
+            root: None,
+        };
+        let all = (0..tree.intervals.len()).collect();
+        tree.root = tree.build(all);
+        Ok(tree)
+    }
+
+    // 中位数端点一定属于某个区间且被它跨过，所以每层至少留下一个区间，递归必然结束
+    fn build(&mut self, members: Vec<usize>) -> Option<NodeId> {
+        if members.is_empty() {
+            return None;
+        }
+        let mut endpoints: Vec<&T> = members
+            .iter()
+            .flat_map(|&index| [&self.intervals[index].0, &self.intervals[index].1])
+            .collect();
+        endpoints.sort();
+        let center = endpoints[endpoints.len() / 2].clone();
+        let (mut left, mut right, mut crossing) = (Vec::new(), Vec::new(), Vec::new());
+        for index in members {
+            let (start, end, _) = &self.intervals[index];
+            if *end < center {
+                left.push(index);
+            } else if *start > center {
+                right.push(index);
+            } else {
+                crossing.push(index);
+            }
+        }
+        let mut by_start = crossing.clone();
+        by_start.sort_by(|&a, &b| self.intervals[a].0.cmp(&self.intervals[b].0));
+        let mut by_end = crossing;
+        by_end.sort_by(|&a, &b| self.intervals[b].1.cmp(&self.intervals[a].1));
+        let left = self.build(left);
+        let right = self.build(right);
+        self.nodes.push(Node {
+            center,
+            by_start,
+            by_end,
+            left,
+            right,
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // 包含点 point 的所有区间，顺序不作保证
+    pub fn stab(&self, point: &T) -> Vec<(&T, &T, &V)> {
+        self.overlap(point, point)
+    }
+
+    // 与闭区间 [start, end] 有公共点的所有区间，顺序不作保证；start > end 时为空
+    pub fn overlap(&self, start: &T, end: &T) -> Vec<(&T, &T, &V)> {
+        let mut result = Vec::new();
+        if start > end {
+            return result;
+        }
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root);
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id];
+            // 节点上的区间都跨过 center：查询在 center 左边时只看左端点，
+            // 在右边时只看右端点，有序列表扫到第一个不满足的就可以停
+            if *end < node.center {
+                for &index in &node.by_start {
+                    if self.intervals[index].0 > *end {
+                        break;
+                    }
+                    result.push(self.entry(index));
+                }
+                stack.extend(node.left);
+            } else if *start > node.center {
+                for &index in &node.by_end {
+                    if self.intervals[index].1 < *start {
+                        break;
+                    }
+                    result.push(self.entry(index));
+                }
+                stack.extend(node.right);
+            } else {
+                result.extend(node.by_start.iter().map(|&index| self.entry(index)));
+                stack.extend(node.left);
+                stack.extend(node.right);
+            }
+        }
+        result
+    }
+
+    fn entry(&self, index: usize) -> (&T, &T, &V) {
+        let (start, end, value) = &self.intervals[index];
+        (start, end, value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &T, &V)> {
+        self.intervals
+            .iter()
+            .map(|(start, end, value)| (start, end, value))
+    }
+
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.map(|id| (id, 1)));
+        while let Some((id, depth)) = stack.pop() {
+            height = height.max(depth);
+            let node = &self.nodes[id];
+            stack.extend(node.left.map(|id| (id, depth + 1)));
+            stack.extend(node.right.map(|id| (id, depth + 1)));
+        }
+        height
+    }
+
+    // 节点上的区间都跨过 center，左子树的区间都在它左边，右子树的都在它右边，且每个区间恰好出现一次
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let mut seen = vec![false; self.intervals.len()];
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.map(|id| (id, None::<&T>, None::<&T>)));
+        while let Some((id, lower, upper)) = stack.pop() {
+            let node = &self.nodes[id];
+            if node.by_start.is_empty() {
+                return Err("Node holds no intervals.".to_owned());
+            }
+            if node.by_start.len() != node.by_end.len() {
+                return Err("Start and end lists differ in length.".to_owned());
+            }
+            for &index in &node.by_start {
+                let (start, end, _) = &self.intervals[index];
+                if *start > node.center || *end < node.center {
+                    return Err(format!("Interval {} does not cross its center.", index));
+                }
+                if lower.is_some_and(|lower| start <= lower)
+                    || upper.is_some_and(|upper| end >= upper)
+                {
+                    return Err(format!(
+                        "Interval {} is on the wrong side of an ancestor.",
+                        index
+                    ));
+                }
+                if std::mem::replace(&mut seen[index], true) {
+                    return Err(format!("Interval {} is stored twice.", index));
+                }
+            }
+            if node
+                .by_start
+                .windows(2)
+                .any(|pair| self.intervals[pair[0]].0 > self.intervals[pair[1]].0)
+                || node
+                    .by_end
+                    .windows(2)
+                    .any(|pair| self.intervals[pair[0]].1 < self.intervals[pair[1]].1)
+            {
+                return Err("Interval lists are not sorted.".to_owned());
+            }
+            stack.extend(node.left.map(|left| (left, lower, Some(&node.center))));
+            stack.extend(node.right.map(|right| (right, Some(&node.center), upper)));
+        }
+        if let Some(index) = seen.iter().position(|&seen| !seen) {
+            return Err(format!("Interval {} is missing from the tree.", index));
+        }
+        Ok(())
+    }
+}
+
+impl<T, V> Debug for CenteredIntervalTree<T, V>
+where
+    T: Ord + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(start, end, value)| (start..=end, value)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CenteredIntervalTree;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn queries_match_brute_force() {
+        let mut random = TestRng::with_seed(46);
+        let intervals: Vec<(u64, u64, usize)> = (0..500)
+            .map(|index| {
+                let start = random.below(1000);
+                (start, start + random.below(50), index)
+            })
+            .collect();
+        let tree = CenteredIntervalTree::new(intervals.clone()).unwrap();
+        tree.check_invariants().unwrap();
+        assert_eq!(tree.get_size(), intervals.len());
+        for _ in 0..200 {
+            let low = random.below(1100);
+            let high = low + random.below(30);
+            let mut expected: Vec<_> = intervals
+                .iter()
+                .filter(|(start, end, _)| *start <= high && low <= *end)
+                .map(|(start, end, value)| (start, end, value))
+                .collect();
+            let mut overlapping = tree.overlap(&low, &high);
+            expected.sort();
+            overlapping.sort();
+            assert_eq!(overlapping, expected);
+
+            let mut stabbed = tree.stab(&low);
+            stabbed.sort();
+            let stabbed_expected: Vec<_> = expected
+                .iter()
+                .copied()
+                .filter(|(start, end, _)| **start <= low && low <= **end)
+                .collect();
+            assert_eq!(stabbed, stabbed_expected);
+        }
+    }
+}
@@ -0,0 +1,423 @@
+#![allow(dead_code)]
+
+use std::{cmp::Ordering, fmt::Debug, ops::Range};
+
+use super::treap::PriorityGenerator;
+use crate::queue::array_queue::ArrayQueue;
+use crate::stack::array_stack::ArrayStack;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    priority: u64,
+    size: usize,
+    // 懒标记：整棵子树还欠一次翻转，访问孩子之前先下推
+    reversed: bool,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn update(&mut self) {
+        self.size = 1 + size_of(&self.left) + size_of(&self.right);
+    }
+
+    fn push_down(&mut self) {
+        if self.reversed {
+            std::mem::swap(&mut self.left, &mut self.right);
+            for child in [self.left.as_mut(), self.right.as_mut()]
+                .into_iter()
+                .flatten()
+            {
+                child.reversed ^= true;
+            }
+            self.reversed = false;
+        }
+    }
+}
+
+fn size_of<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+// 前 count 个元素分到左边，其余分到右边
+fn split<T>(link: Link<T>, count: usize) -> (Link<T>, Link<T>) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    node.push_down();
+    let left_size = size_of(&node.left);
+    if count <= left_size {
+        let (left, right) = split(node.left.take(), count);
+        node.left = right;
+        node.update();
+        (left, Some(node))
+    } else {
+        let (left, right) = split(node.right.take(), count - left_size - 1);
+        node.right = left;
+        node.update();
+        (Some(node), right)
+    }
+}
+
+fn merge<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.push_down();
+                left.right = merge(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.push_down();
+                right.left = merge(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+fn check_node<T>(link: &Link<T>) -> Result<(), String> {
+    let Some(node) = link else {
+        return Ok(());
+    };
+    for child in [&node.left, &node.right].into_iter().flatten() {
+        if child.priority > node.priority {
+            return Err("Child priority exceeds parent priority.".to_owned());
+        }
+    }
+    if node.size != 1 + size_of(&node.left) + size_of(&node.right) {
+        return Err("Subtree size mismatch.".to_owned());
+    }
+    check_node(&node.left)?;
+    check_node(&node.right)
+}
+
+// 隐式键的 Treap：不存键，节点在中序里的位置就是下标，由子树大小算出；
+// 按位置分裂合并就能在 O(log n) 内完成任意位置的插入、删除和区间翻转
+pub struct ImplicitTreap<T> {
+    root: Link<T>,
+    priorities: PriorityGenerator,
+}
+
+impl<T> ImplicitTreap<T> {
+    pub fn new() -> Self {
+        ImplicitTreap {
+            root: None,
+            priorities: PriorityGenerator::new(),
+        }
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        ImplicitTreap {
+            root: None,
+            priorities: PriorityGenerator::with_seed(seed),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        size_of(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        if index >= self.get_size() {
+            return Err(format!(
+                "Index out of bounds: {}. Current size: {}.",
+                index,
+                self.get_size()
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.get_size() {
+            return Err(format!(
+                "Invalid range: {}..{}. Current size: {}.",
+                range.start,
+                range.end,
+                self.get_size()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), String> {
+        if index > self.get_size() {
+            return Err(format!(
+                "Insertion index is out of bounds. Valid range: 0 to {}. Requested: {}",
+                self.get_size(),
+                index
+            ));
+        }
+        let node = Box::new(Node {
+            value,
+            priority: self.priorities.next_priority(),
+            size: 1,
+            reversed: false,
+            left: None,
+            right: None,
+        });
+        let (left, right) = split(self.root.take(), index);
+        self.root = merge(merge(left, Some(node)), right);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, String> {
+        self.check_index(index)?;
+        let (left, rest) = split(self.root.take(), index);
+        let (middle, right) = split(rest, 1);
+        self.root = merge(left, right);
+        Ok(middle.unwrap().value)
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.insert(0, value).unwrap();
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.insert(self.get_size(), value).unwrap();
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove(0).ok()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let size = self.get_size();
+        if size == 0 {
+            return None;
+        }
+        self.remove(size - 1).ok()
+    }
+
+    // 把区间单独分裂出来，只在它的根上打翻转标记
+    pub fn reverse(&mut self, range: Range<usize>) -> Result<(), String> {
+        self.check_range(&range)?;
+        if range.len() < 2 {
+            return Ok(());
+        }
+        let (left, rest) = split(self.root.take(), range.start);
+        let (mut middle, right) = split(rest, range.len());
+        if let Some(node) = middle.as_mut() {
+            node.reversed ^= true;
+        }
+        self.root = merge(merge(left, middle), right);
+        Ok(())
+    }
+
+    // 只读访问不能下推标记，沿途记下累计的翻转次数，翻转奇数次时左右孩子互换
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.check_index(index).ok()?;
+        let mut index = index;
+        let mut flipped = false;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            flipped ^= node.reversed;
+            let (left, right) = if flipped {
+                (&node.right, &node.left)
+            } else {
+                (&node.left, &node.right)
+            };
+            let left_size = size_of(left);
+            match index.cmp(&left_size) {
+                Ordering::Less => current = left.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    current = right.as_deref();
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.check_index(index).ok()?;
+        let mut index = index;
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            node.push_down();
+            let left_size = size_of(&node.left);
+            match index.cmp(&left_size) {
+                Ordering::Less => current = node.left.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    current = node.right.as_deref_mut();
+                }
+            }
+        }
+        None
+    }
+
+    // 下标 at 及之后的元素分裂出来作为新的序列返回
+    pub fn split_off(&mut self, at: usize) -> Result<ImplicitTreap<T>, String> {
+        if at > self.get_size() {
+            return Err(format!(
+                "Split index is out of bounds. Valid range: 0 to {}. Requested: {}",
+                self.get_size(),
+                at
+            ));
+        }
+        let (left, right) = split(self.root.take(), at);
+        self.root = left;
+        Ok(ImplicitTreap {
+            root: right,
+            priorities: PriorityGenerator::with_seed(self.priorities.next_priority()),
+        })
+    }
+
+    // 把 other 整体接在末尾
+    pub fn append(&mut self, other: &mut ImplicitTreap<T>) {
+        self.root = merge(self.root.take(), other.root.take());
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            stack: ArrayStack::new(),
+        };
+        iter.push_left_spine(self.root.as_deref(), false);
+        iter
+    }
+
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut queue = ArrayQueue::new();
+        queue.extend(self.root.as_deref());
+        while !queue.is_empty() {
+            height += 1;
+            for _ in 0..queue.get_size() {
+                let node = queue.dequeue().unwrap();
+                queue.extend(node.left.as_deref());
+                queue.extend(node.right.as_deref());
+            }
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        check_node(&self.root)
+    }
+}
+
+pub struct Iter<'a, T> {
+    // 节点和它的祖先累计的翻转状态
+    stack: ArrayStack<(&'a Node<T>, bool)>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut current: Option<&'a Node<T>>, mut flipped: bool) {
+        while let Some(node) = current {
+            self.stack.push((node, flipped));
+            flipped ^= node.reversed;
+            current = if flipped {
+                node.right.as_deref()
+            } else {
+                node.left.as_deref()
+            };
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, flipped) = self.stack.pop()?;
+        let flipped = flipped ^ node.reversed;
+        let next = if flipped {
+            node.left.as_deref()
+        } else {
+            node.right.as_deref()
+        };
+        self.push_left_spine(next, flipped);
+        Some(&node.value)
+    }
+}
+
+impl<T> Default for ImplicitTreap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for ImplicitTreap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut treap = ImplicitTreap::new();
+        treap.extend(iter);
+        treap
+    }
+}
+
+impl<T> Extend<T> for ImplicitTreap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ImplicitTreap<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Debug for ImplicitTreap<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImplicitTreap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_vec() {
+        let mut random = TestRng::with_seed(43);
+        let mut sequence = ImplicitTreap::with_seed(43);
+        let mut expected = Vec::new();
+        for step in 0..2000 {
+            let at = random.index(expected.len() + 1);
+            match random.below(4) {
+                0 if at < expected.len() => {
+                    assert_eq!(sequence.remove(at), Ok(expected.remove(at)));
+                }
+                1 => {
+                    let end = at + random.index(8);
+                    let end = end.min(expected.len());
+                    sequence.reverse(at..end).unwrap();
+                    expected[at..end].reverse();
+                }
+                _ => {
+                    sequence.insert(at, step).unwrap();
+                    expected.insert(at, step);
+                }
+            }
+            sequence.check_invariants().unwrap();
+            assert_eq!(sequence.get_size(), expected.len());
+        }
+        assert!(sequence.iter().eq(expected.iter()));
+    }
+}
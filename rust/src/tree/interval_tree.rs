@@ -0,0 +1,363 @@
+#![allow(dead_code)]
+
+use std::{cmp::Ordering, fmt::Debug};
+
+use super::avl_tree::{self, AvlNode};
+use super::search_tree::{self, SearchNode, SplitMut};
+use crate::stack::array_stack::ArrayStack;
+
+type Link<T, V> = avl_tree::Link<Node<T, V>>;
+
+pub struct Node<T, V> {
+    // 闭区间 [start, end]，按 (start, end) 字典序排列
+    interval: (T, T),
+    value: V,
+    // 子树里所有区间右端点的最大值，子树整体落在查询区间左边时据此剪枝
+    max_end: T,
+    height: usize,
+    left: Link<T, V>,
+    right: Link<T, V>,
+}
+
+impl<T, V> Node<T, V>
+where
+    T: Ord,
+{
+    fn compare(&self, start: &T, end: &T) -> Ordering {
+        start
+            .cmp(&self.interval.0)
+            .then_with(|| end.cmp(&self.interval.1))
+    }
+}
+
+impl<T, V> SearchNode for Node<T, V> {
+    type Key = (T, T);
+    type Value = V;
+
+    fn key(&self) -> &(T, T) {
+        &self.interval
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.interval,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+impl<T, V> AvlNode for Node<T, V>
+where
+    T: Ord + Clone,
+{
+    fn leaf(interval: (T, T), value: V) -> Box<Self> {
+        Box::new(Node {
+            max_end: interval.1.clone(),
+            interval,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn left_link(&mut self) -> &mut Link<T, V> {
+        &mut self.left
+    }
+
+    fn right_link(&mut self) -> &mut Link<T, V> {
+        &mut self.right
+    }
+
+    // 旋转时先更新下沉的节点再更新上浮的节点，max_end 随之正确
+    fn update(&mut self) {
+        self.height = 1 + avl_tree::height_of(self.left()).max(avl_tree::height_of(self.right()));
+        let mut max_end = &self.interval.1;
+        for child in [&self.left, &self.right].into_iter().flatten() {
+            if child.max_end > *max_end {
+                max_end = &child.max_end;
+            }
+        }
+        self.max_end = max_end.clone();
+    }
+}
+
+// 后序核对区间端点和每个节点记录的 max_end
+fn check_max_end<T: Ord, V>(link: &Link<T, V>) -> Result<(), String> {
+    let Some(node) = link else {
+        return Ok(());
+    };
+    if node.interval.0 > node.interval.1 {
+        return Err("Interval start is greater than its end.".to_owned());
+    }
+    check_max_end(&node.left)?;
+    check_max_end(&node.right)?;
+    let max_end = [&node.left, &node.right]
+        .into_iter()
+        .flatten()
+        .map(|child| &child.max_end)
+        .fold(&node.interval.1, |max, end| max.max(end));
+    if node.max_end != *max_end {
+        return Err("Max endpoint mismatch.".to_owned());
+    }
+    Ok(())
+}
+
+// 区间树：以左端点为主序的 AVL 树，每个节点额外记录子树的最大右端点，
+// 重叠查询只进入可能含有答案的子树，耗时 O(log n + k)
+pub struct IntervalTree<T, V> {
+    root: Link<T, V>,
+    size: usize,
+    rotations: usize,
+}
+
+impl<T, V> IntervalTree<T, V>
+where
+    T: Ord + Clone,
+{
+    pub fn new() -> Self {
+        IntervalTree {
+            root: None,
+            size: 0,
+            rotations: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 同一个区间重复插入时替换旧值
+    pub fn insert(&mut self, start: T, end: T, value: V) -> Result<Option<V>, String> {
+        if start > end {
+            return Err("Invalid interval: start is greater than end.".to_owned());
+        }
+        let old = avl_tree::insert(&mut self.root, (start, end), value, &mut self.rotations);
+        if old.is_none() {
+            self.size += 1;
+        }
+        Ok(old)
+    }
+
+    pub fn get(&self, start: &T, end: &T) -> Option<&V> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match node.compare(start, end) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, start: &T, end: &T) -> Option<&mut V> {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match node.compare(start, end) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains(&self, start: &T, end: &T) -> bool {
+        self.get(start, end).is_some()
+    }
+
+    pub fn remove(&mut self, start: &T, end: &T) -> Option<V> {
+        let compare = |node: &Node<T, V>| node.compare(start, end);
+        let node = avl_tree::remove(&mut self.root, &compare, &mut self.rotations)?;
+        self.size -= 1;
+        Some(node.value)
+    }
+
+    // 与闭区间 [start, end] 有公共点的所有区间，按 (start, end) 升序给出；start > end 时为空
+    pub fn overlap<'a>(&'a self, start: &'a T, end: &'a T) -> Overlap<'a, T, V> {
+        let mut overlap = Overlap {
+            stack: ArrayStack::new(),
+            start,
+            end,
+        };
+        if start <= end {
+            overlap.push_left_spine(self.root.as_deref());
+        }
+        overlap
+    }
+
+    // 包含点 point 的所有区间
+    pub fn stab<'a>(&'a self, point: &'a T) -> Overlap<'a, T, V> {
+        self.overlap(point, point)
+    }
+
+    // 只要一个重叠区间时不必建迭代器：左子树的 max_end 够得着就往左，否则往右
+    pub fn find_any(&self, start: &T, end: &T) -> Option<(&T, &T, &V)> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            if node.interval.0 <= *end && *start <= node.interval.1 {
+                return Some((&node.interval.0, &node.interval.1, &node.value));
+            }
+            current = match node.left.as_deref() {
+                Some(left) if left.max_end >= *start => Some(left),
+                _ => node.right.as_deref(),
+            };
+        }
+        None
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &T, &V)> {
+        search_tree::iter(self.root.as_deref()).map(|((start, end), value)| (start, end, value))
+    }
+
+    pub fn height(&self) -> usize {
+        avl_tree::height_of(self.root.as_deref())
+    }
+
+    pub fn get_rotation_count(&self) -> usize {
+        self.rotations
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.size = 0;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        search_tree::check_order(self.root.as_deref(), self.size)?;
+        avl_tree::check_balance(self.root.as_deref())?;
+        check_max_end(&self.root)?;
+        Ok(())
+    }
+}
+
+pub struct Overlap<'a, T, V> {
+    stack: ArrayStack<&'a Node<T, V>>,
+    start: &'a T,
+    end: &'a T,
+}
+
+impl<'a, T, V> Overlap<'a, T, V>
+where
+    T: Ord,
+{
+    // max_end 小于查询左端点的子树整棵跳过
+    fn push_left_spine(&mut self, mut current: Option<&'a Node<T, V>>) {
+        while let Some(node) = current {
+            if node.max_end < *self.start {
+                break;
+            }
+            self.stack.push(node);
+            current = node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T, V> Iterator for Overlap<'a, T, V>
+where
+    T: Ord,
+{
+    type Item = (&'a T, &'a T, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            // 左端点已经超过查询右端点，中序在它之后的区间左端点只会更大
+            if node.interval.0 > *self.end {
+                self.stack.clear();
+                return None;
+            }
+            self.push_left_spine(node.right.as_deref());
+            if *self.start <= node.interval.1 {
+                return Some((&node.interval.0, &node.interval.1, &node.value));
+            }
+        }
+        None
+    }
+}
+
+impl<T, V> Default for IntervalTree<T, V>
+where
+    T: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, V> Debug for IntervalTree<T, V>
+where
+    T: Ord + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(start, end, value)| (start..=end, value)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::IntervalTree;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_brute_force() {
+        let mut random = TestRng::with_seed(46);
+        let mut tree = IntervalTree::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..2000 {
+            let start = random.below(100);
+            let end = start + random.below(20);
+            if random.next_u64().is_multiple_of(3) {
+                assert_eq!(tree.remove(&start, &end), expected.remove(&(start, end)));
+            } else {
+                assert_eq!(
+                    tree.insert(start, end, step),
+                    Ok(expected.insert((start, end), step))
+                );
+            }
+            tree.check_invariants().unwrap();
+            assert_eq!(tree.get_size(), expected.len());
+
+            let low = random.below(120);
+            let high = low + random.below(10);
+            let overlapping: Vec<_> = expected
+                .iter()
+                .filter(|&(&(s, e), _)| s <= high && low <= e)
+                .map(|((s, e), v)| (s, e, v))
+                .collect();
+            assert!(tree.overlap(&low, &high).eq(overlapping.iter().copied()));
+            assert_eq!(
+                tree.find_any(&low, &high).is_some(),
+                !overlapping.is_empty()
+            );
+        }
+        assert!(tree.iter().eq(expected.iter().map(|((s, e), v)| (s, e, v))));
+    }
+}
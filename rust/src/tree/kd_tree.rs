@@ -0,0 +1,525 @@
+#![allow(dead_code)]
+
+use std::{cmp::Ordering, collections::BinaryHeap, fmt::Debug};
+
+use crate::queue::array_queue::ArrayQueue;
+use crate::stack::array_stack::ArrayStack;
+
+type Link<const K: usize, V> = Option<Box<Node<K, V>>>;
+
+// 查询结果：点、值和到查询点的欧氏距离
+pub type Neighbor<'a, const K: usize, V> = (&'a [f64; K], &'a V, f64);
+
+struct Node<const K: usize, V> {
+    point: [f64; K],
+    value: V,
+    // 按第 axis 维切分：左子树在这一维上不大于 point，右子树不小于 point
+    axis: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+// 候选点按距离平方排序，放进大根堆时堆顶就是当前第 k 近的点
+struct Candidate<'a, const K: usize, V> {
+    distance: f64,
+    node: &'a Node<K, V>,
+}
+
+impl<const K: usize, V> PartialEq for Candidate<'_, K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<const K: usize, V> Eq for Candidate<'_, K, V> {}
+
+impl<const K: usize, V> PartialOrd for Candidate<'_, K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const K: usize, V> Ord for Candidate<'_, K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+fn squared_distance<const K: usize>(a: &[f64; K], b: &[f64; K]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+fn check_point<const K: usize>(point: &[f64; K]) -> Result<(), String> {
+    if K == 0 {
+        return Err("Dimension must be a positive integer.".to_owned());
+    }
+    if point.iter().any(|coordinate| coordinate.is_nan()) {
+        return Err("Point coordinates must not be NaN.".to_owned());
+    }
+    Ok(())
+}
+
+// 每层取当前维的中位数作根，左右各分一半，树高 ⌈log2(n + 1)⌉
+fn build<const K: usize, V>(mut items: Vec<([f64; K], V)>, depth: usize) -> Link<K, V> {
+    if items.is_empty() {
+        return None;
+    }
+    let axis = depth % K;
+    let middle = items.len() / 2;
+    items.select_nth_unstable_by(middle, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    let right = items.split_off(middle + 1);
+    let (point, value) = items.pop().unwrap();
+    Some(Box::new(Node {
+        point,
+        value,
+        axis,
+        left: build(items, depth + 1),
+        right: build(right, depth + 1),
+    }))
+}
+
+// k-d 树：每层按一个坐标轴把空间一分为二，轴按深度轮换。
+// 近邻查询先钻进查询点所在的一侧，另一侧只有当切分平面比当前第 k 近的点更近时才需要看
+pub struct KdTree<const K: usize, V> {
+    root: Link<K, V>,
+    size: usize,
+}
+
+impl<const K: usize, V> KdTree<K, V> {
+    pub fn new() -> Self {
+        KdTree {
+            root: None,
+            size: 0,
+        }
+    }
+
+    // 批量建树得到平衡的树；逐个插入不做平衡，插入顺序不好时会退化
+    pub fn from_points(items: Vec<([f64; K], V)>) -> Result<Self, String> {
+        for (point, _) in &items {
+            check_point(point)?;
+        }
+        let size = items.len();
+        Ok(KdTree {
+            root: build(items, 0),
+            size,
+        })
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // 允许重复的点，相同坐标放到右子树
+    pub fn insert(&mut self, point: [f64; K], value: V) -> Result<(), String> {
+        check_point(&point)?;
+        let mut link = &mut self.root;
+        let mut depth = 0;
+        while let Some(node) = link {
+            link = if point[node.axis] < node.point[node.axis] {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+            depth += 1;
+        }
+        *link = Some(Box::new(Node {
+            point,
+            value,
+            axis: depth % K,
+            left: None,
+            right: None,
+        }));
+        self.size += 1;
+        Ok(())
+    }
+
+    // 切分值相同的点可能在任意一侧，坐标等于切分值时两侧都要找
+    pub fn get(&self, point: &[f64; K]) -> Option<&V> {
+        check_point(point).ok()?;
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.as_deref());
+        while let Some(node) = stack.pop() {
+            if node.point == *point {
+                return Some(&node.value);
+            }
+            let (coordinate, split) = (point[node.axis], node.point[node.axis]);
+            if coordinate <= split {
+                stack.extend(node.left.as_deref());
+            }
+            if coordinate >= split {
+                stack.extend(node.right.as_deref());
+            }
+        }
+        None
+    }
+
+    pub fn contains(&self, point: &[f64; K]) -> bool {
+        self.get(point).is_some()
+    }
+
+    // 最近的 k 个点，按距离从近到远；距离相同时先后顺序不作保证
+    pub fn nearest(&self, query: &[f64; K], k: usize) -> Result<Vec<Neighbor<'_, K, V>>, String> {
+        check_point(query)?;
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        let mut best: BinaryHeap<Candidate<'_, K, V>> = BinaryHeap::with_capacity(k + 1);
+        // 栈里同时记下子树到查询点的距离下界（切分平面的距离平方），弹出时再判断是否剪枝
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.as_deref().map(|node| (node, 0.0)));
+        while let Some((node, bound)) = stack.pop() {
+            if best.len() == k && bound >= best.peek().unwrap().distance {
+                continue;
+            }
+            let distance = squared_distance(&node.point, query);
+            if best.len() < k {
+                best.push(Candidate { distance, node });
+            } else if distance < best.peek().unwrap().distance {
+                best.pop();
+                best.push(Candidate { distance, node });
+            }
+            let difference = query[node.axis] - node.point[node.axis];
+            let (near, far) = if difference < 0.0 {
+                (&node.left, &node.right)
+            } else {
+                (&node.right, &node.left)
+            };
+            stack.extend(far.as_deref().map(|far| (far, difference * difference)));
+            stack.extend(near.as_deref().map(|near| (near, 0.0)));
+        }
+        Ok(best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|candidate| {
+                let node = candidate.node;
+                (&node.point, &node.value, candidate.distance.sqrt())
+            })
+            .collect())
+    }
+
+    pub fn nearest_one(&self, query: &[f64; K]) -> Result<Option<Neighbor<'_, K, V>>, String> {
+        Ok(self.nearest(query, 1)?.into_iter().next())
+    }
+
+    // 与查询点距离不超过 radius 的所有点，按距离从近到远
+    pub fn within_radius(
+        &self,
+        query: &[f64; K],
+        radius: f64,
+    ) -> Result<Vec<Neighbor<'_, K, V>>, String> {
+        check_point(query)?;
+        if radius.is_nan() || radius < 0.0 {
+            return Err("Radius must be a non-negative number.".to_owned());
+        }
+        let limit = radius * radius;
+        let mut found = Vec::new();
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.as_deref());
+        while let Some(node) = stack.pop() {
+            let distance = squared_distance(&node.point, query);
+            if distance <= limit {
+                found.push(Candidate { distance, node });
+            }
+            let difference = query[node.axis] - node.point[node.axis];
+            if difference <= radius {
+                stack.extend(node.left.as_deref());
+            }
+            if difference >= -radius {
+                stack.extend(node.right.as_deref());
+            }
+        }
+        found.sort();
+        Ok(found
+            .into_iter()
+            .map(|candidate| {
+                let node = candidate.node;
+                (&node.point, &node.value, candidate.distance.sqrt())
+            })
+            .collect())
+    }
+
+    // 落在轴对齐盒子 [min, max] 内（含边界）的所有点，按到盒子中心的距离从近到远
+    pub fn range(&self, min: &[f64; K], max: &[f64; K]) -> Result<Vec<(&[f64; K], &V)>, String> {
+        check_point(min)?;
+        check_point(max)?;
+        let centre: [f64; K] = std::array::from_fn(|axis| (min[axis] + max[axis]) / 2.0);
+        let mut found = Vec::new();
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.as_deref());
+        while let Some(node) = stack.pop() {
+            let inside =
+                (0..K).all(|axis| min[axis] <= node.point[axis] && node.point[axis] <= max[axis]);
+            if inside {
+                let distance = squared_distance(&node.point, &centre);
+                found.push(Candidate { distance, node });
+            }
+            if min[node.axis] <= node.point[node.axis] {
+                stack.extend(node.left.as_deref());
+            }
+            if max[node.axis] >= node.point[node.axis] {
+                stack.extend(node.right.as_deref());
+            }
+        }
+        found.sort();
+        Ok(found
+            .into_iter()
+            .map(|candidate| (&candidate.node.point, &candidate.node.value))
+            .collect())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&[f64; K], &V)> {
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.as_deref());
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.right.as_deref());
+            stack.extend(node.left.as_deref());
+            Some((&node.point, &node.value))
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut queue = ArrayQueue::new();
+        queue.extend(self.root.as_deref());
+        while !queue.is_empty() {
+            height += 1;
+            for _ in 0..queue.get_size() {
+                let node = queue.dequeue().unwrap();
+                queue.extend(node.left.as_deref());
+                queue.extend(node.right.as_deref());
+            }
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        drop_links(self.root.take());
+        self.size = 0;
+    }
+
+    // 每个节点都要和所有祖先的切分平面比较：栈里带着从根到当前节点累积的每一维上下界
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let mut count = 0;
+        let mut stack = ArrayStack::new();
+        stack.extend(
+            self.root
+                .as_deref()
+                .map(|node| (node, 0, [f64::NEG_INFINITY; K], [f64::INFINITY; K])),
+        );
+        while let Some((node, depth, lower, upper)) = stack.pop() {
+            count += 1;
+            if node.axis != depth % K {
+                return Err(format!(
+                    "Axis mismatch at depth {}: recorded {}, expected {}.",
+                    depth,
+                    node.axis,
+                    depth % K
+                ));
+            }
+            if (0..K).any(|axis| node.point[axis] < lower[axis] || node.point[axis] > upper[axis]) {
+                return Err(format!(
+                    "Point at depth {} is on the wrong side of an ancestor's split.",
+                    depth
+                ));
+            }
+            let split = node.point[node.axis];
+            if let Some(left) = node.left.as_deref() {
+                let mut upper = upper;
+                upper[node.axis] = split;
+                stack.push((left, depth + 1, lower, upper));
+            }
+            if let Some(right) = node.right.as_deref() {
+                let mut lower = lower;
+                lower[node.axis] = split;
+                stack.push((right, depth + 1, lower, upper));
+            }
+        }
+        if count != self.size {
+            return Err(format!(
+                "Size mismatch: recorded {}, counted {}.",
+                self.size, count
+            ));
+        }
+        Ok(())
+    }
+}
+
+// 逐个插入有序的点时树是一条长链，逐个节点释放避免递归 drop 爆栈
+fn drop_links<const K: usize, V>(root: Link<K, V>) {
+    let mut stack = ArrayStack::new();
+    stack.extend(root);
+    while let Some(mut node) = stack.pop() {
+        stack.extend(node.left.take());
+        stack.extend(node.right.take());
+    }
+}
+
+impl<const K: usize, V> Drop for KdTree<K, V> {
+    fn drop(&mut self) {
+        drop_links(self.root.take());
+    }
+}
+
+impl<const K: usize, V> Default for KdTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize, V> Debug for KdTree<K, V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    // 坐标取小整数，制造大量重复的切分值
+    fn random_point(rng: &mut TestRng) -> [f64; 2] {
+        [rng.below(10) as f64, rng.below(10) as f64]
+    }
+
+    fn sorted_distances(points: &[[f64; 2]], query: &[f64; 2]) -> Vec<f64> {
+        let mut distances: Vec<f64> = points
+            .iter()
+            .map(|point| squared_distance(point, query).sqrt())
+            .collect();
+        distances.sort_by(f64::total_cmp);
+        distances
+    }
+
+    #[test]
+    fn random_queries_match_brute_force() {
+        let mut rng = TestRng::with_seed(50);
+        for round in 0..20 {
+            let points: Vec<[f64; 2]> = (0..60).map(|_| random_point(&mut rng)).collect();
+            let tree = if round % 2 == 0 {
+                let items = points.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+                KdTree::from_points(items).unwrap()
+            } else {
+                let mut tree = KdTree::new();
+                for (i, &point) in points.iter().enumerate() {
+                    tree.insert(point, i).unwrap();
+                    tree.check_invariants().unwrap();
+                }
+                tree
+            };
+            tree.check_invariants().unwrap();
+            assert_eq!(tree.get_size(), points.len());
+
+            for _ in 0..10 {
+                let query = random_point(&mut rng);
+                let expected = sorted_distances(&points, &query);
+
+                let k = rng.index(8);
+                let nearest: Vec<f64> = tree
+                    .nearest(&query, k)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(_, _, distance)| distance)
+                    .collect();
+                assert_eq!(nearest, expected[..k]);
+
+                let (_, _, distance) = tree.nearest_one(&query).unwrap().unwrap();
+                assert_eq!(distance, expected[0]);
+
+                let radius = rng.below(4) as f64;
+                let within: Vec<f64> = tree
+                    .within_radius(&query, radius)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(_, _, distance)| distance)
+                    .collect();
+                let inside: Vec<f64> = expected.into_iter().filter(|&d| d <= radius).collect();
+                assert_eq!(within, inside);
+
+                let (min, max) = (random_point(&mut rng), random_point(&mut rng));
+                let centre = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+                let found = tree.range(&min, &max).unwrap();
+                let mut values: Vec<usize> = found.iter().map(|&(_, &value)| value).collect();
+                values.sort_unstable();
+                let expected: Vec<usize> = (0..points.len())
+                    .filter(|&i| (0..2).all(|a| min[a] <= points[i][a] && points[i][a] <= max[a]))
+                    .collect();
+                assert_eq!(values, expected);
+                let distances: Vec<f64> = found
+                    .iter()
+                    .map(|(point, _)| squared_distance(point, &centre))
+                    .collect();
+                assert!(distances.is_sorted());
+
+                assert_eq!(tree.contains(&query), points.contains(&query));
+                if let Some(&value) = tree.get(&query) {
+                    assert_eq!(points[value], query);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_one_reports_invalid_query() {
+        let empty: KdTree<2, ()> = KdTree::new();
+        assert_eq!(empty.nearest_one(&[0.0, 0.0]).unwrap(), None);
+        let tree = KdTree::from_points(vec![([1.0, 2.0], 'a')]).unwrap();
+        assert!(tree.nearest_one(&[f64::NAN, 0.0]).is_err());
+    }
+
+    #[test]
+    fn range_rejects_nan_bounds() {
+        let tree = KdTree::from_points(vec![([1.0, 2.0], 'a')]).unwrap();
+        assert!(tree.range(&[f64::NAN, 0.0], &[3.0, 3.0]).is_err());
+        assert!(tree.range(&[0.0, 0.0], &[3.0, f64::NAN]).is_err());
+        assert_eq!(tree.range(&[0.0, 0.0], &[3.0, 3.0]).unwrap().len(), 1);
+        assert_eq!(tree.get(&[f64::NAN, 2.0]), None);
+    }
+
+    #[test]
+    fn get_finds_points_on_both_sides_of_a_split() {
+        // 中位数建树时和根坐标相同的点会分到两侧
+        let items = (0..9).map(|i| ([5.0, i as f64], i)).collect();
+        let tree = KdTree::from_points(items).unwrap();
+        for i in 0..9 {
+            assert_eq!(tree.get(&[5.0, i as f64]), Some(&i));
+        }
+        assert_eq!(tree.get(&[5.0, 9.0]), None);
+    }
+
+    // 逐个插入 20 万个有序的点是平方复杂度，这里直接拼出同样形状的链
+    fn chain(len: usize) -> KdTree<1, usize> {
+        let mut root: Link<1, usize> = None;
+        for i in (0..len).rev() {
+            root = Some(Box::new(Node {
+                point: [i as f64],
+                value: i,
+                axis: 0,
+                left: None,
+                right: root,
+            }));
+        }
+        KdTree { root, size: len }
+    }
+
+    #[test]
+    fn dropping_a_long_chain_does_not_overflow() {
+        let mut tree = chain(200_000);
+        assert_eq!(tree.height(), 200_000);
+        assert_eq!(tree.get(&[199_999.0]), Some(&199_999));
+        tree.clear();
+        assert!(tree.is_empty());
+        tree.insert([1.0], 1).unwrap();
+        assert_eq!(tree.get(&[1.0]), Some(&1));
+
+        drop(chain(200_000));
+    }
+}
@@ -0,0 +1,629 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, cmp::Ordering, fmt::Debug, ops::RangeBounds};
+
+use super::ordered_map::OrderedMap;
+use super::search_tree::{self, IterMut, Range, SearchNode, SplitMut};
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+impl Color {
+    fn flip(self) -> Color {
+        match self {
+            Color::Red => Color::Black,
+            Color::Black => Color::Red,
+        }
+    }
+}
+
+pub struct Node<K, V> {
+    key: K,
+    value: V,
+    // 指向该节点的链接的颜色
+    color: Color,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            color: Color::Red,
+            left: None,
+            right: None,
+        })
+    }
+}
+
+impl<K, V> SearchNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.key,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+fn is_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| node.color == Color::Red)
+}
+
+fn is_left_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| is_red(&node.left))
+}
+
+// 左倾红黑树和 2-3 树一一对应：红链接只能是左链接，不能有连续两条红链接
+struct Balancer {
+    rotations: usize,
+}
+
+impl Balancer {
+    fn rotate_left<K, V>(&mut self, mut h: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        let mut x = h.right.take().unwrap();
+        h.right = x.left.take();
+        x.color = h.color;
+        h.color = Color::Red;
+        x.left = Some(h);
+        self.rotations += 1;
+        x
+    }
+
+    fn rotate_right<K, V>(&mut self, mut h: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        let mut x = h.left.take().unwrap();
+        h.left = x.right.take();
+        x.color = h.color;
+        h.color = Color::Red;
+        x.right = Some(h);
+        self.rotations += 1;
+        x
+    }
+
+    // 对应 2-3 树里 4-节点的分裂（插入）或合并（删除）
+    fn flip_colors<K, V>(h: &mut Node<K, V>) {
+        h.color = h.color.flip();
+        for child in [h.left.as_mut(), h.right.as_mut()].into_iter().flatten() {
+            child.color = child.color.flip();
+        }
+    }
+
+    // 回溯时消除右倾红链接和连续红链接，再分裂临时的 4-节点
+    fn fix_up<K, V>(&mut self, mut h: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        if is_red(&h.right) && !is_red(&h.left) {
+            h = self.rotate_left(h);
+        }
+        if is_red(&h.left) && is_left_red(&h.left) {
+            h = self.rotate_right(h);
+        }
+        if is_red(&h.left) && is_red(&h.right) {
+            Self::flip_colors(&mut h);
+        }
+        h
+    }
+
+    fn insert<K: Ord, V>(
+        &mut self,
+        link: Link<K, V>,
+        key: K,
+        value: V,
+    ) -> (Box<Node<K, V>>, Option<V>) {
+        let Some(mut h) = link else {
+            return (Node::new(key, value), None);
+        };
+        let old = match key.cmp(&h.key) {
+            Ordering::Less => {
+                let (left, old) = self.insert(h.left.take(), key, value);
+                h.left = Some(left);
+                old
+            }
+            Ordering::Greater => {
+                let (right, old) = self.insert(h.right.take(), key, value);
+                h.right = Some(right);
+                old
+            }
+            Ordering::Equal => Some(std::mem::replace(&mut h.value, value)),
+        };
+        (self.fix_up(h), old)
+    }
+
+    // 保证 h 或者 h.left 是红色，这样往左走时当前节点不会是 2-节点
+    fn move_red_left<K, V>(&mut self, mut h: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        Self::flip_colors(&mut h);
+        if is_left_red(&h.right) {
+            h.right = Some(self.rotate_right(h.right.take().unwrap()));
+            h = self.rotate_left(h);
+            Self::flip_colors(&mut h);
+        }
+        h
+    }
+
+    fn move_red_right<K, V>(&mut self, mut h: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        Self::flip_colors(&mut h);
+        if is_left_red(&h.left) {
+            h = self.rotate_right(h);
+            Self::flip_colors(&mut h);
+        }
+        h
+    }
+
+    // 返回删除后的子树和被摘下的最小节点
+    fn delete_min<K, V>(&mut self, mut h: Box<Node<K, V>>) -> (Link<K, V>, Box<Node<K, V>>) {
+        if h.left.is_none() {
+            return (h.right.take(), h);
+        }
+        if !is_red(&h.left) && !is_left_red(&h.left) {
+            h = self.move_red_left(h);
+        }
+        let (left, min) = self.delete_min(h.left.take().unwrap());
+        h.left = left;
+        (Some(self.fix_up(h)), min)
+    }
+
+    fn delete_max<K, V>(&mut self, mut h: Box<Node<K, V>>) -> (Link<K, V>, Box<Node<K, V>>) {
+        if is_red(&h.left) {
+            h = self.rotate_right(h);
+        }
+        if h.right.is_none() {
+            return (h.left.take(), h);
+        }
+        if !is_red(&h.right) && !is_left_red(&h.right) {
+            h = self.move_red_right(h);
+        }
+        let (right, max) = self.delete_max(h.right.take().unwrap());
+        h.right = right;
+        (Some(self.fix_up(h)), max)
+    }
+
+    // 调用前已确认 key 在树里
+    fn delete<K, V, Q>(&mut self, mut h: Box<Node<K, V>>, key: &Q) -> (Link<K, V>, (K, V))
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed;
+        if key < h.key.borrow() {
+            if !is_red(&h.left) && !is_left_red(&h.left) {
+                h = self.move_red_left(h);
+            }
+            let (left, entry) = self.delete(h.left.take().unwrap(), key);
+            h.left = left;
+            removed = entry;
+        } else {
+            if is_red(&h.left) {
+                h = self.rotate_right(h);
+            }
+            if key == h.key.borrow() && h.right.is_none() {
+                let node = *h;
+                return (node.left, (node.key, node.value));
+            }
+            if !is_red(&h.right) && !is_left_red(&h.right) {
+                h = self.move_red_right(h);
+            }
+            if key == h.key.borrow() {
+                // 用右子树的最小节点顶替当前节点
+                let (right, mut min) = self.delete_min(h.right.take().unwrap());
+                h.right = right;
+                std::mem::swap(&mut h.key, &mut min.key);
+                std::mem::swap(&mut h.value, &mut min.value);
+                removed = (min.key, min.value);
+            } else {
+                let (right, entry) = self.delete(h.right.take().unwrap(), key);
+                h.right = right;
+                removed = entry;
+            }
+        }
+        (Some(self.fix_up(h)), removed)
+    }
+}
+
+// 返回子树的黑高
+fn check_colors<K, V>(link: &Link<K, V>) -> Result<usize, String> {
+    let Some(node) = link else {
+        return Ok(1);
+    };
+    if is_red(&node.right) {
+        return Err("Right-leaning red link.".to_owned());
+    }
+    if node.color == Color::Red && is_red(&node.left) {
+        return Err("Two red links in a row.".to_owned());
+    }
+    let left = check_colors(&node.left)?;
+    let right = check_colors(&node.right)?;
+    if left != right {
+        return Err(format!(
+            "Black height mismatch: left {}, right {}.",
+            left, right
+        ));
+    }
+    Ok(left + usize::from(node.color == Color::Black))
+}
+
+// Sedgewick 的左倾红黑树，插入删除都写成递归，代码比经典红黑树短得多
+pub struct LlrbMap<K, V> {
+    root: Link<K, V>,
+    size: usize,
+    balancer: Balancer,
+}
+
+impl<K, V> LlrbMap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        LlrbMap {
+            root: None,
+            size: 0,
+            balancer: Balancer { rotations: 0 },
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_rotation_count(&self) -> usize {
+        self.balancer.rotations
+    }
+
+    fn blacken_root(&mut self) {
+        if let Some(root) = self.root.as_mut() {
+            root.color = Color::Black;
+        }
+    }
+
+    // 根的两个孩子都是黑色时先把根染红，让删除从一个 3-节点开始往下走
+    fn redden_root(&mut self) {
+        if let Some(root) = self.root.as_mut()
+            && !is_red(&root.left)
+            && !is_red(&root.right)
+        {
+            root.color = Color::Red;
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (root, old) = self.balancer.insert(self.root.take(), key, value);
+        self.root = Some(root);
+        self.blacken_root();
+        if old.is_none() {
+            self.size += 1;
+        }
+        old
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(search_tree::entry)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.contains_key(key) {
+            return None;
+        }
+        self.redden_root();
+        let (root, removed) = self.balancer.delete(self.root.take().unwrap(), key);
+        self.root = root;
+        self.blacken_root();
+        self.size -= 1;
+        Some(removed)
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        self.redden_root();
+        let (root, min) = self.balancer.delete_min(self.root.take().unwrap());
+        self.root = root;
+        self.blacken_root();
+        self.size -= 1;
+        Some((min.key, min.value))
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        self.redden_root();
+        let (root, max) = self.balancer.delete_max(self.root.take().unwrap());
+        self.root = root;
+        self.blacken_root();
+        self.size -= 1;
+        Some((max.key, max.value))
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn successor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn iter(&self) -> Range<'_, Node<K, V>> {
+        search_tree::iter(self.root.as_deref())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        search_tree::range(self.root.as_deref(), range)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Node<K, V>> {
+        search_tree::iter_mut(self.root.as_deref_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    pub fn height(&self) -> usize {
+        search_tree::height(self.root.as_deref())
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.size = 0;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if is_red(&self.root) {
+            return Err("Root is not black.".to_owned());
+        }
+        search_tree::check_order(self.root.as_deref(), self.size)?;
+        check_colors(&self.root)?;
+        Ok(())
+    }
+}
+
+impl<K, V> Default for LlrbMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for LlrbMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = LlrbMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for LlrbMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LlrbMap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, Node<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for LlrbMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for LlrbMap<K, V> {
+    fn get_name(&self) -> &'static str {
+        "LLRB"
+    }
+
+    fn get_size(&self) -> usize {
+        self.size
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        LlrbMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        LlrbMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        LlrbMap::remove(self, key)
+    }
+
+    fn min(&self) -> Option<(&K, &V)> {
+        LlrbMap::min(self)
+    }
+
+    fn max(&self) -> Option<(&K, &V)> {
+        LlrbMap::max(self)
+    }
+
+    fn pop_min(&mut self) -> Option<(K, V)> {
+        LlrbMap::pop_min(self)
+    }
+
+    fn pop_max(&mut self) -> Option<(K, V)> {
+        LlrbMap::pop_max(self)
+    }
+
+    fn height(&self) -> usize {
+        LlrbMap::height(self)
+    }
+
+    fn get_rotation_count(&self) -> usize {
+        self.balancer.rotations
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        LlrbMap::check_invariants(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::LlrbMap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(42);
+        let mut map = LlrbMap::new();
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            match random.below(8) {
+                0..=2 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                3 => assert_eq!(map.pop_min(), expected.pop_first()),
+                4 => assert_eq!(map.pop_max(), expected.pop_last()),
+                _ => assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2)),
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+        let (start, end) = (150, 50);
+        assert_eq!(map.range(start..end).count(), 0);
+    }
+}
@@ -0,0 +1,19 @@
+pub mod avl_map;
+pub mod avl_set;
+pub mod avl_tree;
+pub mod binary_tree;
+pub mod bplus_tree;
+pub mod bst_map;
+pub mod btree;
+pub mod centered_interval_tree;
+pub mod implicit_treap;
+pub mod interval_tree;
+pub mod kd_tree;
+pub mod llrb_map;
+pub mod order_statistic_map;
+pub mod order_statistic_set;
+pub mod ordered_map;
+pub mod red_black_map;
+pub mod search_tree;
+pub mod splay_tree;
+pub mod treap;
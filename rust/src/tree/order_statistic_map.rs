@@ -0,0 +1,503 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Bound, RangeBounds},
+};
+
+use super::avl_tree::{self, AvlNode};
+use super::ordered_map::OrderedMap;
+use super::search_tree::{self, IterMut, Range, SearchNode, SplitMut};
+
+type Link<K, V> = avl_tree::Link<Node<K, V>>;
+
+pub struct Node<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    // 以该节点为根的子树节点数，排名和选择都靠它
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> SearchNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.key,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+impl<K, V> AvlNode for Node<K, V> {
+    fn leaf(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            height: 1,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn left_link(&mut self) -> &mut Link<K, V> {
+        &mut self.left
+    }
+
+    fn right_link(&mut self) -> &mut Link<K, V> {
+        &mut self.right
+    }
+
+    // 旋转和删改之后自底向上同时刷新高度和子树大小
+    fn update(&mut self) {
+        self.height = 1 + avl_tree::height_of(self.left()).max(avl_tree::height_of(self.right()));
+        self.size = 1 + size_of(&self.left) + size_of(&self.right);
+    }
+}
+
+fn size_of<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+// 键小于 key（inclusive 时为小于等于）的节点个数：往右走时把左子树和当前节点都计入
+fn count_below<K, V, Q>(root: &Link<K, V>, key: &Q, inclusive: bool) -> usize
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut count = 0;
+    let mut current = root.as_deref();
+    while let Some(node) = current {
+        let go_right = match node.key.borrow().cmp(key) {
+            Ordering::Less => true,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => false,
+        };
+        if go_right {
+            count += size_of(&node.left) + 1;
+            current = node.right.as_deref();
+        } else {
+            current = node.left.as_deref();
+        }
+    }
+    count
+}
+
+// 后序核对每个节点记录的子树大小，返回子树节点数
+fn check_sizes<K, V>(link: &Link<K, V>) -> Result<usize, String> {
+    let Some(node) = link else {
+        return Ok(0);
+    };
+    let size = 1 + check_sizes(&node.left)? + check_sizes(&node.right)?;
+    if node.size != size {
+        return Err(format!(
+            "Subtree size mismatch: recorded {}, actual {}.",
+            node.size, size
+        ));
+    }
+    Ok(size)
+}
+
+// 顺序统计树：在 AVL 树的每个节点上多记一个子树大小，
+// 第 k 小的键和某个键的排名都能沿一条根到叶的路径在 O(log n) 内算出
+pub struct OrderStatisticMap<K, V> {
+    root: Link<K, V>,
+    rotations: usize,
+}
+
+impl<K, V> OrderStatisticMap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        OrderStatisticMap {
+            root: None,
+            rotations: 0,
+        }
+    }
+
+    // 根节点的子树大小就是元素个数
+    pub fn get_size(&self) -> usize {
+        size_of(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        avl_tree::insert(&mut self.root, key, value, &mut self.rotations)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(search_tree::entry)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let compare = |node: &Node<K, V>| key.cmp(node.key.borrow());
+        let node = avl_tree::remove(&mut self.root, &compare, &mut self.rotations)?;
+        Some((node.key, node.value))
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        let node = avl_tree::take_min(&mut self.root, &mut self.rotations);
+        Some((node.key, node.value))
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        self.root.as_ref()?;
+        let node = avl_tree::take_max(&mut self.root, &mut self.rotations);
+        Some((node.key, node.value))
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    pub fn successor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, false).map(search_tree::entry)
+    }
+
+    // 第 index 小的元素（从 0 开始）：index 落在左子树就往左，否则减掉左子树和当前节点往右
+    pub fn select(&self, index: usize) -> Option<(&K, &V)> {
+        let mut index = index;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let left_size = size_of(&node.left);
+            match index.cmp(&left_size) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    current = node.right.as_deref();
+                }
+            }
+        }
+        None
+    }
+
+    // 严格小于 key 的元素个数；key 存在时恰好是它在有序序列里的下标
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        count_below(&self.root, key, false)
+    }
+
+    // 落在区间内的元素个数，等于上界的排名减去下界的排名
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => count_below(&self.root, key, false),
+            Bound::Excluded(key) => count_below(&self.root, key, true),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => count_below(&self.root, key, true),
+            Bound::Excluded(key) => count_below(&self.root, key, false),
+            Bound::Unbounded => self.get_size(),
+        };
+        end.saturating_sub(start)
+    }
+
+    pub fn iter(&self) -> Range<'_, Node<K, V>> {
+        search_tree::iter(self.root.as_deref())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        search_tree::range(self.root.as_deref(), range)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Node<K, V>> {
+        search_tree::iter_mut(self.root.as_deref_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    pub fn height(&self) -> usize {
+        avl_tree::height_of(self.root.as_deref())
+    }
+
+    pub fn get_rotation_count(&self) -> usize {
+        self.rotations
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        search_tree::check_order(self.root.as_deref(), self.get_size())?;
+        avl_tree::check_balance(self.root.as_deref())?;
+        check_sizes(&self.root)?;
+        Ok(())
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for OrderStatisticMap<K, V> {
+    fn get_name(&self) -> &'static str {
+        "Order-Statistic"
+    }
+
+    fn get_size(&self) -> usize {
+        OrderStatisticMap::get_size(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        OrderStatisticMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        OrderStatisticMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        OrderStatisticMap::remove(self, key)
+    }
+
+    fn min(&self) -> Option<(&K, &V)> {
+        OrderStatisticMap::min(self)
+    }
+
+    fn max(&self) -> Option<(&K, &V)> {
+        OrderStatisticMap::max(self)
+    }
+
+    fn pop_min(&mut self) -> Option<(K, V)> {
+        OrderStatisticMap::pop_min(self)
+    }
+
+    fn pop_max(&mut self) -> Option<(K, V)> {
+        OrderStatisticMap::pop_max(self)
+    }
+
+    fn height(&self) -> usize {
+        OrderStatisticMap::height(self)
+    }
+
+    fn get_rotation_count(&self) -> usize {
+        OrderStatisticMap::get_rotation_count(self)
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        OrderStatisticMap::check_invariants(self)
+    }
+}
+
+impl<K, V> Default for OrderStatisticMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for OrderStatisticMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderStatisticMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for OrderStatisticMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderStatisticMap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, Node<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for OrderStatisticMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::OrderStatisticMap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(45);
+        let mut map = OrderStatisticMap::new();
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            match random.below(8) {
+                0..=2 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                3 => assert_eq!(map.pop_min(), expected.pop_first()),
+                4 => assert_eq!(map.pop_max(), expected.pop_last()),
+                _ => assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2)),
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+            assert_eq!(map.rank(&key), expected.range(..key).count());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        for (index, entry) in expected.iter().enumerate() {
+            assert_eq!(map.select(index), Some(entry));
+        }
+        assert_eq!(map.select(expected.len()), None);
+        assert_eq!(map.count_range(50..150), expected.range(50..150).count());
+        let (start, end) = (150, 50);
+        assert_eq!(map.count_range(start..end), 0);
+    }
+}
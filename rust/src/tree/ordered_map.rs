@@ -0,0 +1,139 @@
+#![allow(dead_code)]
+
+use super::{avl_map::AvlMap, bst_map::BstMap};
+
+// 各种有序映射的公共接口，基准测试用它在相同负载下比较树高和旋转次数
+pub trait OrderedMap<K, V> {
+    fn get_name(&self) -> &'static str;
+
+    fn get_size(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.get_size() == 0
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    fn get(&self, key: &K) -> Option<&V>;
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    fn min(&self) -> Option<(&K, &V)>;
+
+    fn max(&self) -> Option<(&K, &V)>;
+
+    fn pop_min(&mut self) -> Option<(K, V)>;
+
+    fn pop_max(&mut self) -> Option<(K, V)>;
+
+    fn height(&self) -> usize;
+
+    // 不做平衡的树恒为 0
+    fn get_rotation_count(&self) -> usize;
+
+    fn check_invariants(&self) -> Result<(), String>;
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for BstMap<K, V> {
+    fn get_name(&self) -> &'static str {
+        "BST"
+    }
+
+    fn get_size(&self) -> usize {
+        BstMap::get_size(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BstMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        BstMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BstMap::remove(self, key)
+    }
+
+    fn min(&self) -> Option<(&K, &V)> {
+        BstMap::min(self)
+    }
+
+    fn max(&self) -> Option<(&K, &V)> {
+        BstMap::max(self)
+    }
+
+    fn pop_min(&mut self) -> Option<(K, V)> {
+        BstMap::pop_min(self)
+    }
+
+    fn pop_max(&mut self) -> Option<(K, V)> {
+        BstMap::pop_max(self)
+    }
+
+    fn height(&self) -> usize {
+        BstMap::height(self)
+    }
+
+    fn get_rotation_count(&self) -> usize {
+        0
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        BstMap::check_invariants(self)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for AvlMap<K, V> {
+    fn get_name(&self) -> &'static str {
+        "AVL"
+    }
+
+    fn get_size(&self) -> usize {
+        AvlMap::get_size(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        AvlMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        AvlMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        AvlMap::remove(self, key)
+    }
+
+    fn min(&self) -> Option<(&K, &V)> {
+        AvlMap::min(self)
+    }
+
+    fn max(&self) -> Option<(&K, &V)> {
+        AvlMap::max(self)
+    }
+
+    fn pop_min(&mut self) -> Option<(K, V)> {
+        AvlMap::pop_min(self)
+    }
+
+    fn pop_max(&mut self) -> Option<(K, V)> {
+        AvlMap::pop_max(self)
+    }
+
+    fn height(&self) -> usize {
+        AvlMap::height(self)
+    }
+
+    fn get_rotation_count(&self) -> usize {
+        AvlMap::get_rotation_count(self)
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        AvlMap::check_invariants(self)
+    }
+}
@@ -0,0 +1,896 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Bound, RangeBounds},
+};
+
+use super::ordered_map::OrderedMap;
+
+type NodeId = usize;
+
+// 0 号节点就是 CLRS 里的哨兵 T.nil：永远是黑色，删除时可以临时记录父节点
+const NIL: NodeId = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+struct Node<K, V> {
+    entry: Option<(K, V)>,
+    color: Color,
+    parent: NodeId,
+    left: NodeId,
+    right: NodeId,
+}
+
+impl<K, V> Node<K, V> {
+    fn nil() -> Self {
+        Node {
+            entry: None,
+            color: Color::Black,
+            parent: NIL,
+            left: NIL,
+            right: NIL,
+        }
+    }
+}
+
+// 按《算法导论》实现的红黑树：节点放在数组里，带父指针，插入和删除后分情况做修复
+pub struct RedBlackMap<K, V> {
+    nodes: Vec<Node<K, V>>,
+    // 删除后空出来的槽位
+    free: Vec<NodeId>,
+    root: NodeId,
+    size: usize,
+    rotations: usize,
+}
+
+impl<K, V> RedBlackMap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        RedBlackMap {
+            nodes: vec![Node::nil()],
+            free: Vec::new(),
+            root: NIL,
+            size: 0,
+            rotations: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_rotation_count(&self) -> usize {
+        self.rotations
+    }
+
+    fn key(&self, x: NodeId) -> &K {
+        &self.nodes[x].entry.as_ref().unwrap().0
+    }
+
+    fn entry(&self, x: NodeId) -> (&K, &V) {
+        let (key, value) = self.nodes[x].entry.as_ref().unwrap();
+        (key, value)
+    }
+
+    fn color(&self, x: NodeId) -> Color {
+        self.nodes[x].color
+    }
+
+    fn parent(&self, x: NodeId) -> NodeId {
+        self.nodes[x].parent
+    }
+
+    fn left(&self, x: NodeId) -> NodeId {
+        self.nodes[x].left
+    }
+
+    fn right(&self, x: NodeId) -> NodeId {
+        self.nodes[x].right
+    }
+
+    fn allocate(&mut self, key: K, value: V) -> NodeId {
+        let node = Node {
+            entry: Some((key, value)),
+            color: Color::Red,
+            parent: NIL,
+            left: NIL,
+            right: NIL,
+        };
+        match self.free.pop() {
+            Some(x) => {
+                self.nodes[x] = node;
+                x
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, x: NodeId) -> (K, V) {
+        let node = std::mem::replace(&mut self.nodes[x], Node::nil());
+        self.free.push(x);
+        node.entry.unwrap()
+    }
+
+    fn find<Q>(&self, key: &Q) -> NodeId
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut x = self.root;
+        while x != NIL {
+            x = match key.cmp(self.key(x).borrow()) {
+                Ordering::Less => self.left(x),
+                Ordering::Greater => self.right(x),
+                Ordering::Equal => return x,
+            };
+        }
+        NIL
+    }
+
+    fn minimum(&self, mut x: NodeId) -> NodeId {
+        while x != NIL && self.left(x) != NIL {
+            x = self.left(x);
+        }
+        x
+    }
+
+    fn maximum(&self, mut x: NodeId) -> NodeId {
+        while x != NIL && self.right(x) != NIL {
+            x = self.right(x);
+        }
+        x
+    }
+
+    // 有右子树就取右子树的最小节点，否则沿父指针往上找第一个从左边上来的祖先
+    fn next_node(&self, mut x: NodeId) -> NodeId {
+        if self.right(x) != NIL {
+            return self.minimum(self.right(x));
+        }
+        let mut y = self.parent(x);
+        while y != NIL && x == self.right(y) {
+            x = y;
+            y = self.parent(y);
+        }
+        y
+    }
+
+    fn search_below<Q>(&self, key: &Q, inclusive: bool) -> NodeId
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut best = NIL;
+        let mut x = self.root;
+        while x != NIL {
+            match self.key(x).borrow().cmp(key) {
+                Ordering::Less => {
+                    best = x;
+                    x = self.right(x);
+                }
+                Ordering::Equal if inclusive => return x,
+                _ => x = self.left(x),
+            }
+        }
+        best
+    }
+
+    fn search_above<Q>(&self, key: &Q, inclusive: bool) -> NodeId
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut best = NIL;
+        let mut x = self.root;
+        while x != NIL {
+            match self.key(x).borrow().cmp(key) {
+                Ordering::Greater => {
+                    best = x;
+                    x = self.left(x);
+                }
+                Ordering::Equal if inclusive => return x,
+                _ => x = self.right(x),
+            }
+        }
+        best
+    }
+
+    //     x                 y
+    //    / \               / \
+    //   a   y     =>      x   c
+    //      / \           / \
+    //     b   c         a   b
+    fn left_rotate(&mut self, x: NodeId) {
+        let y = self.right(x);
+        let b = self.left(y);
+        self.nodes[x].right = b;
+        if b != NIL {
+            self.nodes[b].parent = x;
+        }
+        let p = self.parent(x);
+        self.nodes[y].parent = p;
+        if p == NIL {
+            self.root = y;
+        } else if x == self.left(p) {
+            self.nodes[p].left = y;
+        } else {
+            self.nodes[p].right = y;
+        }
+        self.nodes[y].left = x;
+        self.nodes[x].parent = y;
+        self.rotations += 1;
+    }
+
+    fn right_rotate(&mut self, x: NodeId) {
+        let y = self.left(x);
+        let b = self.right(y);
+        self.nodes[x].left = b;
+        if b != NIL {
+            self.nodes[b].parent = x;
+        }
+        let p = self.parent(x);
+        self.nodes[y].parent = p;
+        if p == NIL {
+            self.root = y;
+        } else if x == self.right(p) {
+            self.nodes[p].right = y;
+        } else {
+            self.nodes[p].left = y;
+        }
+        self.nodes[y].right = x;
+        self.nodes[x].parent = y;
+        self.rotations += 1;
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut parent = NIL;
+        let mut x = self.root;
+        let mut go_left = false;
+        while x != NIL {
+            parent = x;
+            match key.cmp(self.key(x)) {
+                Ordering::Less => {
+                    go_left = true;
+                    x = self.left(x);
+                }
+                Ordering::Greater => {
+                    go_left = false;
+                    x = self.right(x);
+                }
+                Ordering::Equal => {
+                    let entry = self.nodes[x].entry.as_mut().unwrap();
+                    return Some(std::mem::replace(&mut entry.1, value));
+                }
+            }
+        }
+        let z = self.allocate(key, value);
+        self.nodes[z].parent = parent;
+        if parent == NIL {
+            self.root = z;
+        } else if go_left {
+            self.nodes[parent].left = z;
+        } else {
+            self.nodes[parent].right = z;
+        }
+        self.size += 1;
+        self.insert_fixup(z);
+        None
+    }
+
+    // 新节点是红色，只可能破坏“红节点的孩子是黑色”：
+    // 叔叔是红色时把颜色推给祖父继续向上，否则最多两次旋转结束
+    fn insert_fixup(&mut self, mut z: NodeId) {
+        while self.color(self.parent(z)) == Color::Red {
+            let p = self.parent(z);
+            let g = self.parent(p);
+            if p == self.left(g) {
+                let uncle = self.right(g);
+                if self.color(uncle) == Color::Red {
+                    self.nodes[p].color = Color::Black;
+                    self.nodes[uncle].color = Color::Black;
+                    self.nodes[g].color = Color::Red;
+                    z = g;
+                } else {
+                    if z == self.right(p) {
+                        z = p;
+                        self.left_rotate(z);
+                    }
+                    let p = self.parent(z);
+                    let g = self.parent(p);
+                    self.nodes[p].color = Color::Black;
+                    self.nodes[g].color = Color::Red;
+                    self.right_rotate(g);
+                }
+            } else {
+                let uncle = self.left(g);
+                if self.color(uncle) == Color::Red {
+                    self.nodes[p].color = Color::Black;
+                    self.nodes[uncle].color = Color::Black;
+                    self.nodes[g].color = Color::Red;
+                    z = g;
+                } else {
+                    if z == self.left(p) {
+                        z = p;
+                        self.right_rotate(z);
+                    }
+                    let p = self.parent(z);
+                    let g = self.parent(p);
+                    self.nodes[p].color = Color::Black;
+                    self.nodes[g].color = Color::Red;
+                    self.left_rotate(g);
+                }
+            }
+        }
+        let root = self.root;
+        self.nodes[root].color = Color::Black;
+    }
+
+    // 用以 v 为根的子树替换以 u 为根的子树，v 是哨兵时也要记下父节点
+    fn transplant(&mut self, u: NodeId, v: NodeId) {
+        let p = self.parent(u);
+        if p == NIL {
+            self.root = v;
+        } else if u == self.left(p) {
+            self.nodes[p].left = v;
+        } else {
+            self.nodes[p].right = v;
+        }
+        self.nodes[v].parent = p;
+    }
+
+    fn delete(&mut self, z: NodeId) -> (K, V) {
+        let mut removed_color = self.color(z);
+        let x;
+        if self.left(z) == NIL {
+            x = self.right(z);
+            self.transplant(z, x);
+        } else if self.right(z) == NIL {
+            x = self.left(z);
+            self.transplant(z, x);
+        } else {
+            // 两个孩子都在时由后继 y 顶替 z，真正被摘走的是 y 原来的位置
+            let y = self.minimum(self.right(z));
+            removed_color = self.color(y);
+            x = self.right(y);
+            if self.parent(y) == z {
+                self.nodes[x].parent = y;
+            } else {
+                self.transplant(y, x);
+                let right = self.right(z);
+                self.nodes[y].right = right;
+                self.nodes[right].parent = y;
+            }
+            self.transplant(z, y);
+            let left = self.left(z);
+            self.nodes[y].left = left;
+            self.nodes[left].parent = y;
+            self.nodes[y].color = self.color(z);
+        }
+        if removed_color == Color::Black {
+            self.delete_fixup(x);
+        }
+        self.size -= 1;
+        self.release(z)
+    }
+
+    // x 多背了一层黑色，按兄弟 w 的颜色分四种情况把它消掉或者往上推
+    fn delete_fixup(&mut self, mut x: NodeId) {
+        while x != self.root && self.color(x) == Color::Black {
+            let p = self.parent(x);
+            if x == self.left(p) {
+                let mut w = self.right(p);
+                if self.color(w) == Color::Red {
+                    self.nodes[w].color = Color::Black;
+                    self.nodes[p].color = Color::Red;
+                    self.left_rotate(p);
+                    w = self.right(self.parent(x));
+                }
+                if self.color(self.left(w)) == Color::Black
+                    && self.color(self.right(w)) == Color::Black
+                {
+                    self.nodes[w].color = Color::Red;
+                    x = self.parent(x);
+                } else {
+                    if self.color(self.right(w)) == Color::Black {
+                        let left = self.left(w);
+                        self.nodes[left].color = Color::Black;
+                        self.nodes[w].color = Color::Red;
+                        self.right_rotate(w);
+                        w = self.right(self.parent(x));
+                    }
+                    let p = self.parent(x);
+                    self.nodes[w].color = self.color(p);
+                    self.nodes[p].color = Color::Black;
+                    let right = self.right(w);
+                    self.nodes[right].color = Color::Black;
+                    self.left_rotate(p);
+                    x = self.root;
+                }
+            } else {
+                let mut w = self.left(p);
+                if self.color(w) == Color::Red {
+                    self.nodes[w].color = Color::Black;
+                    self.nodes[p].color = Color::Red;
+                    self.right_rotate(p);
+                    w = self.left(self.parent(x));
+                }
+                if self.color(self.left(w)) == Color::Black
+                    && self.color(self.right(w)) == Color::Black
+                {
+                    self.nodes[w].color = Color::Red;
+                    x = self.parent(x);
+                } else {
+                    if self.color(self.left(w)) == Color::Black {
+                        let right = self.right(w);
+                        self.nodes[right].color = Color::Black;
+                        self.nodes[w].color = Color::Red;
+                        self.left_rotate(w);
+                        w = self.left(self.parent(x));
+                    }
+                    let p = self.parent(x);
+                    self.nodes[w].color = self.color(p);
+                    self.nodes[p].color = Color::Black;
+                    let left = self.left(w);
+                    self.nodes[left].color = Color::Black;
+                    self.right_rotate(p);
+                    x = self.root;
+                }
+            }
+        }
+        self.nodes[x].color = Color::Black;
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            NIL => None,
+            x => Some(self.entry(x)),
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            NIL => None,
+            x => self.nodes[x].entry.as_mut().map(|(_, value)| value),
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key) != NIL
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            NIL => None,
+            z => Some(self.delete(z)),
+        }
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        match self.minimum(self.root) {
+            NIL => None,
+            z => Some(self.delete(z)),
+        }
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        match self.maximum(self.root) {
+            NIL => None,
+            z => Some(self.delete(z)),
+        }
+    }
+
+    fn entry_of(&self, x: NodeId) -> Option<(&K, &V)> {
+        if x == NIL { None } else { Some(self.entry(x)) }
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.entry_of(self.minimum(self.root))
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        self.entry_of(self.maximum(self.root))
+    }
+
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entry_of(self.search_below(key, true))
+    }
+
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entry_of(self.search_above(key, true))
+    }
+
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entry_of(self.search_below(key, false))
+    }
+
+    pub fn successor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entry_of(self.search_above(key, false))
+    }
+
+    // 有父指针，遍历不需要栈，从起点沿后继一直走到终点
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            map: self,
+            next: self.minimum(self.root),
+            stop: NIL,
+        }
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Unbounded => self.minimum(self.root),
+            Bound::Included(start) => self.search_above(start, true),
+            Bound::Excluded(start) => self.search_above(start, false),
+        };
+        let stop = match range.end_bound() {
+            Bound::Unbounded => NIL,
+            Bound::Included(end) => self.search_above(end, false),
+            Bound::Excluded(end) => self.search_above(end, true),
+        };
+        // 下界之后没有节点，或者下界在上界之后时，区间为空
+        let next = if start == NIL || (stop != NIL && self.key(start) > self.key(stop)) {
+            stop
+        } else {
+            start
+        };
+        Iter {
+            map: self,
+            next,
+            stop,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn subtree_height(&self, x: NodeId) -> usize {
+        if x == NIL {
+            return 0;
+        }
+        1 + self
+            .subtree_height(self.left(x))
+            .max(self.subtree_height(self.right(x)))
+    }
+
+    pub fn height(&self) -> usize {
+        self.subtree_height(self.root)
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[NIL] = Node::nil();
+        self.free.clear();
+        self.root = NIL;
+        self.size = 0;
+    }
+
+    // 检查父指针、红节点没有红孩子、每条路径黑高相同，返回子树黑高
+    fn check_subtree(&self, x: NodeId) -> Result<usize, String> {
+        if x == NIL {
+            return Ok(1);
+        }
+        for child in [self.left(x), self.right(x)] {
+            if child == NIL {
+                continue;
+            }
+            if self.parent(child) != x {
+                return Err("Parent pointer mismatch.".to_owned());
+            }
+            if self.color(x) == Color::Red && self.color(child) == Color::Red {
+                return Err("Red node has a red child.".to_owned());
+            }
+        }
+        let left = self.check_subtree(self.left(x))?;
+        let right = self.check_subtree(self.right(x))?;
+        if left != right {
+            return Err(format!(
+                "Black height mismatch: left {}, right {}.",
+                left, right
+            ));
+        }
+        Ok(left + usize::from(self.color(x) == Color::Black))
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if self.color(NIL) != Color::Black {
+            return Err("Sentinel is not black.".to_owned());
+        }
+        if self.root != NIL {
+            if self.color(self.root) != Color::Black {
+                return Err("Root is not black.".to_owned());
+            }
+            if self.parent(self.root) != NIL {
+                return Err("Root has a parent.".to_owned());
+            }
+        }
+        self.check_subtree(self.root)?;
+        let mut count = 0;
+        let mut previous: Option<&K> = None;
+        for (key, _) in self.iter() {
+            if previous.is_some_and(|previous| previous >= key) {
+                return Err(format!(
+                    "Keys are not strictly increasing in order at position {}.",
+                    count
+                ));
+            }
+            previous = Some(key);
+            count += 1;
+        }
+        if count != self.size {
+            return Err(format!(
+                "Size mismatch: recorded {}, counted {}.",
+                self.size, count
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    map: &'a RedBlackMap<K, V>,
+    next: NodeId,
+    // 第一个超出上界的节点，没有上界时是哨兵
+    stop: NodeId,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.stop {
+            return None;
+        }
+        let x = self.next;
+        self.next = self.map.next_node(x);
+        Some(self.map.entry(x))
+    }
+}
+
+impl<K, V> Default for RedBlackMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RedBlackMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = RedBlackMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for RedBlackMap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a RedBlackMap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for RedBlackMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for RedBlackMap<K, V> {
+    fn get_name(&self) -> &'static str {
+        "Red-Black"
+    }
+
+    fn get_size(&self) -> usize {
+        self.size
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        RedBlackMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        RedBlackMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        RedBlackMap::remove(self, key)
+    }
+
+    fn min(&self) -> Option<(&K, &V)> {
+        RedBlackMap::min(self)
+    }
+
+    fn max(&self) -> Option<(&K, &V)> {
+        RedBlackMap::max(self)
+    }
+
+    fn pop_min(&mut self) -> Option<(K, V)> {
+        RedBlackMap::pop_min(self)
+    }
+
+    fn pop_max(&mut self) -> Option<(K, V)> {
+        RedBlackMap::pop_max(self)
+    }
+
+    fn height(&self) -> usize {
+        RedBlackMap::height(self)
+    }
+
+    fn get_rotation_count(&self) -> usize {
+        self.rotations
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        RedBlackMap::check_invariants(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+
+    use super::RedBlackMap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(42);
+        let mut map = RedBlackMap::new();
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            match random.below(8) {
+                0..=2 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                3 => assert_eq!(map.pop_min(), expected.pop_first()),
+                4 => assert_eq!(map.pop_max(), expected.pop_last()),
+                _ => assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2)),
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+
+            let (a, b) = (random.below(220), random.below(220));
+            let bound = |key, kind| match kind {
+                0 => Bound::Included(key),
+                1 => Bound::Excluded(key),
+                _ => Bound::Unbounded,
+            };
+            let range = (bound(a, random.below(3)), bound(b, random.below(3)));
+            // BTreeMap 遇到下界大于上界会 panic，这种区间只检查结果为空
+            let empty = match range {
+                (Bound::Included(a), Bound::Included(b)) => a > b,
+                (
+                    Bound::Included(a) | Bound::Excluded(a),
+                    Bound::Included(b) | Bound::Excluded(b),
+                ) => a >= b,
+                _ => false,
+            };
+            if empty {
+                assert_eq!(map.range(range).count(), 0);
+            } else {
+                assert!(map.range(range).eq(expected.range(range)));
+            }
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+        let (start, end) = (150, 50);
+        assert_eq!(map.range(start..end).count(), 0);
+    }
+
+    #[test]
+    fn range_starting_after_the_last_key_is_empty() {
+        let mut map = RedBlackMap::new();
+        map.insert(60, ());
+        let (start, end) = (150, 50);
+        assert_eq!(map.range(start..end).count(), 0);
+        assert_eq!(map.range(start..).count(), 0);
+        assert_eq!(map.range(start..=start).count(), 0);
+        assert_eq!(
+            map.range((Bound::Excluded(60), Bound::Unbounded)).count(),
+            0
+        );
+        assert!(map.range(..=start).map(|(k, _)| *k).eq([60]));
+        let empty: RedBlackMap<i32, ()> = RedBlackMap::new();
+        assert_eq!(empty.range(start..end).count(), 0);
+    }
+}
@@ -0,0 +1,270 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::queue::array_queue::ArrayQueue;
+use crate::stack::array_stack::ArrayStack;
+
+// 各种二叉搜索树的节点只要能给出键、值和左右孩子，查找、前驱后继和区间遍历都可以共用下面的实现
+pub trait SearchNode: Sized {
+    type Key;
+    type Value;
+
+    fn key(&self) -> &Self::Key;
+
+    fn value(&self) -> &Self::Value;
+
+    fn left(&self) -> Option<&Self>;
+
+    fn right(&self) -> Option<&Self>;
+
+    // 同时借出值和两个孩子的可变引用，供 IterMut 使用
+    fn split_mut(&mut self) -> SplitMut<'_, Self>;
+}
+
+pub type SplitMut<'a, N> = (
+    &'a <N as SearchNode>::Key,
+    &'a mut <N as SearchNode>::Value,
+    Option<&'a mut N>,
+    Option<&'a mut N>,
+);
+
+pub fn find<'a, N, Q>(root: Option<&'a N>, key: &Q) -> Option<&'a N>
+where
+    N: SearchNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut current = root;
+    while let Some(node) = current {
+        current = match key.cmp(node.key().borrow()) {
+            Ordering::Less => node.left(),
+            Ordering::Greater => node.right(),
+            Ordering::Equal => return Some(node),
+        };
+    }
+    None
+}
+
+// 从根往下走，往右拐之前记下当前节点，最后记下的就是不超过（或小于）key 的最大节点
+pub fn search_below<'a, N, Q>(root: Option<&'a N>, key: &Q, inclusive: bool) -> Option<&'a N>
+where
+    N: SearchNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut best = None;
+    let mut current = root;
+    while let Some(node) = current {
+        match node.key().borrow().cmp(key) {
+            Ordering::Less => {
+                best = Some(node);
+                current = node.right();
+            }
+            Ordering::Equal if inclusive => return Some(node),
+            _ => current = node.left(),
+        }
+    }
+    best
+}
+
+pub fn search_above<'a, N, Q>(root: Option<&'a N>, key: &Q, inclusive: bool) -> Option<&'a N>
+where
+    N: SearchNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut best = None;
+    let mut current = root;
+    while let Some(node) = current {
+        match node.key().borrow().cmp(key) {
+            Ordering::Greater => {
+                best = Some(node);
+                current = node.left();
+            }
+            Ordering::Equal if inclusive => return Some(node),
+            _ => current = node.right(),
+        }
+    }
+    best
+}
+
+pub fn min_node<N: SearchNode>(root: Option<&N>) -> Option<&N> {
+    let mut node = root?;
+    while let Some(left) = node.left() {
+        node = left;
+    }
+    Some(node)
+}
+
+pub fn max_node<N: SearchNode>(root: Option<&N>) -> Option<&N> {
+    let mut node = root?;
+    while let Some(right) = node.right() {
+        node = right;
+    }
+    Some(node)
+}
+
+pub fn entry<N: SearchNode>(node: &N) -> (&N::Key, &N::Value) {
+    (node.key(), node.value())
+}
+
+// 空树高度为 0，按层统计
+pub fn height<N: SearchNode>(root: Option<&N>) -> usize {
+    let mut height = 0;
+    let mut queue = ArrayQueue::new();
+    queue.extend(root);
+    while !queue.is_empty() {
+        height += 1;
+        for _ in 0..queue.get_size() {
+            let node = queue.dequeue().unwrap();
+            queue.extend(node.left());
+            queue.extend(node.right());
+        }
+    }
+    height
+}
+
+// 只把不小于下界的节点压栈，按中序逐个弹出，遇到第一个超过上界的节点就停止
+pub fn range<'a, N, Q, R>(root: Option<&'a N>, range: R) -> Range<'a, N>
+where
+    N: SearchNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+{
+    let mut stack = ArrayStack::new();
+    let mut current = root;
+    while let Some(node) = current {
+        let above_start = match range.start_bound() {
+            Bound::Unbounded => true,
+            Bound::Included(start) => node.key().borrow() >= start,
+            Bound::Excluded(start) => node.key().borrow() > start,
+        };
+        if above_start {
+            stack.push(node);
+            current = node.left();
+        } else {
+            current = node.right();
+        }
+    }
+    let stop = match range.end_bound() {
+        Bound::Unbounded => None,
+        Bound::Included(end) => search_above(root, end, false),
+        Bound::Excluded(end) => search_above(root, end, true),
+    };
+    // 下界在上界之后时区间为空
+    if let (Some(first), Some(stop)) = (stack.peek(), stop)
+        && Borrow::<Q>::borrow(first.key()) > stop.key().borrow()
+    {
+        stack.clear();
+    }
+    Range { stack, stop }
+}
+
+pub fn iter<N: SearchNode>(root: Option<&N>) -> Range<'_, N> {
+    let mut stack = ArrayStack::new();
+    let mut current = root;
+    while let Some(node) = current {
+        stack.push(node);
+        current = node.left();
+    }
+    Range { stack, stop: None }
+}
+
+pub fn iter_mut<N: SearchNode>(root: Option<&mut N>) -> IterMut<'_, N> {
+    let mut iter = IterMut {
+        stack: ArrayStack::new(),
+    };
+    iter.push_left_spine(root);
+    iter
+}
+
+// 中序遍历严格递增等价于每个节点都满足左小右大，同时核对节点数
+pub fn check_order<N>(root: Option<&N>, size: usize) -> Result<(), String>
+where
+    N: SearchNode,
+    N::Key: Ord,
+{
+    let mut count = 0;
+    let mut previous: Option<&N::Key> = None;
+    for (key, _) in iter(root) {
+        if previous.is_some_and(|previous| previous >= key) {
+            return Err(format!(
+                "Keys are not strictly increasing in order at position {}.",
+                count
+            ));
+        }
+        previous = Some(key);
+        count += 1;
+    }
+    if count != size {
+        return Err(format!(
+            "Size mismatch: recorded {}, counted {}.",
+            size, count
+        ));
+    }
+    Ok(())
+}
+
+pub struct Range<'a, N> {
+    stack: ArrayStack<&'a N>,
+    // 第一个超出上界的节点
+    stop: Option<&'a N>,
+}
+
+impl<'a, N> Iterator for Range<'a, N>
+where
+    N: SearchNode,
+{
+    type Item = (&'a N::Key, &'a N::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if self.stop.is_some_and(|stop| std::ptr::eq(stop, node)) {
+            self.stack.clear();
+            return None;
+        }
+        let mut current = node.right();
+        while let Some(child) = current {
+            self.stack.push(child);
+            current = child.left();
+        }
+        Some(entry(node))
+    }
+}
+
+type Pending<'a, N> = (
+    &'a <N as SearchNode>::Key,
+    &'a mut <N as SearchNode>::Value,
+    Option<&'a mut N>,
+);
+
+pub struct IterMut<'a, N: SearchNode> {
+    // 压栈时就把节点拆开，键和值的借用与右子树的借用互不冲突
+    stack: ArrayStack<Pending<'a, N>>,
+}
+
+impl<'a, N: SearchNode> IterMut<'a, N> {
+    fn push_left_spine(&mut self, mut current: Option<&'a mut N>) {
+        while let Some(node) = current {
+            let (key, value, left, right) = node.split_mut();
+            self.stack.push((key, value, right));
+            current = left;
+        }
+    }
+}
+
+impl<'a, N: SearchNode> Iterator for IterMut<'a, N> {
+    type Item = (&'a N::Key, &'a mut N::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value, right) = self.stack.pop()?;
+        self.push_left_spine(right);
+        Some((key, value))
+    }
+}
@@ -0,0 +1,512 @@
+#![allow(dead_code)]
+
+use std::{borrow::Borrow, cmp::Ordering, fmt::Debug, ops::RangeBounds};
+
+use super::search_tree::{self, IterMut, Range, SearchNode, SplitMut};
+use crate::stack::array_stack::ArrayStack;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+pub struct Node<K, V> {
+    key: K,
+    value: V,
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn update(&mut self) {
+        self.size = 1 + size_of(&self.left) + size_of(&self.right);
+    }
+}
+
+impl<K, V> SearchNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.key,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+fn size_of<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+// 自顶向下伸展：沿查找路径往下走，路过的节点按方向挂到左树（都比目标小）或右树（都比目标大），
+// 一字形时先旋转一次；最后把左右树接到停下的节点两侧。direction 给出目标相对节点键的方向
+fn splay<K, V, F>(mut t: Box<Node<K, V>>, direction: F) -> Box<Node<K, V>>
+where
+    F: Fn(&K) -> Ordering,
+{
+    // 左树沿右链连接，右树沿左链连接，先按顺序存下来最后再接
+    let mut left_pieces = Vec::new();
+    let mut right_pieces = Vec::new();
+    loop {
+        match direction(&t.key) {
+            Ordering::Less => {
+                let Some(mut child) = t.left.take() else {
+                    break;
+                };
+                if direction(&child.key) == Ordering::Less {
+                    t.left = child.right.take();
+                    t.update();
+                    child.right = Some(t);
+                    t = child;
+                    let Some(next) = t.left.take() else {
+                        break;
+                    };
+                    right_pieces.push(t);
+                    t = next;
+                } else {
+                    right_pieces.push(t);
+                    t = child;
+                }
+            }
+            Ordering::Greater => {
+                let Some(mut child) = t.right.take() else {
+                    break;
+                };
+                if direction(&child.key) == Ordering::Greater {
+                    t.right = child.left.take();
+                    t.update();
+                    child.left = Some(t);
+                    t = child;
+                    let Some(next) = t.right.take() else {
+                        break;
+                    };
+                    left_pieces.push(t);
+                    t = next;
+                } else {
+                    left_pieces.push(t);
+                    t = child;
+                }
+            }
+            Ordering::Equal => break,
+        }
+    }
+    let mut left = t.left.take();
+    while let Some(mut piece) = left_pieces.pop() {
+        piece.right = left;
+        piece.update();
+        left = Some(piece);
+    }
+    let mut right = t.right.take();
+    while let Some(mut piece) = right_pieces.pop() {
+        piece.left = right;
+        piece.update();
+        right = Some(piece);
+    }
+    t.left = left;
+    t.right = right;
+    t.update();
+    t
+}
+
+fn splay_key<K, V, Q>(t: Box<Node<K, V>>, key: &Q) -> Box<Node<K, V>>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    splay(t, |node_key| key.cmp(node_key.borrow()))
+}
+
+// 左树的键都小于右树的键：把左树的最大节点伸展到根，它没有右孩子，正好挂上右树
+fn join<K, V>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    let Some(left) = left else {
+        return right;
+    };
+    let mut root = splay(left, |_| Ordering::Greater);
+    root.right = right;
+    root.update();
+    Some(root)
+}
+
+// 伸展树：每次访问都把目标节点旋转到根，单次操作最坏 O(n)，均摊 O(log n)，
+// 频繁访问的键会停留在根附近
+pub struct SplayTree<K, V> {
+    root: Link<K, V>,
+}
+
+impl<K, V> SplayTree<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        SplayTree { root: None }
+    }
+
+    pub fn get_size(&self) -> usize {
+        size_of(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    // 把 key 或查找路径上最后一个节点伸展到根，返回根是否就是 key
+    fn splay_to_root<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.root.take() {
+            None => false,
+            Some(root) => {
+                let root = splay_key(root, key);
+                let found = root.key.borrow() == key;
+                self.root = Some(root);
+                found
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let Some(mut root) = self.root.take() else {
+            self.root = Some(Node::new(key, value));
+            return None;
+        };
+        root = splay_key(root, &key);
+        let mut node = match key.cmp(&root.key) {
+            Ordering::Equal => {
+                let old = std::mem::replace(&mut root.value, value);
+                self.root = Some(root);
+                return Some(old);
+            }
+            Ordering::Less => {
+                let mut node = Node::new(key, value);
+                node.left = root.left.take();
+                root.update();
+                node.right = Some(root);
+                node
+            }
+            Ordering::Greater => {
+                let mut node = Node::new(key, value);
+                node.right = root.right.take();
+                root.update();
+                node.left = Some(root);
+                node
+            }
+        };
+        node.update();
+        self.root = Some(node);
+        None
+    }
+
+    // 查找也会改变树的形状，所以要 &mut self
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.splay_to_root(key) {
+            self.root.as_ref().map(|root| &root.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.splay_to_root(key) {
+            self.root.as_mut().map(|root| &mut root.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.splay_to_root(key)
+    }
+
+    // 不伸展的查找，不影响树的形状
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    // 目标伸展到根后摘掉根，再把左右子树合并
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.splay_to_root(key) {
+            return None;
+        }
+        let mut root = self.root.take().unwrap();
+        self.root = join(root.left.take(), root.right.take());
+        Some((root.key, root.value))
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        let mut root = splay(self.root.take()?, |_| Ordering::Less);
+        self.root = root.right.take();
+        Some((root.key, root.value))
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        let mut root = splay(self.root.take()?, |_| Ordering::Greater);
+        self.root = root.left.take();
+        Some((root.key, root.value))
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    // 键不小于 key 的部分分裂出来作为新的树返回
+    pub fn split_off<Q>(&mut self, key: &Q) -> SplayTree<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let Some(mut root) = self.root.take() else {
+            return SplayTree::new();
+        };
+        root = splay_key(root, key);
+        if root.key.borrow() < key {
+            let right = root.right.take();
+            root.update();
+            self.root = Some(root);
+            SplayTree { root: right }
+        } else {
+            self.root = root.left.take();
+            root.update();
+            SplayTree { root: Some(root) }
+        }
+    }
+
+    // split_off 的逆操作，other 的键必须都大于当前树的键
+    pub fn join(&mut self, mut other: SplayTree<K, V>) -> Result<(), String> {
+        if let (Some((max, _)), Some((min, _))) = (self.max(), other.min())
+            && max >= min
+        {
+            return Err(
+                "Keys of the joined tree must all be greater than the existing keys.".to_owned(),
+            );
+        }
+        self.root = join(self.root.take(), other.root.take());
+        Ok(())
+    }
+
+    pub fn iter(&self) -> Range<'_, Node<K, V>> {
+        search_tree::iter(self.root.as_deref())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        search_tree::range(self.root.as_deref(), range)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Node<K, V>> {
+        search_tree::iter_mut(self.root.as_deref_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn root_key(&self) -> Option<&K> {
+        self.root.as_ref().map(|root| &root.key)
+    }
+
+    pub fn height(&self) -> usize {
+        search_tree::height(self.root.as_deref())
+    }
+
+    pub fn clear(&mut self) {
+        drop_links(self.root.take());
+    }
+
+    // 树可能很深，子树大小逐个节点检查，不用递归
+    pub fn check_invariants(&self) -> Result<(), String> {
+        search_tree::check_order(self.root.as_deref(), self.get_size())?;
+        let mut stack = ArrayStack::new();
+        stack.extend(self.root.as_deref());
+        while let Some(node) = stack.pop() {
+            if node.size != 1 + size_of(&node.left) + size_of(&node.right) {
+                return Err("Subtree size mismatch.".to_owned());
+            }
+            stack.extend(node.left.as_deref());
+            stack.extend(node.right.as_deref());
+        }
+        Ok(())
+    }
+}
+
+// 顺序插入后树是一条长链，逐个节点释放避免递归 drop 爆栈
+fn drop_links<K, V>(root: Link<K, V>) {
+    let mut stack = ArrayStack::new();
+    stack.extend(root);
+    while let Some(mut node) = stack.pop() {
+        stack.extend(node.left.take());
+        stack.extend(node.right.take());
+    }
+}
+
+impl<K, V> Drop for SplayTree<K, V> {
+    fn drop(&mut self) {
+        drop_links(self.root.take());
+    }
+}
+
+impl<K, V> Default for SplayTree<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for SplayTree<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = SplayTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K, V> Extend<(K, V)> for SplayTree<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SplayTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, Node<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for SplayTree<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::SplayTree;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(43);
+        let mut map = SplayTree::new();
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            match random.below(8) {
+                0..=1 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                2 => assert_eq!(map.get(&key), expected.get(&key)),
+                3 => assert_eq!(map.pop_min(), expected.pop_first()),
+                4 => assert_eq!(map.pop_max(), expected.pop_last()),
+                _ => assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2)),
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+    }
+}
@@ -0,0 +1,495 @@
+#![allow(dead_code)]
+
+use std::{
+    borrow::Borrow, cmp::Ordering, collections::hash_map::RandomState, fmt::Debug,
+    hash::BuildHasher, ops::RangeBounds,
+};
+
+use super::search_tree::{self, IterMut, Range, SearchNode, SplitMut};
+
+// xorshift64 生成节点优先级；默认种子取自标准库的随机哈希种子，指定种子时结果可以复现
+pub struct PriorityGenerator {
+    state: u64,
+}
+
+impl PriorityGenerator {
+    pub fn new() -> Self {
+        PriorityGenerator::with_seed(RandomState::new().hash_one(0x5EED_u64))
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        // 全 0 是 xorshift 的不动点
+        let state = if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        };
+        PriorityGenerator { state }
+    }
+
+    pub fn next_priority(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+impl Default for PriorityGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+pub struct Node<K, V> {
+    key: K,
+    value: V,
+    priority: u64,
+    // 子树节点数，分裂后不用重新数就能知道两边的大小
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn update(&mut self) {
+        self.size = 1 + size_of(&self.left) + size_of(&self.right);
+    }
+}
+
+impl<K, V> SearchNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn split_mut(&mut self) -> SplitMut<'_, Self> {
+        (
+            &self.key,
+            &mut self.value,
+            self.left.as_deref_mut(),
+            self.right.as_deref_mut(),
+        )
+    }
+}
+
+fn size_of<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+// 按键分成两棵：左边的键都小于 key，右边的键都不小于 key
+fn split<K, V, Q>(link: Link<K, V>, key: &Q) -> (Link<K, V>, Link<K, V>)
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    if node.key.borrow() < key {
+        let (left, right) = split(node.right.take(), key);
+        node.right = left;
+        node.update();
+        (Some(node), right)
+    } else {
+        let (left, right) = split(node.left.take(), key);
+        node.left = right;
+        node.update();
+        (left, Some(node))
+    }
+}
+
+// 要求 left 的键都小于 right 的键，优先级大的节点当根
+fn merge<K, V>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+fn remove_at<K, V, Q>(link: &mut Link<K, V>, key: &Q) -> Option<(K, V)>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let node = link.as_mut()?;
+    let removed = match key.cmp(node.key.borrow()) {
+        Ordering::Less => remove_at(&mut node.left, key),
+        Ordering::Greater => remove_at(&mut node.right, key),
+        Ordering::Equal => {
+            let mut node = link.take().unwrap();
+            *link = merge(node.left.take(), node.right.take());
+            return Some((node.key, node.value));
+        }
+    };
+    if removed.is_some() {
+        node.update();
+    }
+    removed
+}
+
+fn take_min<K, V>(link: &mut Link<K, V>) -> Option<Box<Node<K, V>>> {
+    let node = link.as_mut()?;
+    if node.left.is_some() {
+        let min = take_min(&mut node.left);
+        node.update();
+        return min;
+    }
+    let mut node = link.take().unwrap();
+    *link = node.right.take();
+    Some(node)
+}
+
+fn take_max<K, V>(link: &mut Link<K, V>) -> Option<Box<Node<K, V>>> {
+    let node = link.as_mut()?;
+    if node.right.is_some() {
+        let max = take_max(&mut node.right);
+        node.update();
+        return max;
+    }
+    let mut node = link.take().unwrap();
+    *link = node.left.take();
+    Some(node)
+}
+
+fn check_heap<K, V>(link: &Link<K, V>) -> Result<(), String> {
+    let Some(node) = link else {
+        return Ok(());
+    };
+    for child in [&node.left, &node.right].into_iter().flatten() {
+        if child.priority > node.priority {
+            return Err("Child priority exceeds parent priority.".to_owned());
+        }
+    }
+    if node.size != 1 + size_of(&node.left) + size_of(&node.right) {
+        return Err("Subtree size mismatch.".to_owned());
+    }
+    check_heap(&node.left)?;
+    check_heap(&node.right)
+}
+
+// 键满足二叉搜索树性质、随机优先级满足大根堆性质，期望树高 O(log n)；
+// 所有修改都可以归结为按键分裂和合并
+pub struct Treap<K, V> {
+    root: Link<K, V>,
+    priorities: PriorityGenerator,
+}
+
+impl<K, V> Treap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Treap {
+            root: None,
+            priorities: PriorityGenerator::new(),
+        }
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Treap {
+            root: None,
+            priorities: PriorityGenerator::with_seed(seed),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        size_of(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(std::mem::replace(old, value));
+        }
+        let node = Box::new(Node {
+            key,
+            value,
+            priority: self.priorities.next_priority(),
+            size: 1,
+            left: None,
+            right: None,
+        });
+        let (left, right) = split(self.root.take(), &node.key);
+        self.root = merge(merge(left, Some(node)), right);
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(search_tree::entry)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    // 找到节点后用它左右子树合并的结果替换它
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove_at(&mut self.root, key)
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        take_min(&mut self.root).map(|node| (node.key, node.value))
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        take_max(&mut self.root).map(|node| (node.key, node.value))
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max_node(self.root.as_deref()).map(search_tree::entry)
+    }
+
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_below(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::search_above(self.root.as_deref(), key, true).map(search_tree::entry)
+    }
+
+    // 键不小于 key 的部分分裂出来作为新的树返回，原树只保留小于 key 的部分
+    pub fn split_off<Q>(&mut self, key: &Q) -> Treap<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, right) = split(self.root.take(), key);
+        self.root = left;
+        Treap {
+            root: right,
+            priorities: PriorityGenerator::with_seed(self.priorities.next_priority()),
+        }
+    }
+
+    // split_off 的逆操作，other 的键必须都大于当前树的键
+    pub fn merge(&mut self, mut other: Treap<K, V>) -> Result<(), String> {
+        if let (Some((max, _)), Some((min, _))) = (self.max(), other.min())
+            && max >= min
+        {
+            return Err(
+                "Keys of the merged treap must all be greater than the existing keys.".to_owned(),
+            );
+        }
+        self.root = merge(self.root.take(), other.root.take());
+        Ok(())
+    }
+
+    pub fn iter(&self) -> Range<'_, Node<K, V>> {
+        search_tree::iter(self.root.as_deref())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        search_tree::range(self.root.as_deref(), range)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Node<K, V>> {
+        search_tree::iter_mut(self.root.as_deref_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn height(&self) -> usize {
+        search_tree::height(self.root.as_deref())
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    pub fn check_invariants(&self) -> Result<(), String> {
+        search_tree::check_order(self.root.as_deref(), self.get_size())?;
+        check_heap(&self.root)
+    }
+}
+
+impl<K, V> Default for Treap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Treap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut treap = Treap::new();
+        treap.extend(iter);
+        treap
+    }
+}
+
+impl<K, V> Extend<(K, V)> for Treap<K, V>
+where
+    K: Ord,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Treap<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, Node<K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for Treap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Treap;
+    use crate::test_util::rng::TestRng;
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(43);
+        let mut map = Treap::with_seed(43);
+        let mut expected = BTreeMap::new();
+        for _ in 0..2000 {
+            let key = random.below(200);
+            match random.below(8) {
+                0..=2 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                3 => assert_eq!(map.pop_min(), expected.pop_first()),
+                4 => assert_eq!(map.pop_max(), expected.pop_last()),
+                _ => assert_eq!(map.insert(key, key * 2), expected.insert(key, key * 2)),
+            }
+            map.check_invariants().unwrap();
+            assert_eq!(map.get_size(), expected.len());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+    }
+
+    #[test]
+    fn split_off_and_merge_keep_invariants() {
+        let mut left: Treap<u32, u32> = (0..100).map(|key| (key, key)).collect();
+        let right = left.split_off(&60);
+        left.check_invariants().unwrap();
+        right.check_invariants().unwrap();
+        assert!(left.keys().copied().eq(0..60));
+        assert!(right.keys().copied().eq(60..100));
+        left.merge(right).unwrap();
+        left.check_invariants().unwrap();
+        assert_eq!(left.get_size(), 100);
+    }
+}
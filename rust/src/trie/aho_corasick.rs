@@ -0,0 +1,467 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use super::sequence_trie::SequenceTrie;
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    // 报告所有匹配，包括互相重叠的
+    Overlapping,
+    // 不重叠；起点最靠左的优先，起点相同时按模式串传入的顺序优先
+    LeftmostFirst,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// 状态就是字典树上的节点，转移按字节有序存放
+struct State {
+    transitions: Vec<(u8, usize)>,
+    fail: usize,
+    // 沿失配链能到达的最近一个有输出的状态
+    output_link: Option<usize>,
+    // 恰好在这个状态结束的模式串编号（升序）
+    outputs: Vec<usize>,
+    depth: usize,
+}
+
+impl State {
+    fn new(depth: usize) -> Self {
+        State {
+            transitions: Vec::new(),
+            fail: ROOT,
+            output_link: None,
+            outputs: Vec::new(),
+            depth,
+        }
+    }
+
+    fn goto(&self, byte: u8) -> Option<usize> {
+        self.transitions
+            .binary_search_by(|(b, _)| b.cmp(&byte))
+            .ok()
+            .map(|index| self.transitions[index].1)
+    }
+}
+
+pub struct AhoCorasick {
+    states: Vec<State>,
+    pattern_lens: Vec<usize>,
+    // 空模式串不进字典树，在每个位置单独报告
+    empty_patterns: Vec<usize>,
+    match_kind: MatchKind,
+    max_pattern_len: usize,
+}
+
+impl AhoCorasick {
+    pub fn new<I, P>(patterns: I, match_kind: MatchKind) -> AhoCorasick
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        // 先把模式串放进字典树，节点上记录在这里结束的模式串编号
+        let mut trie: SequenceTrie<u8, Vec<usize>> = SequenceTrie::new();
+        let mut pattern_lens = Vec::new();
+        let mut empty_patterns = Vec::new();
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            let id = pattern_lens.len();
+            pattern_lens.push(pattern.len());
+            if pattern.is_empty() {
+                empty_patterns.push(id);
+                continue;
+            }
+            // LeftmostFirst 下空模式串在每个起点都能匹配，编号更大的模式串永远不会被选中
+            if match_kind == MatchKind::LeftmostFirst && !empty_patterns.is_empty() {
+                continue;
+            }
+            match trie.get_mut(pattern) {
+                Some(ids) => ids.push(id),
+                None => {
+                    trie.insert(pattern, vec![id]);
+                }
+            }
+        }
+        let mut automaton = AhoCorasick {
+            states: Self::build_states(&trie),
+            max_pattern_len: pattern_lens.iter().copied().max().unwrap_or(0),
+            pattern_lens,
+            empty_patterns,
+            match_kind,
+        };
+        automaton.build_links();
+        automaton
+    }
+
+    // 字典树就是 goto 函数：按层序给节点编号，子节点按字节升序，转移表天然有序
+    fn build_states(trie: &SequenceTrie<u8, Vec<usize>>) -> Vec<State> {
+        let mut states = vec![State::new(0)];
+        let mut queue = VecDeque::from([(trie.root(), ROOT)]);
+        while let Some((node, index)) = queue.pop_front() {
+            for (&byte, child) in node.children() {
+                let next = states.len();
+                let mut state = State::new(states[index].depth + 1);
+                state.outputs = child.value().cloned().unwrap_or_default();
+                states.push(state);
+                states[index].transitions.push((byte, next));
+                queue.push_back((child, next));
+            }
+        }
+        states
+    }
+
+    // 按层序（BFS）计算失配链接和输出链接，父节点的链接一定先于子节点算好
+    fn build_links(&mut self) {
+        let mut queue = VecDeque::new();
+        for &(_, child) in self.states[ROOT].transitions.iter() {
+            queue.push_back(child);
+        }
+        while let Some(current) = queue.pop_front() {
+            let transitions = self.states[current].transitions.clone();
+            for (byte, child) in transitions {
+                let mut fallback = self.states[current].fail;
+                let fail = loop {
+                    if let Some(next) = self.states[fallback].goto(byte) {
+                        break next;
+                    }
+                    if fallback == ROOT {
+                        break ROOT;
+                    }
+                    fallback = self.states[fallback].fail;
+                };
+                self.states[child].fail = fail;
+                self.states[child].output_link = if self.states[fail].outputs.is_empty() {
+                    self.states[fail].output_link
+                } else {
+                    Some(fail)
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    pub fn get_pattern_count(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    pub fn get_state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn get_match_kind(&self) -> MatchKind {
+        self.match_kind
+    }
+
+    fn next_state(&self, mut current: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = self.states[current].goto(byte) {
+                return next;
+            }
+            if current == ROOT {
+                return ROOT;
+            }
+            current = self.states[current].fail;
+        }
+    }
+
+    // 以 end 结尾的所有匹配：当前状态自身的输出加上输出链接上的输出
+    fn collect_matches(&self, state: usize, end: usize, out: &mut Vec<Match>) {
+        let mut current = if self.states[state].outputs.is_empty() {
+            self.states[state].output_link
+        } else {
+            Some(state)
+        };
+        while let Some(index) = current {
+            for &pattern in self.states[index].outputs.iter() {
+                out.push(Match {
+                    pattern,
+                    start: end - self.pattern_lens[pattern],
+                    end,
+                });
+            }
+            current = self.states[index].output_link;
+        }
+    }
+
+    fn collect_empty_matches(&self, position: usize, out: &mut Vec<Match>) {
+        for &pattern in self.empty_patterns.iter() {
+            out.push(Match {
+                pattern,
+                start: position,
+                end: position,
+            });
+        }
+    }
+
+    pub fn stream(&self) -> StreamSearcher<'_> {
+        StreamSearcher {
+            automaton: self,
+            state: ROOT,
+            position: 0,
+            window: VecDeque::new(),
+            candidate: None,
+            next_empty: 0,
+        }
+    }
+
+    pub fn find_all(&self, haystack: &[u8]) -> Vec<Match> {
+        let mut searcher = self.stream();
+        let mut matches = searcher.feed(haystack);
+        matches.extend(searcher.finish());
+        matches
+    }
+
+    pub fn find(&self, haystack: &[u8]) -> Option<Match> {
+        let mut searcher = self.stream();
+        let mut matches = searcher.feed(haystack);
+        if matches.is_empty() {
+            matches = searcher.finish();
+        }
+        matches.into_iter().next()
+    }
+
+    pub fn is_match(&self, haystack: &[u8]) -> bool {
+        if !self.empty_patterns.is_empty() {
+            return true;
+        }
+        let mut state = ROOT;
+        for &byte in haystack {
+            state = self.next_state(state, byte);
+            if !self.states[state].outputs.is_empty() || self.states[state].output_link.is_some() {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// 分块喂入数据的搜索器，返回的区间都是相对整个输入流的字节偏移
+pub struct StreamSearcher<'a> {
+    automaton: &'a AhoCorasick,
+    state: usize,
+    // 下一个字节在整个输入流中的位置
+    position: usize,
+    // 最近的 max_pattern_len 个字节，LeftmostFirst 确定一个匹配后要从它的结尾重新扫描
+    window: VecDeque<u8>,
+    candidate: Option<Match>,
+    // 还没有报告空模式串匹配的最小位置
+    next_empty: usize,
+}
+
+impl<'a> StreamSearcher<'a> {
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Match> {
+        let mut out = Vec::new();
+        for &byte in chunk {
+            self.step(byte, &mut out);
+        }
+        out
+    }
+
+    // 输入结束，把还没确定下来的候选匹配输出
+    pub fn finish(mut self) -> Vec<Match> {
+        let mut out = Vec::new();
+        while let Some(found) = self.candidate.take() {
+            self.accept(found, &mut out);
+        }
+        self.emit_empty_until(self.position + 1, &mut out);
+        out
+    }
+
+    // 报告 [next_empty, limit) 上的空模式串匹配
+    fn emit_empty_until(&mut self, limit: usize, out: &mut Vec<Match>) {
+        while self.next_empty < limit {
+            self.automaton.collect_empty_matches(self.next_empty, out);
+            self.next_empty += 1;
+        }
+    }
+
+    // LeftmostFirst 确定一个匹配：它前面的位置只剩空匹配，之后从它的结尾继续
+    fn accept(&mut self, found: Match, out: &mut Vec<Match>) {
+        self.emit_empty_until(found.start, out);
+        out.push(found);
+        self.next_empty = found.end;
+        self.replay_from(found.end, out);
+    }
+
+    fn step(&mut self, byte: u8, out: &mut Vec<Match>) {
+        let automaton = self.automaton;
+        self.window.push_back(byte);
+        if self.window.len() > automaton.max_pattern_len {
+            self.window.pop_front();
+        }
+        if automaton.match_kind == MatchKind::Overlapping {
+            self.emit_empty_until(self.position + 1, out);
+        }
+        self.state = automaton.next_state(self.state, byte);
+        self.position += 1;
+        if automaton.match_kind == MatchKind::Overlapping {
+            // 结尾相同的匹配按模式串编号排序
+            let mut found = Vec::new();
+            automaton.collect_matches(self.state, self.position, &mut found);
+            automaton.collect_empty_matches(self.position, &mut found);
+            found.sort_unstable_by_key(|m| m.pattern);
+            out.extend(found);
+            self.next_empty = self.position + 1;
+            return;
+        }
+        let mut found = Vec::new();
+        automaton.collect_matches(self.state, self.position, &mut found);
+        for m in found {
+            let better = match self.candidate {
+                None => true,
+                Some(current) => (m.start, m.pattern) < (current.start, current.pattern),
+            };
+            if better {
+                self.candidate = Some(m);
+            }
+        }
+        // 之后出现的匹配起点都不会早于 position - depth，候选可以确定下来了
+        let earliest_future_start = self.position - automaton.states[self.state].depth;
+        match self.candidate {
+            Some(found) if found.start < earliest_future_start => {
+                self.candidate = None;
+                self.accept(found, out);
+            }
+            // 候选和之后的匹配起点都不早于 earliest_future_start，之前的位置只可能是空匹配
+            _ => self.emit_empty_until(earliest_future_start, out),
+        }
+    }
+
+    // 匹配之间不能重叠，从上一个匹配的结尾处重新开始扫描
+    fn replay_from(&mut self, end: usize, out: &mut Vec<Match>) {
+        let skip = self.window.len() - (self.position - end);
+        let replay: Vec<u8> = self.window.iter().skip(skip).copied().collect();
+        self.window.clear();
+        self.state = ROOT;
+        self.position = end;
+        for byte in replay {
+            self.step(byte, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+
+    fn random_bytes(rng: &mut TestRng, max_len: usize) -> Vec<u8> {
+        let len = 1 + rng.index(max_len);
+        (0..len).map(|_| b'a' + rng.below(3) as u8).collect()
+    }
+
+    // 按结尾位置、再按模式串编号排序
+    fn brute_force_overlapping(patterns: &[Vec<u8>], haystack: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for end in 0..=haystack.len() {
+            for (pattern, bytes) in patterns.iter().enumerate() {
+                if haystack[..end].ends_with(bytes) {
+                    matches.push(Match {
+                        pattern,
+                        start: end - bytes.len(),
+                        end,
+                    });
+                }
+            }
+        }
+        matches
+    }
+
+    fn brute_force_leftmost_first(patterns: &[Vec<u8>], haystack: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while start <= haystack.len() {
+            match patterns
+                .iter()
+                .position(|bytes| haystack[start..].starts_with(bytes))
+            {
+                Some(pattern) => {
+                    let end = start + patterns[pattern].len();
+                    matches.push(Match {
+                        pattern,
+                        start,
+                        end,
+                    });
+                    // 空匹配之后从下一个位置继续
+                    start = end.max(start + 1);
+                }
+                None => start += 1,
+            }
+        }
+        matches
+    }
+
+    #[test]
+    fn states_come_from_sequence_trie() {
+        let mut rng = TestRng::with_seed(33);
+        for _ in 0..50 {
+            let patterns: Vec<Vec<u8>> = (0..8).map(|_| random_bytes(&mut rng, 5)).collect();
+            let automaton = AhoCorasick::new(&patterns, MatchKind::Overlapping);
+            let trie: SequenceTrie<u8, ()> = patterns.iter().map(|p| (p.clone(), ())).collect();
+            assert_eq!(automaton.get_state_count(), trie.get_node_count());
+            assert_eq!(automaton.get_pattern_count(), patterns.len());
+        }
+    }
+
+    #[test]
+    fn random_matches_agree_with_brute_force() {
+        let mut rng = TestRng::with_seed(34);
+        for round in 0..200 {
+            let mut patterns: Vec<Vec<u8>> = (0..6).map(|_| random_bytes(&mut rng, 4)).collect();
+            // 一部分轮次混入空模式串
+            if round % 4 == 0 {
+                let index = rng.index(patterns.len());
+                patterns[index].clear();
+            }
+            let haystack = random_bytes(&mut rng, 40);
+
+            let overlapping = AhoCorasick::new(&patterns, MatchKind::Overlapping);
+            let expected = brute_force_overlapping(&patterns, &haystack);
+            assert_eq!(overlapping.find_all(&haystack), expected);
+            assert_eq!(overlapping.is_match(&haystack), !expected.is_empty());
+
+            let leftmost = AhoCorasick::new(&patterns, MatchKind::LeftmostFirst);
+            let expected = brute_force_leftmost_first(&patterns, &haystack);
+            assert_eq!(leftmost.find_all(&haystack), expected);
+            assert_eq!(leftmost.find(&haystack), expected.first().copied());
+            assert_eq!(leftmost.is_match(&haystack), !expected.is_empty());
+
+            // 分块喂入和一次性搜索结果相同
+            let mut searcher = leftmost.stream();
+            let mut streamed = Vec::new();
+            for chunk in haystack.chunks(3) {
+                streamed.extend(searcher.feed(chunk));
+            }
+            streamed.extend(searcher.finish());
+            assert_eq!(streamed, expected);
+        }
+    }
+
+    #[test]
+    fn empty_pattern_matches_every_position() {
+        let spans = |kind: MatchKind, haystack: &[u8]| -> Vec<(usize, usize, usize)> {
+            AhoCorasick::new(["b", ""], kind)
+                .find_all(haystack)
+                .iter()
+                .map(|m| (m.pattern, m.start, m.end))
+                .collect()
+        };
+        let overlapping = vec![(1, 0, 0), (1, 1, 1), (0, 1, 2), (1, 2, 2)];
+        assert_eq!(spans(MatchKind::Overlapping, b"ab"), overlapping);
+        let leftmost = vec![(1, 0, 0), (0, 1, 2), (1, 2, 2)];
+        assert_eq!(spans(MatchKind::LeftmostFirst, b"ab"), leftmost);
+        assert_eq!(spans(MatchKind::LeftmostFirst, b""), vec![(1, 0, 0)]);
+        assert!(AhoCorasick::new([""], MatchKind::Overlapping).is_match(b""));
+    }
+}
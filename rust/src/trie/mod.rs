@@ -0,0 +1,5 @@
+pub mod aho_corasick;
+pub mod radix_tree;
+pub mod sequence_trie;
+pub mod string_trie;
+pub mod ternary_search_tree;
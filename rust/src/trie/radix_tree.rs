@@ -0,0 +1,507 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, ops::Bound, ops::RangeBounds};
+
+// 边上存放压缩后的字节串，同一节点的子边首字节互不相同，并按首字节排序
+struct RadixNode<V> {
+    value: Option<V>,
+    children: Vec<(Vec<u8>, RadixNode<V>)>,
+}
+
+impl<V> RadixNode<V> {
+    fn new() -> Self {
+        RadixNode {
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn find_edge(&self, first: u8) -> Result<usize, usize> {
+        self.children
+            .binary_search_by(|(label, _)| label[0].cmp(&first))
+    }
+
+    fn child(&self, first: u8) -> Option<&(Vec<u8>, RadixNode<V>)> {
+        self.find_edge(first)
+            .ok()
+            .map(|index| &self.children[index])
+    }
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count()
+}
+
+pub struct RadixTree<V> {
+    root: RadixNode<V>,
+    size: usize,
+    node_count: usize,
+}
+
+impl<V> RadixTree<V> {
+    pub fn new() -> Self {
+        RadixTree {
+            root: RadixNode::new(),
+            size: 0,
+            node_count: 1,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn insert(&mut self, key: &[u8], value: V) -> Option<V> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        loop {
+            if rest.is_empty() {
+                let old = node.value.replace(value);
+                if old.is_none() {
+                    self.size += 1;
+                }
+                return old;
+            }
+            let index = match node.find_edge(rest[0]) {
+                Err(index) => {
+                    // 没有共同前缀的边，直接挂一个叶子
+                    let mut leaf = RadixNode::new();
+                    leaf.value = Some(value);
+                    node.children.insert(index, (rest.to_vec(), leaf));
+                    self.node_count += 1;
+                    self.size += 1;
+                    return None;
+                }
+                Ok(index) => index,
+            };
+            let (label, _) = &node.children[index];
+            let common = common_prefix_len(label, rest);
+            if common < label.len() {
+                // 在公共前缀处把边拆成两段，中间插入一个新节点
+                let (old_label, old_child) = node.children.remove(index);
+                let mut middle = RadixNode::new();
+                middle
+                    .children
+                    .push((old_label[common..].to_vec(), old_child));
+                node.children
+                    .insert(index, (old_label[..common].to_vec(), middle));
+                self.node_count += 1;
+            }
+            node = &mut node.children[index].1;
+            rest = &rest[common..];
+        }
+    }
+
+    fn find_node(&self, key: &[u8]) -> Option<&RadixNode<V>> {
+        let mut node = &self.root;
+        let mut rest = key;
+        while !rest.is_empty() {
+            let (label, child) = node.child(rest[0])?;
+            if !rest.starts_with(label) {
+                return None;
+            }
+            node = child;
+            rest = &rest[label.len()..];
+        }
+        Some(node)
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&V> {
+        self.find_node(key)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: &[u8]) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while !rest.is_empty() {
+            let index = node.find_edge(rest[0]).ok()?;
+            let (label, child) = &mut node.children[index];
+            if !rest.starts_with(label) {
+                return None;
+            }
+            rest = &rest[label.len()..];
+            node = child;
+        }
+        node.value.as_mut()
+    }
+
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> Option<V> {
+        let (value, removed_nodes) = Self::remove_from(&mut self.root, key)?;
+        self.size -= 1;
+        self.node_count -= removed_nodes;
+        Some(value)
+    }
+
+    // 回溯时整理子节点：没有值也没有孩子就删掉，只剩一个孩子就和孩子合并成一条边
+    fn remove_from(node: &mut RadixNode<V>, key: &[u8]) -> Option<(V, usize)> {
+        if key.is_empty() {
+            return node.value.take().map(|value| (value, 0));
+        }
+        let index = node.find_edge(key[0]).ok()?;
+        let label_len = node.children[index].0.len();
+        if !key.starts_with(&node.children[index].0) {
+            return None;
+        }
+        let (value, mut removed_nodes) =
+            Self::remove_from(&mut node.children[index].1, &key[label_len..])?;
+        let child = &node.children[index].1;
+        if child.value.is_none() {
+            match child.children.len() {
+                0 => {
+                    node.children.remove(index);
+                    removed_nodes += 1;
+                }
+                1 => {
+                    let (mut label, mut child) = node.children.remove(index);
+                    let (grand_label, grand_child) = child.children.pop().unwrap();
+                    label.extend_from_slice(&grand_label);
+                    node.children.insert(index, (label, grand_child));
+                    removed_nodes += 1;
+                }
+                _ => {}
+            }
+        }
+        Some((value, removed_nodes))
+    }
+
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.prefix_iter(prefix).next().is_some()
+    }
+
+    // 路由表式的最长前缀匹配，返回匹配到的 key 和对应的值
+    pub fn longest_prefix_match<'k>(&self, key: &'k [u8]) -> Option<(&'k [u8], &V)> {
+        let mut node = &self.root;
+        let mut consumed = 0;
+        let mut best = node.value.as_ref().map(|v| (0, v));
+        while consumed < key.len() {
+            let rest = &key[consumed..];
+            let (label, child) = match node.child(rest[0]) {
+                None => break,
+                Some(edge) => edge,
+            };
+            if !rest.starts_with(label) {
+                break;
+            }
+            consumed += label.len();
+            node = child;
+            if let Some(value) = node.value.as_ref() {
+                best = Some((consumed, value));
+            }
+        }
+        best.map(|(len, value)| (&key[..len], value))
+    }
+
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            stack: vec![(&self.root, Vec::new())],
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+        }
+    }
+
+    // prefix 可能停在某条边的中间，此时从这条边的子节点开始遍历
+    pub fn prefix_iter(&self, prefix: &[u8]) -> Iter<'_, V> {
+        let mut node = &self.root;
+        let mut path = Vec::new();
+        let mut rest = prefix;
+        let mut stack = Vec::new();
+        loop {
+            if rest.is_empty() {
+                stack.push((node, path));
+                break;
+            }
+            let (label, child) = match node.child(rest[0]) {
+                None => break,
+                Some(edge) => edge,
+            };
+            let common = common_prefix_len(label, rest);
+            if common == rest.len() || common == label.len() {
+                path.extend_from_slice(label);
+                node = child;
+                rest = &rest[common..];
+            } else {
+                break;
+            }
+        }
+        Iter {
+            stack,
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+        }
+    }
+
+    // 按字典序扫描 range 范围内的 key，跳过整棵都小于下界的子树
+    pub fn range<'k, R>(&self, range: R) -> Iter<'_, V>
+    where
+        R: RangeBounds<&'k [u8]>,
+    {
+        Iter {
+            stack: vec![(&self.root, Vec::new())],
+            start: range.start_bound().map(|b| b.to_vec()),
+            end: range.end_bound().map(|b| b.to_vec()),
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Vec<u8>> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn clear(&mut self) {
+        self.root = RadixNode::new();
+        self.size = 0;
+        self.node_count = 1;
+    }
+
+    // 所有边标签的总字节数
+    pub fn get_label_bytes(&self) -> usize {
+        let mut total = 0;
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            for (label, child) in node.children.iter() {
+                total += label.len();
+                stack.push(child);
+            }
+        }
+        total
+    }
+}
+
+impl<V> Default for RadixTree<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Iter<'a, V> {
+    stack: Vec<(&'a RadixNode<V>, Vec<u8>)>,
+    start: Bound<Vec<u8>>,
+    end: Bound<Vec<u8>>,
+}
+
+impl<'a, V> Iter<'a, V> {
+    fn before_start(&self, path: &[u8]) -> bool {
+        match &self.start {
+            Bound::Unbounded => false,
+            Bound::Included(start) => path < start.as_slice(),
+            Bound::Excluded(start) => path <= start.as_slice(),
+        }
+    }
+
+    fn after_end(&self, path: &[u8]) -> bool {
+        match &self.end {
+            Bound::Unbounded => false,
+            Bound::Included(end) => path > end.as_slice(),
+            Bound::Excluded(end) => path >= end.as_slice(),
+        }
+    }
+
+    // 子树里的 key 都以 path 开头，path 不是下界的前缀且小于下界时整棵子树都可以跳过
+    fn subtree_before_start(&self, path: &[u8]) -> bool {
+        match &self.start {
+            Bound::Unbounded => false,
+            Bound::Included(start) | Bound::Excluded(start) => {
+                !start.starts_with(path) && path < start.as_slice()
+            }
+        }
+    }
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, path)) = self.stack.pop() {
+            if self.subtree_before_start(&path) {
+                continue;
+            }
+            // 先序遍历是按字典序输出的，一旦超过上界后面都不用看了
+            if self.after_end(&path) {
+                self.stack.clear();
+                return None;
+            }
+            for (label, child) in node.children.iter().rev() {
+                let mut child_path = path.clone();
+                child_path.extend_from_slice(label);
+                self.stack.push((child, child_path));
+            }
+            if let Some(value) = node.value.as_ref()
+                && !self.before_start(&path)
+            {
+                return Some((path, value));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V> FromIterator<(&'a [u8], V)> for RadixTree<V> {
+    fn from_iter<I: IntoIterator<Item = (&'a [u8], V)>>(iter: I) -> Self {
+        let mut tree = RadixTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+impl<V> Debug for RadixTree<V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.iter()
+                    .map(|(key, value)| (String::from_utf8_lossy(&key).into_owned(), value)),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        ops::{Bound, RangeBounds},
+    };
+
+    use super::{RadixNode, RadixTree};
+    use crate::test_util::rng::TestRng;
+
+    // 边标签非空、子边首字节严格递增；除根以外没有值的节点至少有两个孩子，否则应该被合并
+    fn check_structure(tree: &RadixTree<u64>) {
+        let mut count = 0;
+        let mut stack: Vec<(&RadixNode<u64>, bool)> = vec![(&tree.root, true)];
+        while let Some((node, is_root)) = stack.pop() {
+            count += 1;
+            if !is_root && node.value.is_none() {
+                assert!(node.children.len() >= 2);
+            }
+            for pair in node.children.windows(2) {
+                assert!(pair[0].0[0] < pair[1].0[0]);
+            }
+            for (label, child) in node.children.iter() {
+                assert!(!label.is_empty());
+                stack.push((child, false));
+            }
+        }
+        assert_eq!(count, tree.get_node_count());
+    }
+
+    fn random_key(random: &mut TestRng) -> Vec<u8> {
+        let len = random.index(7);
+        (0..len).map(|_| b'a' + random.below(3) as u8).collect()
+    }
+
+    fn bound(random: &mut TestRng) -> Bound<Vec<u8>> {
+        match random.below(3) {
+            0 => Bound::Unbounded,
+            1 => Bound::Included(random_key(random)),
+            _ => Bound::Excluded(random_key(random)),
+        }
+    }
+
+    fn as_slice(bound: &Bound<Vec<u8>>) -> Bound<&[u8]> {
+        bound.as_ref().map(|key| key.as_slice())
+    }
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(31);
+        let mut tree = RadixTree::new();
+        let mut expected: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+        for round in 0..3000 {
+            let key = random_key(&mut random);
+            if random.below(3) == 0 {
+                assert_eq!(tree.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(
+                    tree.insert(&key, round),
+                    expected.insert(key.clone(), round)
+                );
+            }
+            assert_eq!(tree.get(&key), expected.get(&key));
+            assert_eq!(tree.get_size(), expected.len());
+            check_structure(&tree);
+
+            // 前缀经常停在某条边的中间
+            let prefix = random_key(&mut random);
+            let found: Vec<(Vec<u8>, &u64)> = tree.prefix_iter(&prefix).collect();
+            let wanted: Vec<(Vec<u8>, &u64)> = expected
+                .iter()
+                .filter(|(k, _)| k.starts_with(&prefix))
+                .map(|(k, v)| (k.clone(), v))
+                .collect();
+            assert_eq!(found, wanted);
+
+            let (start, end) = (bound(&mut random), bound(&mut random));
+            let found: Vec<(Vec<u8>, &u64)> =
+                tree.range((as_slice(&start), as_slice(&end))).collect();
+            let wanted: Vec<(Vec<u8>, &u64)> = expected
+                .iter()
+                .filter(|(k, _)| (as_slice(&start), as_slice(&end)).contains(&k.as_slice()))
+                .map(|(k, v)| (k.clone(), v))
+                .collect();
+            assert_eq!(found, wanted);
+        }
+    }
+
+    #[test]
+    fn split_and_merge_edges() {
+        let mut tree = RadixTree::new();
+        tree.insert(b"romane", 1);
+        assert_eq!((tree.get_node_count(), tree.get_label_bytes()), (2, 6));
+        // 在 "rom" 处拆边：根 -> rom -> {ane, ulus}
+        tree.insert(b"romulus", 2);
+        assert_eq!((tree.get_node_count(), tree.get_label_bytes()), (4, 10));
+        tree.insert(b"rom", 3);
+        assert_eq!(tree.get_node_count(), 4);
+        assert_eq!(tree.prefix_iter(b"romu").count(), 1);
+        assert_eq!(tree.prefix_iter(b"ro").count(), 3);
+        assert_eq!(tree.prefix_iter(b"rox").count(), 0);
+
+        // 中间节点还有两个孩子，只清掉值
+        assert_eq!(tree.remove(b"rom"), Some(3));
+        assert_eq!(tree.get_node_count(), 4);
+        // 只剩一个孩子，和它合并回一条边
+        assert_eq!(tree.remove(b"romulus"), Some(2));
+        assert_eq!((tree.get_node_count(), tree.get_label_bytes()), (2, 6));
+        assert_eq!(tree.remove(b"rom"), None);
+        assert_eq!(
+            tree.longest_prefix_match(b"romanes"),
+            Some((&b"romane"[..], &1))
+        );
+        assert_eq!(tree.remove(b"romane"), Some(1));
+        assert_eq!(tree.get_node_count(), 1);
+    }
+
+    #[test]
+    fn range_skips_subtrees_outside_bounds() {
+        let keys: [&[u8]; 6] = [b"a", b"ab", b"abc", b"b", b"ba", b"c"];
+        let tree: RadixTree<usize> = keys.iter().copied().zip(0..).collect();
+        let collect =
+            |iter: super::Iter<'_, usize>| -> Vec<Vec<u8>> { iter.map(|(key, _)| key).collect() };
+        assert_eq!(
+            collect(tree.range::<std::ops::RangeInclusive<&[u8]>>(b"ab"..=b"ba")),
+            [&b"ab"[..], b"abc", b"b", b"ba"]
+        );
+        assert_eq!(
+            collect(tree.range::<std::ops::Range<&[u8]>>(b"abd"..b"c")),
+            [&b"b"[..], b"ba"]
+        );
+        let (start, end): (&[u8], &[u8]) = (b"c", b"a");
+        assert!(collect(tree.range(start..end)).is_empty());
+    }
+}
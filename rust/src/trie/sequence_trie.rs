@@ -0,0 +1,409 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, mem::size_of};
+
+// 子节点按 key 有序存放在数组里，二分查找定位，遍历时天然就是字典序
+struct TrieNode<K, V> {
+    value: Option<V>,
+    // 以当前节点为根的子树中一共有多少个 key
+    count: usize,
+    children: Vec<(K, TrieNode<K, V>)>,
+}
+
+impl<K: Ord, V> TrieNode<K, V> {
+    fn new() -> Self {
+        TrieNode {
+            value: None,
+            count: 0,
+            children: Vec::new(),
+        }
+    }
+
+    fn child(&self, key: &K) -> Option<&TrieNode<K, V>> {
+        self.children
+            .binary_search_by(|(k, _)| k.cmp(key))
+            .ok()
+            .map(|index| &self.children[index].1)
+    }
+
+    fn child_mut(&mut self, key: &K) -> Option<&mut TrieNode<K, V>> {
+        match self.children.binary_search_by(|(k, _)| k.cmp(key)) {
+            Ok(index) => Some(&mut self.children[index].1),
+            Err(_) => None,
+        }
+    }
+
+    fn child_or_insert(&mut self, key: K) -> &mut TrieNode<K, V> {
+        let index = match self.children.binary_search_by(|(k, _)| k.cmp(&key)) {
+            Ok(index) => index,
+            Err(index) => {
+                self.children.insert(index, (key, TrieNode::new()));
+                index
+            }
+        };
+        &mut self.children[index].1
+    }
+}
+
+// 默认的 drop 沿着子节点递归，很长的 key 会栈溢出，这里用显式栈逐个释放
+impl<K, V> Drop for TrieNode<K, V> {
+    fn drop(&mut self) {
+        let mut stack: Vec<TrieNode<K, V>> =
+            self.children.drain(..).map(|(_, child)| child).collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.children.drain(..).map(|(_, child)| child));
+        }
+    }
+}
+
+// 只读的节点视图，在字典树之上构建其他结构（比如 Aho-Corasick 自动机）时用来逐层遍历
+pub struct NodeRef<'a, K, V> {
+    node: &'a TrieNode<K, V>,
+}
+
+impl<K, V> Clone for NodeRef<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for NodeRef<'_, K, V> {}
+
+impl<'a, K, V> NodeRef<'a, K, V> {
+    pub fn value(&self) -> Option<&'a V> {
+        self.node.value.as_ref()
+    }
+
+    // 按 key 升序
+    pub fn children(&self) -> impl Iterator<Item = (&'a K, NodeRef<'a, K, V>)> + use<'a, K, V> {
+        self.node
+            .children
+            .iter()
+            .map(|(unit, child)| (unit, NodeRef { node: child }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrieStats {
+    pub key_count: usize,
+    pub node_count: usize,
+    pub max_depth: usize,
+    // 根节点加上所有子节点数组已分配的容量，不含 K、V 自身在堆上的内存
+    pub memory_bytes: usize,
+}
+
+pub struct SequenceTrie<K, V> {
+    root: TrieNode<K, V>,
+    node_count: usize,
+}
+
+impl<K, V> SequenceTrie<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new() -> Self {
+        SequenceTrie {
+            root: TrieNode::new(),
+            node_count: 1,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.root.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.count == 0
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn root(&self) -> NodeRef<'_, K, V> {
+        NodeRef { node: &self.root }
+    }
+
+    fn find_node(&self, key: &[K]) -> Option<&TrieNode<K, V>> {
+        let mut current = &self.root;
+        for unit in key {
+            current = current.child(unit)?;
+        }
+        Some(current)
+    }
+
+    pub fn insert(&mut self, key: &[K], value: V) -> Option<V> {
+        let mut created = 0;
+        let mut current = &mut self.root;
+        for unit in key {
+            if current.child(unit).is_none() {
+                created += 1;
+            }
+            current = current.child_or_insert(unit.clone());
+        }
+        let old = current.value.replace(value);
+        self.node_count += created;
+        if old.is_none() {
+            // 新 key，沿路径把计数加一
+            let mut current = &mut self.root;
+            current.count += 1;
+            for unit in key {
+                current = current.child_mut(unit).unwrap();
+                current.count += 1;
+            }
+        }
+        old
+    }
+
+    pub fn get(&self, key: &[K]) -> Option<&V> {
+        self.find_node(key)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: &[K]) -> Option<&mut V> {
+        let mut current = &mut self.root;
+        for unit in key {
+            current = current.child_mut(unit)?;
+        }
+        current.value.as_mut()
+    }
+
+    pub fn contains_key(&self, key: &[K]) -> bool {
+        self.get(key).is_some()
+    }
+
+    // 沿路径逐层把计数减一；第一个计数会变成 0 的子节点下面只剩这一个 key，整条分支直接剪掉。
+    // 用循环而不是递归，很长的 key 也不会栈溢出
+    pub fn remove(&mut self, key: &[K]) -> Option<V> {
+        self.find_node(key)?.value.as_ref()?;
+        let mut current = &mut self.root;
+        current.count -= 1;
+        for (depth, unit) in key.iter().enumerate() {
+            let index = current
+                .children
+                .binary_search_by(|(k, _)| k.cmp(unit))
+                .ok()?;
+            if current.children[index].1.count == 1 {
+                let (_, mut branch) = current.children.remove(index);
+                self.node_count -= Self::count_nodes(&branch);
+                let mut node = &mut branch;
+                for unit in &key[depth + 1..] {
+                    node = node.child_mut(unit)?;
+                }
+                return node.value.take();
+            }
+            current = &mut current.children[index].1;
+            current.count -= 1;
+        }
+        current.value.take()
+    }
+
+    fn count_nodes(node: &TrieNode<K, V>) -> usize {
+        let mut count = 0;
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            count += 1;
+            stack.extend(node.children.iter().map(|(_, child)| child));
+        }
+        count
+    }
+
+    // 是否存在以 prefix 开头的 key
+    pub fn starts_with(&self, prefix: &[K]) -> bool {
+        self.count_prefix(prefix) > 0
+    }
+
+    pub fn count_prefix(&self, prefix: &[K]) -> usize {
+        self.find_node(prefix).map_or(0, |node| node.count)
+    }
+
+    // 已存储的 key 中，作为 key 前缀的最长的那个，返回它的长度和值
+    pub fn longest_prefix_match(&self, key: &[K]) -> Option<(usize, &V)> {
+        let mut current = &self.root;
+        let mut best = current.value.as_ref().map(|v| (0, v));
+        for (depth, unit) in key.iter().enumerate() {
+            current = match current.child(unit) {
+                None => break,
+                Some(child) => child,
+            };
+            if let Some(value) = current.value.as_ref() {
+                best = Some((depth + 1, value));
+            }
+        }
+        best
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: vec![(&self.root, Vec::new())],
+        }
+    }
+
+    // 按字典序遍历所有以 prefix 开头的 key
+    pub fn prefix_iter(&self, prefix: &[K]) -> Iter<'_, K, V> {
+        let stack = match self.find_node(prefix) {
+            None => Vec::new(),
+            Some(node) => vec![(node, prefix.to_vec())],
+        };
+        Iter { stack }
+    }
+
+    pub fn autocomplete(&self, prefix: &[K], limit: usize) -> Vec<Vec<K>> {
+        self.prefix_iter(prefix)
+            .take(limit)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.root = TrieNode::new();
+        self.node_count = 1;
+    }
+
+    pub fn get_stats(&self) -> TrieStats {
+        let mut stats = TrieStats {
+            key_count: self.get_size(),
+            memory_bytes: size_of::<TrieNode<K, V>>(),
+            ..TrieStats::default()
+        };
+        // 用显式栈做深度优先遍历
+        let mut stack = vec![(&self.root, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            stats.node_count += 1;
+            stats.max_depth = stats.max_depth.max(depth);
+            stats.memory_bytes += node.children.capacity() * size_of::<(K, TrieNode<K, V>)>();
+            for (_, child) in node.children.iter() {
+                stack.push((child, depth + 1));
+            }
+        }
+        stats
+    }
+}
+
+impl<K, V> Default for SequenceTrie<K, V>
+where
+    K: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 先序深度优先遍历：父节点的 key 排在子节点前面，兄弟节点逆序入栈保证按字典序弹出
+pub struct Iter<'a, K, V> {
+    stack: Vec<(&'a TrieNode<K, V>, Vec<K>)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Clone,
+{
+    type Item = (Vec<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, path)) = self.stack.pop() {
+            for (unit, child) in node.children.iter().rev() {
+                let mut child_path = path.clone();
+                child_path.push(unit.clone());
+                self.stack.push((child, child_path));
+            }
+            if let Some(value) = node.value.as_ref() {
+                return Some((path, value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> FromIterator<(Vec<K>, V)> for SequenceTrie<K, V>
+where
+    K: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (Vec<K>, V)>>(iter: I) -> Self {
+        let mut trie = SequenceTrie::new();
+        for (key, value) in iter {
+            trie.insert(&key, value);
+        }
+        trie
+    }
+}
+
+impl<K, V> Debug for SequenceTrie<K, V>
+where
+    K: Ord + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::SequenceTrie;
+    use crate::test_util::rng::TestRng;
+
+    // 节点数等于所有 key 的不同前缀个数（含空前缀，即根节点）
+    fn expected_nodes(keys: &BTreeMap<Vec<u8>, u64>) -> usize {
+        let mut prefixes = BTreeSet::new();
+        prefixes.insert(Vec::new());
+        for key in keys.keys() {
+            for end in 1..=key.len() {
+                prefixes.insert(key[..end].to_vec());
+            }
+        }
+        prefixes.len()
+    }
+
+    #[test]
+    fn random_operations_match_btree_map() {
+        let mut random = TestRng::with_seed(30);
+        let mut trie = SequenceTrie::new();
+        let mut expected = BTreeMap::new();
+        for round in 0..3000 {
+            let len = random.index(6);
+            let key: Vec<u8> = (0..len).map(|_| random.below(3) as u8).collect();
+            if random.below(3) == 0 {
+                assert_eq!(trie.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(
+                    trie.insert(&key, round),
+                    expected.insert(key.clone(), round)
+                );
+            }
+            assert_eq!(trie.get(&key), expected.get(&key));
+            assert_eq!(trie.get_size(), expected.len());
+            assert_eq!(trie.get_node_count(), expected_nodes(&expected));
+            assert_eq!(trie.get_stats().node_count, trie.get_node_count());
+
+            let prefix = &key[..random.index(key.len() + 1)];
+            let wanted: Vec<(Vec<u8>, &u64)> = expected
+                .iter()
+                .filter(|(k, _)| k.starts_with(prefix))
+                .map(|(k, v)| (k.clone(), v))
+                .collect();
+            let found: Vec<(Vec<u8>, &u64)> = trie.prefix_iter(prefix).collect();
+            assert_eq!(found, wanted);
+            assert_eq!(trie.count_prefix(prefix), wanted.len());
+            assert_eq!(trie.starts_with(prefix), !wanted.is_empty());
+        }
+        let all: Vec<(Vec<u8>, &u64)> = trie.iter().collect();
+        let wanted: Vec<(Vec<u8>, &u64)> = expected.iter().map(|(k, v)| (k.clone(), v)).collect();
+        assert_eq!(all, wanted);
+    }
+
+    #[test]
+    fn long_keys_do_not_overflow_the_stack() {
+        let mut trie = SequenceTrie::new();
+        let long: Vec<u32> = (0..300_000).collect();
+        trie.insert(&long, 1);
+        trie.insert(&long[..10], 2);
+        assert_eq!(trie.get_stats().max_depth, long.len());
+        assert_eq!(trie.remove(&long), Some(1));
+        assert_eq!(trie.get_node_count(), 11);
+        trie.insert(&long, 3);
+        // 剩下的长链在 drop 时释放
+        drop(trie);
+    }
+}
@@ -0,0 +1,187 @@
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use super::sequence_trie::{SequenceTrie, TrieStats};
+
+// 以 &str 为 key 的字典树，按 char 逐层分支
+pub struct Trie<V> {
+    inner: SequenceTrie<char, V>,
+}
+
+fn to_units(key: &str) -> Vec<char> {
+    key.chars().collect()
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Trie {
+            inner: SequenceTrie::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.inner.get_size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.inner.get_node_count()
+    }
+
+    pub fn get_stats(&self) -> TrieStats {
+        self.inner.get_stats()
+    }
+
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        self.inner.insert(&to_units(key), value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.inner.get(&to_units(key))
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.inner.get_mut(&to_units(key))
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.inner.contains_key(&to_units(key))
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.inner.remove(&to_units(key))
+    }
+
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.inner.starts_with(&to_units(prefix))
+    }
+
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.inner.count_prefix(&to_units(prefix))
+    }
+
+    // 返回 key 中能匹配到的最长的已存储前缀
+    pub fn longest_prefix_match<'k>(&self, key: &'k str) -> Option<(&'k str, &V)> {
+        let (len, value) = self.inner.longest_prefix_match(&to_units(key))?;
+        let end = key.char_indices().nth(len).map_or(key.len(), |(i, _)| i);
+        Some((&key[..end], value))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (String, &V)> {
+        self.inner
+            .iter()
+            .map(|(units, value)| (units.into_iter().collect(), value))
+    }
+
+    pub fn prefix_iter(&self, prefix: &str) -> impl Iterator<Item = (String, &V)> {
+        self.inner
+            .prefix_iter(&to_units(prefix))
+            .map(|(units, value)| (units.into_iter().collect(), value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = String> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    // 按字典序取前 limit 个补全结果
+    pub fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<String> {
+        self.prefix_iter(prefix)
+            .take(limit)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, V> FromIterator<(&'a str, V)> for Trie<V> {
+    fn from_iter<I: IntoIterator<Item = (&'a str, V)>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+        for (key, value) in iter {
+            trie.insert(key, value);
+        }
+        trie
+    }
+}
+
+impl<V> Debug for Trie<V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trie;
+
+    fn sample() -> Trie<usize> {
+        ["tea", "ten", "team", "to", "inn", "in", "i", "tè"]
+            .into_iter()
+            .enumerate()
+            .map(|(value, key)| (key, value))
+            .collect()
+    }
+
+    #[test]
+    fn prefix_search_and_autocomplete() {
+        let trie = sample();
+        assert_eq!(trie.get_size(), 8);
+        let keys: Vec<String> = trie.prefix_iter("te").map(|(key, _)| key).collect();
+        assert_eq!(keys, ["tea", "team", "ten"]);
+        assert_eq!(trie.autocomplete("t", 3), ["tea", "team", "ten"]);
+        assert_eq!(
+            trie.autocomplete("t", 10),
+            ["tea", "team", "ten", "to", "tè"]
+        );
+        assert_eq!(trie.autocomplete("x", 10), Vec::<String>::new());
+        assert_eq!(trie.count_prefix("in"), 2);
+        assert_eq!(trie.count_prefix(""), 8);
+        assert!(trie.starts_with("tè") && !trie.starts_with("tex"));
+        assert_eq!(trie.longest_prefix_match("teams"), Some(("team", &2)));
+        assert_eq!(trie.longest_prefix_match("tèa"), Some(("tè", &7)));
+        assert_eq!(trie.longest_prefix_match("x"), None);
+    }
+
+    #[test]
+    fn remove_prunes_branches_and_updates_stats() {
+        let mut trie = sample();
+        // 根 + t e a m n o è + i n n
+        let stats = trie.get_stats();
+        assert_eq!(stats.key_count, 8);
+        assert_eq!(stats.node_count, 11);
+        assert_eq!(stats.max_depth, 4);
+        assert_eq!(trie.get_node_count(), 11);
+
+        assert_eq!(trie.remove("team"), Some(2));
+        assert_eq!(trie.get_node_count(), 10);
+        assert_eq!(trie.get_stats().max_depth, 3);
+        // 中间节点上的 key 删掉后节点保留
+        assert_eq!(trie.remove("in"), Some(5));
+        assert_eq!(trie.get_node_count(), 10);
+        assert_eq!(trie.remove("in"), None);
+        assert_eq!(trie.remove("inn"), Some(4));
+        assert_eq!(trie.get_node_count(), 8);
+        assert_eq!(trie.remove("te"), None);
+        assert_eq!(trie.get_size(), 5);
+        assert_eq!(trie.get("i"), Some(&6));
+
+        trie.clear();
+        assert!(trie.is_empty());
+        assert_eq!(trie.get_stats().node_count, 1);
+    }
+}
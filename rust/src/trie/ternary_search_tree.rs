@@ -0,0 +1,482 @@
+#![allow(dead_code)]
+
+use std::{fmt::Debug, mem::size_of};
+
+use super::sequence_trie::TrieStats;
+
+type Link<V> = Option<Box<TstNode<V>>>;
+
+// left/right 是同一层上的兄弟（按字符大小组织成 BST），mid 指向下一个字符
+struct TstNode<V> {
+    ch: char,
+    value: Option<V>,
+    left: Link<V>,
+    mid: Link<V>,
+    right: Link<V>,
+}
+
+impl<V> TstNode<V> {
+    fn new(ch: char) -> Box<Self> {
+        Box::new(TstNode {
+            ch,
+            value: None,
+            left: None,
+            mid: None,
+            right: None,
+        })
+    }
+
+    fn is_removable(&self) -> bool {
+        self.value.is_none() && self.mid.is_none()
+    }
+}
+
+// 用显式栈做中序遍历：左兄弟 -> 当前 key -> mid 子树 -> 右兄弟
+enum Visit<'a, V> {
+    Node(&'a TstNode<V>, String),
+    Emit(String, &'a V),
+}
+
+pub struct TernarySearchTree<V> {
+    root: Link<V>,
+    // 空字符串没有对应的节点，单独存放
+    empty_value: Option<V>,
+    size: usize,
+    node_count: usize,
+}
+
+impl<V> TernarySearchTree<V> {
+    pub fn new() -> Self {
+        TernarySearchTree {
+            root: None,
+            empty_value: None,
+            size: 0,
+            node_count: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.node_count
+    }
+
+    // 和 Trie::get_stats 口径一致：深度按字符数计，只有沿 mid 往下才加一；
+    // 没有单独的根节点，内存按自身加上所有装箱节点估算
+    pub fn get_stats(&self) -> TrieStats {
+        let mut stats = TrieStats {
+            key_count: self.size,
+            memory_bytes: size_of::<Self>(),
+            ..TrieStats::default()
+        };
+        let mut stack = Vec::new();
+        stack.extend(self.root.as_deref().map(|node| (node, 1)));
+        while let Some((node, depth)) = stack.pop() {
+            stats.node_count += 1;
+            stats.max_depth = stats.max_depth.max(depth);
+            stats.memory_bytes += size_of::<TstNode<V>>();
+            stack.extend(node.left.as_deref().map(|left| (left, depth)));
+            stack.extend(node.right.as_deref().map(|right| (right, depth)));
+            stack.extend(node.mid.as_deref().map(|mid| (mid, depth + 1)));
+        }
+        stats
+    }
+
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let chars: Vec<char> = key.chars().collect();
+        if chars.is_empty() {
+            let old = self.empty_value.replace(value);
+            if old.is_none() {
+                self.size += 1;
+            }
+            return old;
+        }
+        let mut link = &mut self.root;
+        let mut index = 0;
+        loop {
+            let ch = chars[index];
+            let node = link.get_or_insert_with(|| {
+                self.node_count += 1;
+                TstNode::new(ch)
+            });
+            if ch < node.ch {
+                link = &mut node.left;
+            } else if ch > node.ch {
+                link = &mut node.right;
+            } else if index + 1 < chars.len() {
+                index += 1;
+                link = &mut node.mid;
+            } else {
+                let old = node.value.replace(value);
+                if old.is_none() {
+                    self.size += 1;
+                }
+                return old;
+            }
+        }
+    }
+
+    fn find_node(&self, key: &[char]) -> Option<&TstNode<V>> {
+        let mut current = self.root.as_deref();
+        let mut index = 0;
+        while let Some(node) = current {
+            let ch = key[index];
+            if ch < node.ch {
+                current = node.left.as_deref();
+            } else if ch > node.ch {
+                current = node.right.as_deref();
+            } else if index + 1 < key.len() {
+                index += 1;
+                current = node.mid.as_deref();
+            } else {
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        let chars: Vec<char> = key.chars().collect();
+        if chars.is_empty() {
+            return self.empty_value.as_ref();
+        }
+        self.find_node(&chars)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let chars: Vec<char> = key.chars().collect();
+        if chars.is_empty() {
+            return self.empty_value.as_mut();
+        }
+        let mut current = self.root.as_deref_mut();
+        let mut index = 0;
+        while let Some(node) = current {
+            let ch = chars[index];
+            if ch < node.ch {
+                current = node.left.as_deref_mut();
+            } else if ch > node.ch {
+                current = node.right.as_deref_mut();
+            } else if index + 1 < chars.len() {
+                index += 1;
+                current = node.mid.as_deref_mut();
+            } else {
+                return node.value.as_mut();
+            }
+        }
+        None
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let chars: Vec<char> = key.chars().collect();
+        let value = if chars.is_empty() {
+            self.empty_value.take()?
+        } else {
+            let (value, removed_nodes) = Self::remove_from(&mut self.root, &chars)?;
+            self.node_count -= removed_nodes;
+            value
+        };
+        self.size -= 1;
+        Some(value)
+    }
+
+    fn remove_from(link: &mut Link<V>, key: &[char]) -> Option<(V, usize)> {
+        let node = link.as_mut()?;
+        let ch = key[0];
+        let (value, mut removed_nodes) = if ch < node.ch {
+            Self::remove_from(&mut node.left, key)?
+        } else if ch > node.ch {
+            Self::remove_from(&mut node.right, key)?
+        } else if key.len() > 1 {
+            Self::remove_from(&mut node.mid, &key[1..])?
+        } else {
+            (node.value.take()?, 0)
+        };
+        if node.is_removable() {
+            // 节点已经不再承载任何 key，用左右兄弟替换它（和 BST 删除一样）
+            let mut removed = link.take().unwrap();
+            *link = match (removed.left.take(), removed.right.take()) {
+                (None, None) => None,
+                (Some(left), None) => Some(left),
+                (None, Some(right)) => Some(right),
+                (Some(mut left), Some(right)) => {
+                    let mut rightmost = &mut left;
+                    while rightmost.right.is_some() {
+                        rightmost = rightmost.right.as_mut().unwrap();
+                    }
+                    rightmost.right = Some(right);
+                    Some(left)
+                }
+            };
+            removed_nodes += 1;
+        }
+        Some((value, removed_nodes))
+    }
+
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.prefix_iter(prefix).next().is_some()
+    }
+
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.prefix_iter(prefix).count()
+    }
+
+    pub fn longest_prefix_match<'k>(&self, key: &'k str) -> Option<(&'k str, &V)> {
+        let mut best = self.empty_value.as_ref().map(|v| (0, v));
+        let mut current = self.root.as_deref();
+        let mut chars = key.char_indices().peekable();
+        while let (Some(node), Some(&(offset, ch))) = (current, chars.peek()) {
+            if ch < node.ch {
+                current = node.left.as_deref();
+            } else if ch > node.ch {
+                current = node.right.as_deref();
+            } else {
+                chars.next();
+                if let Some(value) = node.value.as_ref() {
+                    best = Some((offset + ch.len_utf8(), value));
+                }
+                current = node.mid.as_deref();
+            }
+        }
+        best.map(|(len, value)| (&key[..len], value))
+    }
+
+    pub fn iter(&self) -> Iter<'_, V> {
+        let mut stack = Vec::new();
+        if let Some(root) = self.root.as_deref() {
+            stack.push(Visit::Node(root, String::new()));
+        }
+        if let Some(value) = self.empty_value.as_ref() {
+            stack.push(Visit::Emit(String::new(), value));
+        }
+        Iter { stack }
+    }
+
+    pub fn prefix_iter(&self, prefix: &str) -> Iter<'_, V> {
+        let chars: Vec<char> = prefix.chars().collect();
+        if chars.is_empty() {
+            return self.iter();
+        }
+        let mut stack = Vec::new();
+        if let Some(node) = self.find_node(&chars) {
+            if let Some(mid) = node.mid.as_deref() {
+                stack.push(Visit::Node(mid, prefix.to_owned()));
+            }
+            if let Some(value) = node.value.as_ref() {
+                stack.push(Visit::Emit(prefix.to_owned(), value));
+            }
+        }
+        Iter { stack }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = String> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<String> {
+        self.prefix_iter(prefix)
+            .take(limit)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    // '?' 匹配任意一个字符，例如 "c?t" 可以匹配 cat、cut
+    pub fn wildcard_match(&self, pattern: &str) -> Vec<(String, &V)> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut result = Vec::new();
+        if pattern.is_empty() {
+            if let Some(value) = self.empty_value.as_ref() {
+                result.push((String::new(), value));
+            }
+            return result;
+        }
+        // (节点, 当前匹配到 pattern 的位置, 已经走过的路径)
+        let mut stack = Vec::new();
+        if let Some(root) = self.root.as_deref() {
+            stack.push((root, 0, String::new()));
+        }
+        while let Some((node, index, path)) = stack.pop() {
+            let p = pattern[index];
+            let any = p == '?';
+            if let Some(right) = node.right.as_deref()
+                && (any || p > node.ch)
+            {
+                stack.push((right, index, path.clone()));
+            }
+            if any || p == node.ch {
+                let mut next_path = path.clone();
+                next_path.push(node.ch);
+                if index + 1 == pattern.len() {
+                    if let Some(value) = node.value.as_ref() {
+                        result.push((next_path, value));
+                    }
+                } else if let Some(mid) = node.mid.as_deref() {
+                    stack.push((mid, index + 1, next_path));
+                }
+            }
+            if let Some(left) = node.left.as_deref()
+                && (any || p < node.ch)
+            {
+                stack.push((left, index, path));
+            }
+        }
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+        result
+    }
+
+    // 编辑距离不超过 max_distance 的所有 key，按 (距离, key) 排序
+    // 沿 mid 方向每走一个字符就在上一行的基础上算出新的一行 DP，
+    // 一行里的最小值已经超过阈值时，整棵子树都可以剪掉
+    pub fn fuzzy_search(&self, query: &str, max_distance: usize) -> Vec<(String, &V, usize)> {
+        let query: Vec<char> = query.chars().collect();
+        let first_row: Vec<usize> = (0..=query.len()).collect();
+        let mut result = Vec::new();
+        if let Some(value) = self.empty_value.as_ref()
+            && query.len() <= max_distance
+        {
+            result.push((String::new(), value, query.len()));
+        }
+        let mut stack = Vec::new();
+        if let Some(root) = self.root.as_deref() {
+            stack.push((root, first_row, String::new()));
+        }
+        while let Some((node, prev_row, path)) = stack.pop() {
+            // 左右兄弟和当前节点处在同一深度，共用上一行
+            if let Some(left) = node.left.as_deref() {
+                stack.push((left, prev_row.clone(), path.clone()));
+            }
+            if let Some(right) = node.right.as_deref() {
+                stack.push((right, prev_row.clone(), path.clone()));
+            }
+            let mut row = Vec::with_capacity(prev_row.len());
+            row.push(prev_row[0] + 1);
+            for (j, &qc) in query.iter().enumerate() {
+                let cost = if qc == node.ch { 0 } else { 1 };
+                let value = (prev_row[j] + cost)
+                    .min(prev_row[j + 1] + 1)
+                    .min(row[j] + 1);
+                row.push(value);
+            }
+            let mut next_path = path;
+            next_path.push(node.ch);
+            let distance = row[query.len()];
+            if let Some(value) = node.value.as_ref()
+                && distance <= max_distance
+            {
+                result.push((next_path.clone(), value, distance));
+            }
+            if let Some(mid) = node.mid.as_deref()
+                && row.iter().min().copied().unwrap_or(0) <= max_distance
+            {
+                stack.push((mid, row, next_path));
+            }
+        }
+        result.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.0.cmp(&b.0)));
+        result
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.empty_value = None;
+        self.size = 0;
+        self.node_count = 0;
+    }
+}
+
+impl<V> Default for TernarySearchTree<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Iter<'a, V> {
+    stack: Vec<Visit<'a, V>>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(visit) = self.stack.pop() {
+            let (node, path) = match visit {
+                Visit::Emit(key, value) => return Some((key, value)),
+                Visit::Node(node, path) => (node, path),
+            };
+            if let Some(right) = node.right.as_deref() {
+                self.stack.push(Visit::Node(right, path.clone()));
+            }
+            let mut next_path = path.clone();
+            next_path.push(node.ch);
+            if let Some(mid) = node.mid.as_deref() {
+                self.stack.push(Visit::Node(mid, next_path.clone()));
+            }
+            if let Some(value) = node.value.as_ref() {
+                self.stack.push(Visit::Emit(next_path, value));
+            }
+            if let Some(left) = node.left.as_deref() {
+                self.stack.push(Visit::Node(left, path));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V> FromIterator<(&'a str, V)> for TernarySearchTree<V> {
+    fn from_iter<I: IntoIterator<Item = (&'a str, V)>>(iter: I) -> Self {
+        let mut tree = TernarySearchTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+impl<V> Debug for TernarySearchTree<V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rng::TestRng;
+    use crate::trie::string_trie::Trie;
+
+    #[test]
+    fn stats_agree_with_trie() {
+        let mut rng = TestRng::with_seed(32);
+        let mut tree = TernarySearchTree::new();
+        let mut trie = Trie::new();
+        for round in 0..500 {
+            let len = rng.index(6);
+            let key: String = (0..len)
+                .map(|_| (b'a' + rng.below(4) as u8) as char)
+                .collect();
+            if round % 3 == 0 {
+                assert_eq!(tree.remove(&key), trie.remove(&key));
+            } else {
+                assert_eq!(tree.insert(&key, round), trie.insert(&key, round));
+            }
+            let (tst_stats, trie_stats) = (tree.get_stats(), trie.get_stats());
+            assert_eq!(tst_stats.key_count, trie_stats.key_count);
+            assert_eq!(tst_stats.max_depth, trie_stats.max_depth);
+            assert_eq!(tst_stats.node_count, tree.get_node_count());
+        }
+        tree.clear();
+        assert_eq!(tree.get_stats().node_count, 0);
+        assert_eq!(tree.get_stats().max_depth, 0);
+    }
+}